@@ -0,0 +1,125 @@
+use crate::*;
+
+/// Size of the DMG boot ROM, mapped to $0000-$00FF
+pub const BOOT_ROM_SIZE_DMG: usize = 0x100;
+/// Size of the CGB boot ROM, mapped to $0000-$00FF and $0200-$08FF. Bytes
+/// $0100-$01FF are not mapped, the cartridge header is visible there.
+pub const BOOT_ROM_SIZE_CGB: usize = 0x900;
+
+/// Range of the cartridge header which is not covered by the CGB boot ROM
+pub const BOOT_ROM_RANGE_CARTRIDGE_HEADER: std::ops::Range<usize> = 0x0100..0x0200;
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum BootRomError {
+    #[error(
+        "Invalid boot ROM size: {0} bytes, expected {BOOT_ROM_SIZE_DMG} or {BOOT_ROM_SIZE_CGB}"
+    )]
+    InvalidSize(usize),
+    #[error("Boot ROM doesn't match the emulated model {0:?}")]
+    ModelMismatch(EmulatorModel),
+}
+
+/// Boot ROM image, mapped over the cartridge ROM until it's disabled by the
+/// write to [`RegisterBANK`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BootRom {
+    data: Vec<u8>,
+}
+
+impl BootRom {
+    /// Create boot ROM from DMG or CGB image, the model is detected by the
+    /// size.
+    pub fn new(data: impl Into<Vec<u8>>) -> Result<Self, BootRomError> {
+        let data = data.into();
+
+        match data.len() {
+            BOOT_ROM_SIZE_DMG | BOOT_ROM_SIZE_CGB => Ok(Self { data }),
+            size => Err(BootRomError::InvalidSize(size)),
+        }
+    }
+
+    pub fn is_cgb(&self) -> bool {
+        self.data.len() == BOOT_ROM_SIZE_CGB
+    }
+
+    pub fn data(&self) -> &[u8] {
+        &self.data
+    }
+
+    /// Get byte of the boot ROM visible at `address`, `None` if the cartridge
+    /// ROM is visible there.
+    pub fn byte(&self, address: u16) -> Option<&u8> {
+        let address = address as usize;
+        if BOOT_ROM_RANGE_CARTRIDGE_HEADER.contains(&address) {
+            return None;
+        }
+
+        self.data.get(address)
+    }
+}
+
+impl Emulator {
+    /// Start execution from the boot ROM instead of the post-boot state, see
+    /// [`Emulator::set_boot_rom`].
+    pub fn with_boot_rom(mut self, boot_rom: BootRom) -> Result<Self, BootRomError> {
+        self.set_boot_rom(boot_rom)?;
+        Ok(self)
+    }
+
+    /// Map the boot ROM over the cartridge ROM and start execution from
+    /// $0000.
+    ///
+    /// CPU and I/O registers are reset to the power-up state (mostly $00,
+    /// with the LCD turned off), the boot ROM initializes them before handing
+    /// off to the cartridge at $0100.
+    ///
+    /// CGB boot ROM is accepted only by the CGB models, DMG one (also used
+    /// for SGB) only by the others. CGB boot ROM runs in CGB mode, the mode
+    /// of the cartridge is decided when the boot ROM is unmapped.
+    pub fn set_boot_rom(&mut self, boot_rom: BootRom) -> Result<(), BootRomError> {
+        if boot_rom.is_cgb() != self.model.is_cgb() {
+            return Err(BootRomError::ModelMismatch(self.model));
+        }
+        self.cgb_mode = boot_rom.is_cgb();
+        self.boot_rom = Some(boot_rom);
+
+        self.cpu = Cpu::new();
+        self.cpu.accumulator_and_flags.set(0x0000);
+        self.cpu.register_bc.set(0x0000);
+        self.cpu.register_de.set(0x0000);
+        self.cpu.register_hl.set(0x0000);
+        self.cpu.program_counter.0 = 0x0000;
+        self.cpu.stack_pointer.0 = 0x0000;
+
+        self.reset_io_registers();
+
+        Ok(())
+    }
+
+    /// Set I/O registers to the power-up state, before the boot ROM runs
+    fn reset_io_registers(&mut self) {
+        self.io_registers.fill(0x00);
+        self.init_memory();
+        self.reg_reset::<RegisterP1>();
+        self.reset_div();
+
+        // PPU starts from the beginning of the first scanline once the boot
+        // ROM turns the LCD on
+        self.scanline_progress = 0;
+        self.dots_in_current_mode = 0;
+    }
+
+    /// Hand off to the cartridge, called on the write to [`RegisterBANK`].
+    /// CGB switches to the DMG compatibility mode if the cartridge doesn't
+    /// support CGB.
+    pub fn unmap_boot_rom(&mut self) {
+        if self.boot_rom.take().is_some() {
+            self.cgb_mode = self.model.is_cgb() && self.rom.supports_cgb();
+        }
+    }
+
+    /// Check if the boot ROM is mapped over the cartridge ROM
+    pub fn is_boot_rom_mapped(&self) -> bool {
+        self.boot_rom.is_some()
+    }
+}
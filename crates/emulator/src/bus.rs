@@ -0,0 +1,195 @@
+use crate::*;
+
+/// Memory access made by the CPU during single M-cycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum BusActivity {
+    /// No memory access, the CPU is busy with an internal operation.
+    Idle,
+    Read {
+        address: u16,
+        value: u8,
+    },
+    Write {
+        address: u16,
+        value: u8,
+    },
+}
+
+/// Memory bus the [`Cpu`] is connected to.
+///
+/// Every M-cycle the CPU makes single read, single write or no memory access
+/// at all, after that [`Bus::tick`] is called to advance the rest of the
+/// system.
+pub trait Bus {
+    /// Read value as CPU would see it, doesn't advance time.
+    fn read(&mut self, address: u16) -> u8;
+
+    /// Write value as CPU would do it, doesn't advance time.
+    fn write(&mut self, address: u16, value: u8);
+
+    /// Get value without any side effects, e.g. for debuggers.
+    fn peek(&self, address: u16) -> u8;
+
+    /// Advance the rest of the system by single M-cycle, `activity` is the
+    /// memory access made by the CPU during this cycle.
+    fn tick(&mut self, activity: BusActivity);
+
+    /// Number of M-cycles that have passed since the start
+    fn cycles(&self) -> usize;
+
+    /// Handle side effects of STOP instruction.
+    ///
+    /// Returns true if the CPU should enter very low power mode.
+    fn stop(&mut self) -> bool {
+        true
+    }
+
+    /// Called after the CPU has executed an instruction.
+    fn on_after_instruction(&mut self, _cpu: &Cpu, _instruction: Instruction) {}
+
+    /// Called when the CPU is locked by an illegal opcode.
+    fn on_cpu_locked(&mut self, _cpu: &Cpu) {}
+
+    /// Read value from memory as CPU would do it. Takes single M-cycle.
+    fn cpu_read(&mut self, address: u16) -> u8 {
+        let value = self.read(address);
+        self.tick(BusActivity::Read { address, value });
+
+        value
+    }
+
+    /// Write value to memory as CPU would do it. Takes single M-cycle.
+    fn cpu_write(&mut self, address: u16, value: u8) {
+        self.write(address, value);
+        self.tick(BusActivity::Write { address, value });
+    }
+
+    /// Spend single M-cycle without memory access (internal CPU operation).
+    fn cpu_idle(&mut self) {
+        self.tick(BusActivity::Idle);
+    }
+
+    /// Request interrupt by setting corresponding bit in the IF register.
+    fn request_interrupt(&mut self, interrupt: Interrupt) {
+        let reg_if = self.peek(RegisterIF::ADDRESS);
+        self.write(RegisterIF::ADDRESS, reg_if | interrupt.mask());
+    }
+
+    /// Get interrupts which are both requested and enabled (`IE & IF`).
+    fn pending_interrupts(&self) -> u8 {
+        let reg_ie = self.peek(RegisterIE::ADDRESS);
+        let reg_if = self.peek(RegisterIF::ADDRESS);
+
+        reg_ie & reg_if & INTERRUPTS_MASK
+    }
+}
+
+/// Flat 64 KiB RAM without any memory mapped hardware.
+///
+/// Useful to run the [`Cpu`] in isolation, e.g. in tests.
+pub struct FlatBus {
+    pub memory: Box<[u8; 0x10000]>,
+
+    /// Number of M-cycles that have passed since the start
+    pub cycles: usize,
+
+    /// Log of the CPU memory accesses, one entry per M-cycle. Recorded only
+    /// if set to `Some`.
+    pub bus_log: Option<Vec<BusActivity>>,
+}
+
+impl Default for FlatBus {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl FlatBus {
+    pub fn new() -> Self {
+        Self {
+            memory: Box::new([0; 0x10000]),
+            cycles: 0,
+            bus_log: None,
+        }
+    }
+}
+
+impl Bus for FlatBus {
+    fn read(&mut self, address: u16) -> u8 {
+        self.memory[address as usize]
+    }
+
+    fn write(&mut self, address: u16, value: u8) {
+        self.memory[address as usize] = value;
+    }
+
+    fn peek(&self, address: u16) -> u8 {
+        self.memory[address as usize]
+    }
+
+    fn tick(&mut self, activity: BusActivity) {
+        if let Some(bus_log) = &mut self.bus_log {
+            bus_log.push(activity);
+        }
+
+        self.cycles += 1;
+    }
+
+    fn cycles(&self) -> usize {
+        self.cycles
+    }
+}
+
+impl Bus for Emulator {
+    fn read(&mut self, address: u16) -> u8 {
+        self.get(address)
+    }
+
+    fn write(&mut self, address: u16, value: u8) {
+        self.set(address, value);
+    }
+
+    fn peek(&self, address: u16) -> u8 {
+        *self.get_force(address)
+    }
+
+    fn tick(&mut self, activity: BusActivity) {
+        if let Some(bus_log) = &mut self.bus_log {
+            bus_log.push(activity);
+        }
+
+        self.tick_cycles(1);
+    }
+
+    fn cycles(&self) -> usize {
+        self.cycles
+    }
+
+    fn stop(&mut self) -> bool {
+        self.reset_div();
+
+        if self.cgb_mode && self.reg::<RegisterKEY1>().get_switch_armed() {
+            self.switch_speed();
+            return false;
+        }
+
+        true
+    }
+
+    fn on_after_instruction(&mut self, cpu: &Cpu, instruction: Instruction) {
+        if let Some(mut debugger) = self.debugger.take() {
+            // CPU state is copied out while it's running, see `Emulator::with_cpu`
+            self.cpu = *cpu;
+            debugger.on_after_instruction(self, cpu.instruction_register, instruction);
+            self.debugger = Some(debugger);
+        }
+    }
+
+    fn on_cpu_locked(&mut self, cpu: &Cpu) {
+        if let Some(mut debugger) = self.debugger.take() {
+            self.cpu = *cpu;
+            debugger.on_cpu_locked(self, cpu.instruction_register);
+            self.debugger = Some(debugger);
+        }
+    }
+}
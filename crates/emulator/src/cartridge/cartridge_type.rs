@@ -0,0 +1,138 @@
+/// Cartridge hardware, stored at `ROM_ADDRESS_CARTRIDGE_TYPE` of the header.
+#[repr(u8)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CartridgeType {
+    RomOnly = 0x00,
+    Mbc1 = 0x01,
+    Mbc1Ram = 0x02,
+    Mbc1RamBattery = 0x03,
+    Mbc2 = 0x05,
+    Mbc2Battery = 0x06,
+    RomRam = 0x08,
+    RomRamBattery = 0x09,
+    Mmm01 = 0x0B,
+    Mmm01Ram = 0x0C,
+    Mmm01RamBattery = 0x0D,
+    Mbc3TimerBattery = 0x0F,
+    Mbc3TimerRamBattery = 0x10,
+    Mbc3 = 0x11,
+    Mbc3Ram = 0x12,
+    Mbc3RamBattery = 0x13,
+    Mbc5 = 0x19,
+    Mbc5Ram = 0x1A,
+    Mbc5RamBattery = 0x1B,
+    Mbc5Rumble = 0x1C,
+    Mbc5RumbleRam = 0x1D,
+    Mbc5RumbleRamBattery = 0x1E,
+    Mbc6 = 0x20,
+    Mbc7SensorRumbleRamBattery = 0x22,
+    PocketCamera = 0xFC,
+    BandaiTama5 = 0xFD,
+    HuC3 = 0xFE,
+    HuC1RamBattery = 0xFF,
+}
+
+impl CartridgeType {
+    pub fn from_code(code: u8) -> Option<Self> {
+        let cartridge_type = match code {
+            0x00 => Self::RomOnly,
+            0x01 => Self::Mbc1,
+            0x02 => Self::Mbc1Ram,
+            0x03 => Self::Mbc1RamBattery,
+            0x05 => Self::Mbc2,
+            0x06 => Self::Mbc2Battery,
+            0x08 => Self::RomRam,
+            0x09 => Self::RomRamBattery,
+            0x0B => Self::Mmm01,
+            0x0C => Self::Mmm01Ram,
+            0x0D => Self::Mmm01RamBattery,
+            0x0F => Self::Mbc3TimerBattery,
+            0x10 => Self::Mbc3TimerRamBattery,
+            0x11 => Self::Mbc3,
+            0x12 => Self::Mbc3Ram,
+            0x13 => Self::Mbc3RamBattery,
+            0x19 => Self::Mbc5,
+            0x1A => Self::Mbc5Ram,
+            0x1B => Self::Mbc5RamBattery,
+            0x1C => Self::Mbc5Rumble,
+            0x1D => Self::Mbc5RumbleRam,
+            0x1E => Self::Mbc5RumbleRamBattery,
+            0x20 => Self::Mbc6,
+            0x22 => Self::Mbc7SensorRumbleRamBattery,
+            0xFC => Self::PocketCamera,
+            0xFD => Self::BandaiTama5,
+            0xFE => Self::HuC3,
+            0xFF => Self::HuC1RamBattery,
+            _ => return None,
+        };
+
+        Some(cartridge_type)
+    }
+
+    /// Code of the cartridge type in the header, inverse of [`Self::from_code`]
+    pub fn code(self) -> u8 {
+        self as u8
+    }
+
+    /// Check if the cartridge has external RAM
+    pub fn has_ram(self) -> bool {
+        matches!(
+            self,
+            Self::Mbc1Ram
+                | Self::Mbc1RamBattery
+                | Self::Mbc2
+                | Self::Mbc2Battery
+                | Self::RomRam
+                | Self::RomRamBattery
+                | Self::Mmm01Ram
+                | Self::Mmm01RamBattery
+                | Self::Mbc3TimerRamBattery
+                | Self::Mbc3Ram
+                | Self::Mbc3RamBattery
+                | Self::Mbc5Ram
+                | Self::Mbc5RamBattery
+                | Self::Mbc5RumbleRam
+                | Self::Mbc5RumbleRamBattery
+                | Self::Mbc7SensorRumbleRamBattery
+                | Self::PocketCamera
+                | Self::HuC3
+                | Self::HuC1RamBattery
+        )
+    }
+
+    /// Check if the cartridge RAM (or RTC) is battery buffered, so it should
+    /// be saved between sessions
+    pub fn has_battery(self) -> bool {
+        matches!(
+            self,
+            Self::Mbc1RamBattery
+                | Self::Mbc2Battery
+                | Self::RomRamBattery
+                | Self::Mmm01RamBattery
+                | Self::Mbc3TimerBattery
+                | Self::Mbc3TimerRamBattery
+                | Self::Mbc3RamBattery
+                | Self::Mbc5RamBattery
+                | Self::Mbc5RumbleRamBattery
+                | Self::Mbc7SensorRumbleRamBattery
+                | Self::HuC3
+                | Self::HuC1RamBattery
+        )
+    }
+
+    /// Check if the cartridge has real time clock
+    pub fn has_timer(self) -> bool {
+        matches!(self, Self::Mbc3TimerBattery | Self::Mbc3TimerRamBattery)
+    }
+
+    /// Check if the cartridge has rumble motor
+    pub fn has_rumble(self) -> bool {
+        matches!(
+            self,
+            Self::Mbc5Rumble
+                | Self::Mbc5RumbleRam
+                | Self::Mbc5RumbleRamBattery
+                | Self::Mbc7SensorRumbleRamBattery
+        )
+    }
+}
@@ -0,0 +1,241 @@
+use crate::*;
+
+/// Nintendo logo bitmap, must be present at `ROM_RANGE_LOGO` or the boot ROM
+/// locks up.
+pub const NINTENDO_LOGO: [u8; 48] = [
+    0xCE, 0xED, 0x66, 0x66, 0xCC, 0x0D, 0x00, 0x0B, 0x03, 0x73, 0x00, 0x83, 0x00, 0x0C, 0x00, 0x0D,
+    0x00, 0x08, 0x11, 0x1F, 0x88, 0x89, 0x00, 0x0E, 0xDC, 0xCC, 0x6E, 0xE6, 0xDD, 0xDD, 0xD9, 0x99,
+    0xBB, 0xBB, 0x67, 0x63, 0x6E, 0x0E, 0xEC, 0xCC, 0xDD, 0xDC, 0x99, 0x9F, 0xBB, 0xB9, 0x33, 0x3E,
+];
+
+/// Smallest possible ROM, two 16 KiB banks
+pub const ROM_MIN_SIZE: usize = 0x8000;
+
+/// Value of the SGB flag indicating that the game supports SGB functions
+pub const SGB_FLAG_SUPPORTED: u8 = 0x03;
+
+/// CGB support declared by the CGB flag of the header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CgbSupport {
+    /// DMG game, CGB runs it in compatibility mode
+    None,
+    /// Game supports CGB enhancements, but works on DMG too (`$80`)
+    Supported,
+    /// Game works on CGB only (`$C0`)
+    Only,
+}
+
+impl CgbSupport {
+    pub fn from_flag(flag: u8) -> Self {
+        match flag {
+            0xC0 => Self::Only,
+            flag if flag & 0x80 != 0 => Self::Supported,
+            _ => Self::None,
+        }
+    }
+}
+
+/// Region the game is intended to be sold in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Destination {
+    Japan,
+    Overseas,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub enum CartridgeHeaderError {
+    #[error("ROM is too small to contain the header: {0} bytes")]
+    TooSmall(usize),
+    #[error("Unknown cartridge type: {0:#04X}")]
+    UnknownCartridgeType(u8),
+    #[error("Unknown ROM size code: {0:#04X}")]
+    UnknownRomSize(u8),
+    #[error("Unknown RAM size code: {0:#04X}")]
+    UnknownRamSize(u8),
+    #[error("ROM size mismatch: header declares {declared} bytes, but ROM has {actual}")]
+    RomSizeMismatch { declared: usize, actual: usize },
+    #[error("Nintendo logo doesn't match")]
+    InvalidLogo,
+    #[error("Header checksum mismatch: stored {stored:#04X}, computed {computed:#04X}")]
+    HeaderChecksumMismatch { stored: u8, computed: u8 },
+    #[error("Global checksum mismatch: stored {stored:#06X}, computed {computed:#06X}")]
+    GlobalChecksumMismatch { stored: u16, computed: u16 },
+}
+
+/// Cartridge header, located at $0100-$014F of the ROM.
+///
+/// [more info](https://gbdev.io/pandocs/The_Cartridge_Header.html)
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct CartridgeHeader {
+    /// Title of the game in uppercase ASCII, trailing zeros are trimmed
+    pub title: String,
+    pub cgb_support: CgbSupport,
+    pub sgb_support: bool,
+    pub cartridge_type: CartridgeType,
+    /// ROM size in bytes
+    pub rom_size: usize,
+    /// External RAM size in bytes. Always 0 for MBC2, since its RAM is
+    /// built-in and not declared in the header
+    pub ram_size: usize,
+    pub destination: Destination,
+    pub licensee: Licensee,
+    pub version: u8,
+    pub header_checksum: u8,
+    pub global_checksum: u16,
+}
+
+impl CartridgeHeader {
+    /// Parse header and verify it: Nintendo logo, header and global
+    /// checksums and ROM size must match.
+    pub fn parse(data: &[u8]) -> Result<Self, CartridgeHeaderError> {
+        let header = Self::decode(data)?;
+
+        if data.len() != header.rom_size {
+            return Err(CartridgeHeaderError::RomSizeMismatch {
+                declared: header.rom_size,
+                actual: data.len(),
+            });
+        }
+
+        if data[ROM_RANGE_LOGO] != NINTENDO_LOGO {
+            return Err(CartridgeHeaderError::InvalidLogo);
+        }
+
+        let computed = compute_header_checksum(data);
+        if computed != header.header_checksum {
+            return Err(CartridgeHeaderError::HeaderChecksumMismatch {
+                stored: header.header_checksum,
+                computed,
+            });
+        }
+
+        let computed = compute_global_checksum(data);
+        if computed != header.global_checksum {
+            return Err(CartridgeHeaderError::GlobalChecksumMismatch {
+                stored: header.global_checksum,
+                computed,
+            });
+        }
+
+        Ok(header)
+    }
+
+    /// Decode header fields without verifying the logo, checksums and ROM
+    /// size, as the hardware does (only the boot ROM checks the logo and
+    /// header checksum).
+    pub fn decode(data: &[u8]) -> Result<Self, CartridgeHeaderError> {
+        if data.len() < ROM_MIN_SIZE {
+            return Err(CartridgeHeaderError::TooSmall(data.len()));
+        }
+
+        let cgb_flag = data[ROM_ADDRESS_CGB_FLAG];
+        let cgb_support = CgbSupport::from_flag(cgb_flag);
+
+        // on newer cartridges the end of the title area is used for the
+        // manufacturer code and the CGB flag
+        let title = match cgb_support {
+            CgbSupport::None => &data[ROM_RANGE_TITLE],
+            _ => &data[ROM_RANGE_TITLE.start..ROM_RANGE_MANUFACTURER_CODE.start],
+        };
+        let title = title
+            .iter()
+            .take_while(|&&byte| byte != 0)
+            .map(|&byte| if byte.is_ascii() { byte as char } else { '?' })
+            .collect();
+
+        let code = data[ROM_ADDRESS_CARTRIDGE_TYPE];
+        let cartridge_type = CartridgeType::from_code(code)
+            .ok_or(CartridgeHeaderError::UnknownCartridgeType(code))?;
+
+        let code = data[ROM_ADDRESS_ROM_SIZE];
+        let rom_size =
+            rom_size_from_code(code).ok_or(CartridgeHeaderError::UnknownRomSize(code))?;
+
+        let code = data[ROM_ADDRESS_RAM_SIZE];
+        let ram_size =
+            ram_size_from_code(code).ok_or(CartridgeHeaderError::UnknownRamSize(code))?;
+
+        let destination = match data[ROM_ADDRESS_DESTINATION_CODE] {
+            0x00 => Destination::Japan,
+            _ => Destination::Overseas,
+        };
+
+        let licensee = match data[ROM_ADDRESS_OLD_LICENSEE_CODE] {
+            OLD_LICENSEE_CODE_USE_NEW => {
+                let code = &data[ROM_RANGE_NEW_LICENSEE_CODE];
+                Licensee::New([code[0], code[1]])
+            }
+            code => Licensee::Old(code),
+        };
+
+        let global_checksum = &data[ROM_RANGE_GLOBAL_CHECKSUM];
+
+        Ok(Self {
+            title,
+            cgb_support,
+            sgb_support: data[ROM_ADDRESS_SGB_FLAG] == SGB_FLAG_SUPPORTED,
+            cartridge_type,
+            rom_size,
+            ram_size,
+            destination,
+            licensee,
+            version: data[ROM_ADDRESS_MASK_ROM_VERSION_NUMBER],
+            header_checksum: data[ROM_ADDRESS_HEADER_CHECKSUM],
+            global_checksum: u16::from_be_bytes([global_checksum[0], global_checksum[1]]),
+        })
+    }
+
+    /// Amount of 16 KiB ROM banks
+    pub fn rom_banks(&self) -> usize {
+        self.rom_size / MEMORY_SIZE_ROM_BANK_01
+    }
+
+    /// Amount of 8 KiB external RAM banks
+    pub fn ram_banks(&self) -> usize {
+        self.ram_size.div_ceil(MEMORY_SIZE_EXTERNAL_RAM)
+    }
+}
+
+/// Compute header checksum over $0134-$014C, same way as the boot ROM does
+pub fn compute_header_checksum(data: &[u8]) -> u8 {
+    data[ROM_RANGE_TITLE.start..ROM_ADDRESS_HEADER_CHECKSUM]
+        .iter()
+        .fold(0u8, |checksum, &byte| {
+            checksum.wrapping_sub(byte).wrapping_sub(1)
+        })
+}
+
+/// Compute global checksum: sum of all bytes of the ROM except the checksum
+/// itself
+pub fn compute_global_checksum(data: &[u8]) -> u16 {
+    data.iter()
+        .enumerate()
+        .filter(|(address, _)| !ROM_RANGE_GLOBAL_CHECKSUM.contains(address))
+        .fold(0u16, |checksum, (_, &byte)| {
+            checksum.wrapping_add(byte as u16)
+        })
+}
+
+/// ROM size in bytes by the code from the header: 32 KiB << code
+fn rom_size_from_code(code: u8) -> Option<usize> {
+    match code {
+        0x00..=0x08 => Some(ROM_MIN_SIZE << code),
+        _ => None,
+    }
+}
+
+/// External RAM size in bytes by the code from the header
+fn ram_size_from_code(code: u8) -> Option<usize> {
+    let size = match code {
+        0x00 => 0,
+        // unused, but some homebrew declare 2 KiB RAM this way
+        0x01 => 0x800,
+        0x02 => 0x2000,
+        0x03 => 0x8000,
+        0x04 => 0x20000,
+        0x05 => 0x10000,
+        _ => return None,
+    };
+
+    Some(size)
+}
@@ -0,0 +1,147 @@
+use crate::*;
+use enum_dispatch::enum_dispatch;
+
+/// Size of the ROM bank mapped to $0000-$3FFF or $4000-$7FFF
+pub const ROM_BANK_SIZE: usize = 0x4000;
+
+/// Size of the external RAM bank mapped to $A000-$BFFF
+pub const RAM_BANK_SIZE: usize = 0x2000;
+
+/// Memory bank controller of the cartridge (MBC).
+///
+/// Translates CPU addresses to offsets in the ROM and the external RAM and
+/// handles writes to the ROM area, which are used to control the banking.
+/// Offsets wrap around the actual ROM and RAM size.
+#[enum_dispatch]
+#[derive(Debug, Clone)]
+pub enum CartridgeMapper {
+    NoMapper(NoMapper),
+    Mbc1(Mbc1),
+    Mbc2(Mbc2),
+    Mbc3(Mbc3),
+    Mbc5(Mbc5),
+}
+
+impl Default for CartridgeMapper {
+    fn default() -> Self {
+        NoMapper.into()
+    }
+}
+
+impl CartridgeMapper {
+    /// Create mapper for the given cartridge type.
+    ///
+    /// Returns `None` if the mapper is not supported.
+    pub fn new(cartridge_type: CartridgeType) -> Option<Self> {
+        let mapper = match cartridge_type {
+            CartridgeType::RomOnly | CartridgeType::RomRam | CartridgeType::RomRamBattery => {
+                NoMapper.into()
+            }
+            CartridgeType::Mbc1 | CartridgeType::Mbc1Ram | CartridgeType::Mbc1RamBattery => {
+                Mbc1::default().into()
+            }
+            CartridgeType::Mbc2 | CartridgeType::Mbc2Battery => Mbc2::default().into(),
+            CartridgeType::Mbc3 | CartridgeType::Mbc3Ram | CartridgeType::Mbc3RamBattery => {
+                Mbc3::default().into()
+            }
+            CartridgeType::Mbc3TimerBattery | CartridgeType::Mbc3TimerRamBattery => {
+                Mbc3::with_rtc(Rtc::default()).into()
+            }
+            CartridgeType::Mbc5 | CartridgeType::Mbc5Ram | CartridgeType::Mbc5RamBattery => {
+                Mbc5::default().into()
+            }
+            CartridgeType::Mbc5Rumble
+            | CartridgeType::Mbc5RumbleRam
+            | CartridgeType::Mbc5RumbleRamBattery => Mbc5::with_rumble().into(),
+            _ => return None,
+        };
+
+        Some(mapper)
+    }
+
+    /// Real-time clock of the cartridge, if there is one
+    pub fn rtc(&self) -> Option<&Rtc> {
+        match self {
+            Self::Mbc3(mbc3) => mbc3.rtc.as_ref(),
+            _ => None,
+        }
+    }
+
+    pub fn rtc_mut(&mut self) -> Option<&mut Rtc> {
+        match self {
+            Self::Mbc3(mbc3) => mbc3.rtc.as_mut(),
+            _ => None,
+        }
+    }
+
+    /// Check if the rumble motor is turned on
+    pub fn is_rumble_active(&self) -> bool {
+        match self {
+            Self::Mbc5(mbc5) => mbc5.has_rumble && mbc5.rumble,
+            _ => false,
+        }
+    }
+}
+
+#[enum_dispatch(CartridgeMapper)]
+pub trait Mapper {
+    /// Offset in the ROM of the byte visible at `address` ($0000-$7FFF)
+    fn rom_offset(&self, address: u16) -> usize;
+
+    /// Handle CPU write to the ROM area ($0000-$7FFF)
+    fn write_rom(&mut self, address: u16, value: u8);
+
+    /// Offset in the external RAM of the byte visible at `address`
+    /// ($A000-$BFFF), `None` if the RAM is disabled.
+    fn ram_offset(&self, address: u16) -> Option<usize>;
+
+    /// Get byte visible at `address` of the external RAM area ($A000-$BFFF),
+    /// `None` if nothing is mapped there.
+    ///
+    /// Mappers with registers in this area (e.g. RTC) should override it.
+    fn read_ram<'a>(&'a self, ram: &'a [u8], address: u16) -> Option<&'a u8> {
+        if ram.is_empty() {
+            return None;
+        }
+
+        let offset = self.ram_offset(address)?;
+        Some(&ram[offset % ram.len()])
+    }
+
+    /// Handle CPU write to the external RAM area ($A000-$BFFF).
+    ///
+    /// Returns `true` if a byte of the RAM was stored, `false` if the write
+    /// was ignored or went to a register (e.g. RTC).
+    fn write_ram(&mut self, ram: &mut [u8], address: u16, value: u8) -> bool {
+        if ram.is_empty() {
+            return false;
+        }
+
+        let Some(offset) = self.ram_offset(address) else {
+            return false;
+        };
+        let len = ram.len();
+        ram[offset % len] = value;
+
+        true
+    }
+
+    /// Advance the mapper by the given number of 4 MiHz clocks (dots)
+    fn tick(&mut self, _clocks: usize) {}
+}
+
+/// Cartridge without MBC: 32 KiB of ROM and optionally up to 8 KiB of RAM.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct NoMapper;
+
+impl Mapper for NoMapper {
+    fn rom_offset(&self, address: u16) -> usize {
+        address as usize
+    }
+
+    fn write_rom(&mut self, _address: u16, _value: u8) {}
+
+    fn ram_offset(&self, address: u16) -> Option<usize> {
+        Some(address as usize - MEMORY_RANGE_EXTERNAL_RAM.start)
+    }
+}
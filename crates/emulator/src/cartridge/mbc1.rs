@@ -0,0 +1,58 @@
+use crate::*;
+
+/// MBC1: up to 2 MiB of ROM (125 banks) and up to 32 KiB of RAM (4 banks).
+///
+/// [more info](https://gbdev.io/pandocs/MBC1.html)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Mbc1 {
+    pub ram_enabled: bool,
+    /// Lower 5 bits of the ROM bank number (BANK1), 0 is treated as 1
+    pub rom_bank: u8,
+    /// 2-bit register (BANK2), selects the RAM bank or the upper bits of the
+    /// ROM bank number on cartridges with 1 MiB of ROM or more
+    pub bank2: u8,
+    /// Banking mode: if set, BANK2 also applies to $0000-$3FFF and
+    /// $A000-$BFFF
+    pub advanced_banking_mode: bool,
+}
+
+impl Mapper for Mbc1 {
+    fn rom_offset(&self, address: u16) -> usize {
+        let address = address as usize;
+
+        let bank = if address < ROM_BANK_SIZE {
+            if self.advanced_banking_mode {
+                self.bank2 << 5
+            } else {
+                0
+            }
+        } else {
+            self.bank2 << 5 | self.rom_bank.max(1)
+        };
+
+        bank as usize * ROM_BANK_SIZE + address % ROM_BANK_SIZE
+    }
+
+    fn write_rom(&mut self, address: u16, value: u8) {
+        match address {
+            0x0000..=0x1FFF => self.ram_enabled = value & 0x0F == 0x0A,
+            0x2000..=0x3FFF => self.rom_bank = value & 0x1F,
+            0x4000..=0x5FFF => self.bank2 = value & 0x03,
+            _ => self.advanced_banking_mode = value & 0x01 != 0,
+        }
+    }
+
+    fn ram_offset(&self, address: u16) -> Option<usize> {
+        if !self.ram_enabled {
+            return None;
+        }
+
+        let bank = if self.advanced_banking_mode {
+            self.bank2 as usize
+        } else {
+            0
+        };
+
+        Some(bank * RAM_BANK_SIZE + address as usize - MEMORY_RANGE_EXTERNAL_RAM.start)
+    }
+}
@@ -0,0 +1,62 @@
+use crate::*;
+
+/// Size of the built-in RAM, 512 half-bytes
+pub const MBC2_RAM_SIZE: usize = 0x200;
+
+/// MBC2: up to 256 KiB of ROM (16 banks) and built-in 512×4-bit RAM.
+///
+/// Only lower nibble of the RAM cells is used, upper nibble reads as 1s.
+///
+/// [more info](https://gbdev.io/pandocs/MBC2.html)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Mbc2 {
+    pub ram_enabled: bool,
+    /// 4-bit ROM bank number, 0 is treated as 1
+    pub rom_bank: u8,
+}
+
+impl Mapper for Mbc2 {
+    fn rom_offset(&self, address: u16) -> usize {
+        let address = address as usize;
+
+        let bank = if address < ROM_BANK_SIZE {
+            0
+        } else {
+            self.rom_bank.max(1) as usize
+        };
+
+        bank * ROM_BANK_SIZE + address % ROM_BANK_SIZE
+    }
+
+    fn write_rom(&mut self, address: u16, value: u8) {
+        if address >= 0x4000 {
+            return;
+        }
+
+        // bit 8 of the address selects the register
+        if address & 0x0100 == 0 {
+            self.ram_enabled = value & 0x0F == 0x0A;
+        } else {
+            self.rom_bank = value & 0x0F;
+        }
+    }
+
+    fn ram_offset(&self, address: u16) -> Option<usize> {
+        if !self.ram_enabled {
+            return None;
+        }
+
+        // RAM is mirrored across the whole $A000-$BFFF area
+        Some((address as usize - MEMORY_RANGE_EXTERNAL_RAM.start) % MBC2_RAM_SIZE)
+    }
+
+    fn write_ram(&mut self, ram: &mut [u8], address: u16, value: u8) -> bool {
+        let (Some(offset), false) = (self.ram_offset(address), ram.is_empty()) else {
+            return false;
+        };
+        let len = ram.len();
+        ram[offset % len] = value | 0xF0;
+
+        true
+    }
+}
@@ -0,0 +1,112 @@
+use crate::*;
+
+/// First value of the RAM bank register which selects the RTC register
+/// instead of the RAM bank
+pub const MBC3_RTC_REGISTER_SELECT_START: u8 = 0x08;
+
+/// MBC3: up to 2 MiB of ROM (128 banks), up to 32 KiB of RAM (4 banks) and
+/// optional real-time clock.
+///
+/// [more info](https://gbdev.io/pandocs/MBC3.html)
+#[derive(Debug, Clone, Default)]
+pub struct Mbc3 {
+    /// Enables both the RAM and the RTC registers
+    pub ram_enabled: bool,
+    /// 7-bit ROM bank number, 0 is treated as 1
+    pub rom_bank: u8,
+    /// RAM bank number ($00-$07) or RTC register ($08-$0C)
+    pub ram_bank: u8,
+    /// Last value written to the latch register ($6000-$7FFF)
+    pub latch_value: u8,
+    pub rtc: Option<Rtc>,
+}
+
+impl Mbc3 {
+    pub fn with_rtc(rtc: Rtc) -> Self {
+        Self {
+            rtc: Some(rtc),
+            ..Default::default()
+        }
+    }
+
+    /// Index of the selected RTC register, if any
+    fn rtc_register(&self) -> Option<usize> {
+        let register = self.ram_bank.checked_sub(MBC3_RTC_REGISTER_SELECT_START)? as usize;
+        (register < RTC_REGISTERS_COUNT).then_some(register)
+    }
+}
+
+impl Mapper for Mbc3 {
+    fn rom_offset(&self, address: u16) -> usize {
+        let address = address as usize;
+
+        let bank = if address < ROM_BANK_SIZE {
+            0
+        } else {
+            self.rom_bank.max(1) as usize
+        };
+
+        bank * ROM_BANK_SIZE + address % ROM_BANK_SIZE
+    }
+
+    fn write_rom(&mut self, address: u16, value: u8) {
+        match address {
+            0x0000..=0x1FFF => self.ram_enabled = value & 0x0F == 0x0A,
+            0x2000..=0x3FFF => self.rom_bank = value & 0x7F,
+            0x4000..=0x5FFF => self.ram_bank = value & 0x0F,
+            _ => {
+                // writing $00 and then $01 latches the clock
+                if self.latch_value == 0x00 && value == 0x01 {
+                    if let Some(rtc) = &mut self.rtc {
+                        rtc.latch();
+                    }
+                }
+                self.latch_value = value;
+            }
+        }
+    }
+
+    fn ram_offset(&self, address: u16) -> Option<usize> {
+        if !self.ram_enabled || self.ram_bank >= MBC3_RTC_REGISTER_SELECT_START {
+            return None;
+        }
+
+        Some(
+            self.ram_bank as usize * RAM_BANK_SIZE + address as usize
+                - MEMORY_RANGE_EXTERNAL_RAM.start,
+        )
+    }
+
+    fn read_ram<'a>(&'a self, ram: &'a [u8], address: u16) -> Option<&'a u8> {
+        if let Some(register) = self.rtc_register() {
+            let rtc = self.rtc.as_ref().filter(|_| self.ram_enabled)?;
+            return Some(rtc.read(register));
+        }
+
+        let offset = self.ram_offset(address)?;
+        ram.get(offset % ram.len().max(1))
+    }
+
+    fn write_ram(&mut self, ram: &mut [u8], address: u16, value: u8) -> bool {
+        if let Some(register) = self.rtc_register() {
+            if let (Some(rtc), true) = (&mut self.rtc, self.ram_enabled) {
+                rtc.write(register, value);
+            }
+            return false;
+        }
+
+        let (Some(offset), false) = (self.ram_offset(address), ram.is_empty()) else {
+            return false;
+        };
+        let len = ram.len();
+        ram[offset % len] = value;
+
+        true
+    }
+
+    fn tick(&mut self, clocks: usize) {
+        if let Some(rtc) = &mut self.rtc {
+            rtc.tick(clocks);
+        }
+    }
+}
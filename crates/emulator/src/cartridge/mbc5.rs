@@ -0,0 +1,86 @@
+use crate::*;
+
+/// Bit of the RAM bank register which controls the rumble motor on
+/// cartridges with rumble
+pub const MBC5_RUMBLE_BIT: u8 = 0b0000_1000;
+
+/// MBC5: up to 8 MiB of ROM (512 banks), up to 128 KiB of RAM (16 banks) and
+/// optional rumble motor.
+///
+/// [more info](https://gbdev.io/pandocs/MBC5.html)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Mbc5 {
+    pub ram_enabled: bool,
+    /// 9-bit ROM bank number, unlike other MBCs bank 0 can be mapped to
+    /// $4000-$7FFF
+    pub rom_bank: u16,
+    pub ram_bank: u8,
+    /// Cartridge has rumble motor, bit 3 of the RAM bank register controls
+    /// the motor instead of selecting the RAM bank
+    pub has_rumble: bool,
+    pub rumble: bool,
+}
+
+impl Default for Mbc5 {
+    /// Bank 1 is mapped to $4000-$7FFF on power up
+    fn default() -> Self {
+        Self {
+            ram_enabled: false,
+            rom_bank: 1,
+            ram_bank: 0,
+            has_rumble: false,
+            rumble: false,
+        }
+    }
+}
+
+impl Mbc5 {
+    pub fn with_rumble() -> Self {
+        Self {
+            has_rumble: true,
+            ..Default::default()
+        }
+    }
+}
+
+impl Mapper for Mbc5 {
+    fn rom_offset(&self, address: u16) -> usize {
+        let address = address as usize;
+
+        let bank = if address < ROM_BANK_SIZE {
+            0
+        } else {
+            self.rom_bank as usize
+        };
+
+        bank * ROM_BANK_SIZE + address % ROM_BANK_SIZE
+    }
+
+    fn write_rom(&mut self, address: u16, value: u8) {
+        match address {
+            0x0000..=0x1FFF => self.ram_enabled = value == 0x0A,
+            0x2000..=0x2FFF => self.rom_bank = self.rom_bank & 0x100 | value as u16,
+            0x3000..=0x3FFF => self.rom_bank = self.rom_bank & 0xFF | (value as u16 & 0x01) << 8,
+            0x4000..=0x5FFF => {
+                if self.has_rumble {
+                    self.rumble = value & MBC5_RUMBLE_BIT != 0;
+                    self.ram_bank = value & 0x07;
+                } else {
+                    self.ram_bank = value & 0x0F;
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn ram_offset(&self, address: u16) -> Option<usize> {
+        if !self.ram_enabled {
+            return None;
+        }
+
+        Some(
+            self.ram_bank as usize * RAM_BANK_SIZE + address as usize
+                - MEMORY_RANGE_EXTERNAL_RAM.start,
+        )
+    }
+}
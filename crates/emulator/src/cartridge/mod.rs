@@ -0,0 +1,19 @@
+mod cartridge_type;
+mod header;
+mod licensee;
+mod mapper;
+mod mbc1;
+mod mbc2;
+mod mbc3;
+mod mbc5;
+mod rtc;
+
+pub use cartridge_type::*;
+pub use header::*;
+pub use licensee::*;
+pub use mapper::*;
+pub use mbc1::*;
+pub use mbc2::*;
+pub use mbc3::*;
+pub use mbc5::*;
+pub use rtc::*;
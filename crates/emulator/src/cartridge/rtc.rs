@@ -0,0 +1,278 @@
+use std::fmt::Debug;
+use std::sync::Arc;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Frequency of the emulated time, RTC advances by one second every
+/// 4194304 dots regardless of the CPU speed.
+pub const RTC_CLOCKS_PER_SECOND: usize = 4_194_304;
+
+pub const RTC_SECONDS: usize = 0;
+pub const RTC_MINUTES: usize = 1;
+pub const RTC_HOURS: usize = 2;
+/// Lower 8 bits of the day counter
+pub const RTC_DAY_LOW: usize = 3;
+/// Bit 0 - bit 8 of the day counter, bit 6 - halt, bit 7 - day counter carry
+pub const RTC_DAY_HIGH: usize = 4;
+pub const RTC_REGISTERS_COUNT: usize = 5;
+
+/// Writable bits of the RTC registers
+pub const RTC_REGISTER_MASKS: [u8; RTC_REGISTERS_COUNT] = [0x3F, 0x3F, 0x1F, 0xFF, 0xC1];
+
+pub const RTC_DAY_HIGH_DAY: u8 = 0b0000_0001;
+pub const RTC_DAY_HIGH_HALT: u8 = 0b0100_0000;
+pub const RTC_DAY_HIGH_CARRY: u8 = 0b1000_0000;
+
+/// Size of the RTC footer appended to the save file (BGB/VBA format):
+/// running and latched registers as 32-bit values followed by 64-bit UNIX
+/// timestamp.
+pub const RTC_SAVE_FOOTER_SIZE: usize = 48;
+/// Older version of the footer with 32-bit timestamp
+pub const RTC_SAVE_FOOTER_SIZE_SHORT: usize = 44;
+
+const SECONDS_PER_DAY: u64 = 24 * 60 * 60;
+const DAYS_COUNT: u64 = 512;
+
+/// Source of time for the RTC.
+///
+/// Only difference between readings is used, so the value can be counted
+/// from any point in time.
+pub trait RtcClock: Debug {
+    /// Current time in seconds
+    fn now(&self) -> u64;
+}
+
+/// Host wall-clock time
+#[derive(Debug, Clone, Copy, Default)]
+pub struct SystemClock;
+
+impl RtcClock for SystemClock {
+    fn now(&self) -> u64 {
+        SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map_or(0, |duration| duration.as_secs())
+    }
+}
+
+/// Real-time clock of the cartridge (MBC3).
+///
+/// By default it advances with emulated time (see [`Rtc::tick`]), optionally
+/// it can follow external clock (see [`Rtc::set_clock`]).
+///
+/// [more info](https://gbdev.io/pandocs/MBC3.html#the-clock-counter-registers)
+#[derive(Debug, Clone, Default)]
+pub struct Rtc {
+    /// Running counter registers
+    pub registers: [u8; RTC_REGISTERS_COUNT],
+    /// Registers visible to the CPU, updated by the latch sequence
+    pub latched: [u8; RTC_REGISTERS_COUNT],
+    /// Progress to the next second in 4 MiHz clocks (emulated time only)
+    pub sub_second_clocks: usize,
+    /// External source of time, `None` for emulated time
+    clock: Option<Arc<dyn RtcClock>>,
+    /// Last reading of the external clock
+    last_clock_time: u64,
+}
+
+impl Rtc {
+    /// Follow the given clock instead of emulated time
+    pub fn set_clock(&mut self, clock: Arc<dyn RtcClock>) {
+        self.last_clock_time = clock.now();
+        self.clock = Some(clock);
+    }
+
+    /// Go back to emulated time
+    pub fn remove_clock(&mut self) {
+        self.clock = None;
+    }
+
+    pub fn is_halted(&self) -> bool {
+        self.registers[RTC_DAY_HIGH] & RTC_DAY_HIGH_HALT != 0
+    }
+
+    /// Day counter (0-511)
+    pub fn days(&self) -> u16 {
+        let high = (self.registers[RTC_DAY_HIGH] & RTC_DAY_HIGH_DAY) as u16;
+        high << 8 | self.registers[RTC_DAY_LOW] as u16
+    }
+
+    /// Advance emulated time by the given number of 4 MiHz clocks (dots).
+    ///
+    /// Ignored if the RTC follows external clock.
+    pub fn tick(&mut self, clocks: usize) {
+        if self.clock.is_some() || self.is_halted() {
+            return;
+        }
+
+        self.sub_second_clocks += clocks;
+        let seconds = self.sub_second_clocks / RTC_CLOCKS_PER_SECOND;
+        self.sub_second_clocks %= RTC_CLOCKS_PER_SECOND;
+
+        self.advance(seconds as u64);
+    }
+
+    /// Catch up with the external clock, if there is one
+    pub fn sync(&mut self) {
+        let Some(clock) = &self.clock else {
+            return;
+        };
+
+        let now = clock.now();
+        let elapsed = now.saturating_sub(self.last_clock_time);
+        self.last_clock_time = now;
+
+        self.advance(elapsed);
+    }
+
+    /// Current time of the external clock or the host time for emulated time
+    fn now(&self) -> u64 {
+        match &self.clock {
+            Some(clock) => clock.now(),
+            None => SystemClock.now(),
+        }
+    }
+
+    /// Serialize registers to the save file footer, see
+    /// [`RTC_SAVE_FOOTER_SIZE`]
+    pub fn save_footer(&self) -> Vec<u8> {
+        let mut rtc = self.clone();
+        rtc.sync();
+
+        let mut footer = Vec::with_capacity(RTC_SAVE_FOOTER_SIZE);
+        for register in rtc.registers.iter().chain(rtc.latched.iter()) {
+            footer.extend_from_slice(&(*register as u32).to_le_bytes());
+        }
+        footer.extend_from_slice(&rtc.now().to_le_bytes());
+
+        footer
+    }
+
+    /// Restore registers from the save file footer and advance them by the
+    /// time passed since the save.
+    ///
+    /// Returns `false` if the footer size is invalid.
+    pub fn load_footer(&mut self, footer: &[u8]) -> bool {
+        let timestamp = match footer.len() {
+            RTC_SAVE_FOOTER_SIZE => u64::from_le_bytes(footer[40..48].try_into().unwrap()),
+            RTC_SAVE_FOOTER_SIZE_SHORT => {
+                u32::from_le_bytes(footer[40..44].try_into().unwrap()) as u64
+            }
+            _ => return false,
+        };
+
+        let mut values = footer[..40]
+            .chunks_exact(4)
+            .map(|value| u32::from_le_bytes(value.try_into().unwrap()) as u8);
+        for (register, value) in values.by_ref().take(RTC_REGISTERS_COUNT).enumerate() {
+            self.registers[register] = value & RTC_REGISTER_MASKS[register];
+        }
+        for (register, value) in values.enumerate() {
+            self.latched[register] = value & RTC_REGISTER_MASKS[register];
+        }
+
+        let now = self.now();
+        self.last_clock_time = now;
+        self.sub_second_clocks = 0;
+        self.advance(now.saturating_sub(timestamp));
+
+        true
+    }
+
+    /// Copy running registers to the latched ones
+    pub fn latch(&mut self) {
+        self.sync();
+        self.latched = self.registers;
+    }
+
+    /// Read latched register
+    pub fn read(&self, register: usize) -> &u8 {
+        &self.latched[register]
+    }
+
+    /// Write to the running register, value is also visible without latching
+    pub fn write(&mut self, register: usize, value: u8) {
+        self.sync();
+
+        let value = value & RTC_REGISTER_MASKS[register];
+        self.registers[register] = value;
+        self.latched[register] = value;
+
+        if register == RTC_SECONDS {
+            self.sub_second_clocks = 0;
+        }
+    }
+
+    /// Advance running registers by the given number of seconds, nothing
+    /// happens if the RTC is halted.
+    pub fn advance(&mut self, mut seconds: u64) {
+        if self.is_halted() {
+            return;
+        }
+
+        // out of range values are incremented until they overflow the
+        // register width, so they are handled second by second
+        while seconds > 0 && !self.is_in_range() {
+            self.tick_second();
+            seconds -= 1;
+        }
+
+        if seconds == 0 {
+            return;
+        }
+
+        let [s, m, h, ..] = self.registers.map(u64::from);
+        let total = s + m * 60 + h * 3600 + self.days() as u64 * SECONDS_PER_DAY + seconds;
+        let time_of_day = total % SECONDS_PER_DAY;
+        let days = total / SECONDS_PER_DAY;
+
+        self.registers[RTC_SECONDS] = (time_of_day % 60) as u8;
+        self.registers[RTC_MINUTES] = (time_of_day / 60 % 60) as u8;
+        self.registers[RTC_HOURS] = (time_of_day / 3600) as u8;
+        self.set_days(days);
+    }
+
+    fn is_in_range(&self) -> bool {
+        self.registers[RTC_SECONDS] < 60
+            && self.registers[RTC_MINUTES] < 60
+            && self.registers[RTC_HOURS] < 24
+    }
+
+    fn tick_second(&mut self) {
+        if !self.tick_counter(RTC_SECONDS, 60) {
+            return;
+        }
+        if !self.tick_counter(RTC_MINUTES, 60) {
+            return;
+        }
+        if !self.tick_counter(RTC_HOURS, 24) {
+            return;
+        }
+
+        self.set_days(self.days() as u64 + 1);
+    }
+
+    /// Increment register, returns `true` if the next counter should be
+    /// incremented. Values above `limit` overflow silently.
+    fn tick_counter(&mut self, register: usize, limit: u8) -> bool {
+        let value = (self.registers[register] + 1) & RTC_REGISTER_MASKS[register];
+        if value == limit {
+            self.registers[register] = 0;
+            return true;
+        }
+
+        self.registers[register] = value;
+        false
+    }
+
+    /// Set day counter, carry flag is set on overflow and stays set until
+    /// cleared by the game.
+    fn set_days(&mut self, days: u64) {
+        let mut day_high = self.registers[RTC_DAY_HIGH] & !RTC_DAY_HIGH_DAY;
+        if days >= DAYS_COUNT {
+            day_high |= RTC_DAY_HIGH_CARRY;
+        }
+
+        let days = days % DAYS_COUNT;
+        self.registers[RTC_DAY_LOW] = days as u8;
+        self.registers[RTC_DAY_HIGH] = day_high | (days >> 8) as u8;
+    }
+}
@@ -0,0 +1,44 @@
+use crate::*;
+
+/// VBK: VRAM bank (CGB Mode only)
+///
+/// Bit 0 selects VRAM bank mapped to $8000-$9FFF, other bits read as 1.
+#[derive(Copy, Clone, ControlRegister)]
+#[register(address = 0xFF4F)]
+pub struct RegisterVBK(pub u8);
+
+impl Default for RegisterVBK {
+    fn default() -> Self {
+        RegisterVBK(0xFE)
+    }
+}
+
+impl RegisterVBK {
+    pub const BANK: u8 = 0b0000_0001;
+
+    pub fn bank(self) -> usize {
+        (self.0 & Self::BANK) as usize
+    }
+}
+
+/// SVBK: WRAM bank (CGB Mode only)
+///
+/// Bits 0-2 select WRAM bank mapped to $D000-$DFFF, 0 is treated as 1.
+/// Other bits read as 1.
+#[derive(Copy, Clone, ControlRegister)]
+#[register(address = 0xFF70)]
+pub struct RegisterSVBK(pub u8);
+
+impl Default for RegisterSVBK {
+    fn default() -> Self {
+        RegisterSVBK(0xF8)
+    }
+}
+
+impl RegisterSVBK {
+    pub const BANK: u8 = 0b0000_0111;
+
+    pub fn bank(self) -> usize {
+        ((self.0 & Self::BANK) as usize).max(1)
+    }
+}
@@ -0,0 +1,14 @@
+use crate::*;
+
+/// BANK: Boot ROM mapping control
+///
+/// Writing non-zero value unmaps the boot ROM, it can't be mapped back.
+#[derive(Copy, Clone, ControlRegister)]
+#[register(address = 0xFF50)]
+pub struct RegisterBANK(pub u8);
+
+impl Default for RegisterBANK {
+    fn default() -> Self {
+        RegisterBANK(0xFF)
+    }
+}
@@ -0,0 +1,9 @@
+use crate::*;
+
+/// DMA: OAM DMA source address & start
+///
+/// Writing to this register starts copying 160 bytes from `$XX00-$XX9F` to
+/// OAM, see [`OamDma`].
+#[derive(Default, Copy, Clone, ControlRegister)]
+#[register(address = 0xFF46)]
+pub struct RegisterDMA(pub u8);
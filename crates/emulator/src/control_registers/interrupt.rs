@@ -2,8 +2,10 @@ use crate::*;
 use bit_flag::{bit_flag, flag_mask};
 
 /// Interrupt enable
+///
+/// Located outside of the I/O range, backed by `interrupt_enable_register`.
 #[derive(Default, Copy, Clone, ControlRegister)]
-#[register(address = 0xFF40)]
+#[register(address = 0xFFFF)]
 pub struct RegisterIE(pub u8);
 
 #[bit_flag]
@@ -30,10 +32,18 @@ impl RegisterIE {
 }
 
 /// Interrupt flag
-#[derive(Default, Copy, Clone, ControlRegister)]
-#[register(address = 0xFF40)]
+///
+/// Upper 3 bits are unused and always read as 1.
+#[derive(Copy, Clone, ControlRegister)]
+#[register(address = 0xFF0F)]
 pub struct RegisterIF(pub u8);
 
+impl Default for RegisterIF {
+    fn default() -> Self {
+        RegisterIF(0xE1)
+    }
+}
+
 #[bit_flag]
 impl RegisterIF {
     /// Controls whether the VBlank interrupt handler is being requested.
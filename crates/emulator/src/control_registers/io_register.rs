@@ -0,0 +1,192 @@
+use crate::*;
+
+/// Description of an I/O register as seen by the CPU.
+///
+/// [more info](https://gbdev.io/pandocs/Hardware_Reg_List.html)
+#[derive(Debug, Clone, Copy)]
+pub struct IoRegister {
+    /// Bits which can be read by the CPU, other bits read as 1
+    pub read_mask: u8,
+    /// Bits which can be written by the CPU, other bits keep their value
+    pub write_mask: u8,
+    /// If set, the register reads as $FF and ignores writes outside of CGB
+    /// mode
+    pub cgb_only: bool,
+    /// Computes the value read by the CPU instead of the stored one
+    pub on_read: Option<fn(&Emulator) -> u8>,
+    /// Side effect of the write, called with the address and the value
+    /// written by the CPU after the writable bits are stored
+    pub on_write: Option<fn(&mut Emulator, u16, u8)>,
+}
+
+impl IoRegister {
+    /// Unmapped address, reads as $FF and ignores writes
+    pub const UNUSED: Self = Self::new(0x00, 0x00);
+
+    /// Plain storage, every bit can be read and written
+    pub const READ_WRITE: Self = Self::new(0xFF, 0xFF);
+
+    pub const fn new(read_mask: u8, write_mask: u8) -> Self {
+        Self {
+            read_mask,
+            write_mask,
+            cgb_only: false,
+            on_read: None,
+            on_write: None,
+        }
+    }
+
+    pub const fn cgb_only(mut self) -> Self {
+        self.cgb_only = true;
+        self
+    }
+
+    pub const fn on_read(mut self, on_read: fn(&Emulator) -> u8) -> Self {
+        self.on_read = Some(on_read);
+        self
+    }
+
+    pub const fn on_write(mut self, on_write: fn(&mut Emulator, u16, u8)) -> Self {
+        self.on_write = Some(on_write);
+        self
+    }
+}
+
+/// Description of the I/O register at `address` ($FF00-$FF7F).
+///
+/// Audio, serial, infrared and CGB palette registers are plain storage with
+/// hardware masks, since these peripherals are not emulated.
+pub const fn io_register(address: u16) -> IoRegister {
+    match address {
+        RegisterP1::ADDRESS => IoRegister::new(0x3F, 0x30),
+        // SB, SC
+        0xFF01 => IoRegister::READ_WRITE,
+        // clock speed bit exists only in CGB mode
+        0xFF02 => IoRegister::new(0x83, 0x83).on_read(|emulator| {
+            let value = *emulator.get_force(0xFF02);
+            if emulator.cgb_mode {
+                value
+            } else {
+                value | 0x02
+            }
+        }),
+        RegisterDIV::ADDRESS => {
+            IoRegister::new(0xFF, 0x00).on_write(|emulator, _, _| emulator.reset_div())
+        }
+        RegisterTIMA::ADDRESS | RegisterTMA::ADDRESS => IoRegister::READ_WRITE,
+        RegisterTAC::ADDRESS => IoRegister::new(0x07, 0x07),
+        RegisterIF::ADDRESS => IoRegister::new(INTERRUPTS_MASK, INTERRUPTS_MASK),
+
+        // NR10-NR14
+        0xFF10 => IoRegister::new(0x7F, 0x7F),
+        0xFF11 => IoRegister::new(0xC0, 0xFF),
+        0xFF12 => IoRegister::READ_WRITE,
+        0xFF13 => IoRegister::new(0x00, 0xFF),
+        0xFF14 => IoRegister::new(0x40, 0xC7),
+        // NR21-NR24
+        0xFF16 => IoRegister::new(0xC0, 0xFF),
+        0xFF17 => IoRegister::READ_WRITE,
+        0xFF18 => IoRegister::new(0x00, 0xFF),
+        0xFF19 => IoRegister::new(0x40, 0xC7),
+        // NR30-NR34
+        0xFF1A => IoRegister::new(0x80, 0x80),
+        0xFF1B => IoRegister::new(0x00, 0xFF),
+        0xFF1C => IoRegister::new(0x60, 0x60),
+        0xFF1D => IoRegister::new(0x00, 0xFF),
+        0xFF1E => IoRegister::new(0x40, 0xC7),
+        // NR41-NR44
+        0xFF20 => IoRegister::new(0x00, 0x3F),
+        0xFF21 | 0xFF22 => IoRegister::READ_WRITE,
+        0xFF23 => IoRegister::new(0x40, 0xC0),
+        // NR50, NR51
+        0xFF24 | 0xFF25 => IoRegister::READ_WRITE,
+        // NR52, channel status bits are read-only, turning the APU off stops
+        // all channels
+        0xFF26 => IoRegister::new(0x8F, 0x80).on_write(|emulator, _, value| {
+            if value & 0x80 == 0 {
+                *emulator.get_mut_force(0xFF26) = 0x00;
+            }
+        }),
+        // Wave RAM
+        0xFF30..=0xFF3F => IoRegister::READ_WRITE,
+
+        RegisterLCDC::ADDRESS => IoRegister::READ_WRITE,
+        // LYC == LY and PPU mode are read-only
+        RegisterSTAT::ADDRESS => IoRegister::new(0x7F, 0x78),
+        RegisterSCY::ADDRESS | RegisterSCX::ADDRESS => IoRegister::READ_WRITE,
+        // LY is driven by the PPU, writes are ignored
+        RegisterLY::ADDRESS => IoRegister::new(0xFF, 0x00),
+        RegisterLYC::ADDRESS => IoRegister::READ_WRITE,
+        RegisterDMA::ADDRESS => {
+            IoRegister::READ_WRITE.on_write(|emulator, _, value| emulator.start_oam_dma(value))
+        }
+        RegisterBGP::ADDRESS | RegisterOBP0::ADDRESS | RegisterOBP1::ADDRESS => {
+            IoRegister::READ_WRITE
+        }
+        RegisterWY::ADDRESS | RegisterWX::ADDRESS => IoRegister::READ_WRITE,
+
+        RegisterKEY1::ADDRESS => IoRegister::new(0x81, 0x01).cgb_only(),
+        RegisterVBK::ADDRESS => IoRegister::new(RegisterVBK::BANK, RegisterVBK::BANK).cgb_only(),
+        RegisterBANK::ADDRESS => IoRegister::UNUSED.on_write(|emulator, _, value| {
+            if value != 0 {
+                emulator.unmap_boot_rom();
+            }
+        }),
+        RegisterHDMA1::ADDRESS..=RegisterHDMA4::ADDRESS => IoRegister::new(0x00, 0xFF)
+            .cgb_only()
+            .on_write(Emulator::set_vram_dma_address),
+        RegisterHDMA5::ADDRESS => IoRegister::READ_WRITE
+            .cgb_only()
+            .on_read(|emulator| emulator.vram_dma.status())
+            .on_write(|emulator, _, value| emulator.start_vram_dma(value)),
+        // RP
+        0xFF56 => IoRegister::new(0xC3, 0xC1).cgb_only(),
+        // BCPS, BCPD, OCPS, OCPD
+        0xFF68 | 0xFF6A => IoRegister::new(0xBF, 0xBF).cgb_only(),
+        0xFF69 | 0xFF6B => IoRegister::READ_WRITE.cgb_only(),
+        // OPRI
+        0xFF6C => IoRegister::new(0x01, 0x01).cgb_only(),
+        RegisterSVBK::ADDRESS => IoRegister::new(RegisterSVBK::BANK, RegisterSVBK::BANK).cgb_only(),
+        // undocumented registers
+        0xFF72 | 0xFF73 => IoRegister::READ_WRITE,
+        0xFF74 => IoRegister::READ_WRITE.cgb_only(),
+        0xFF75 => IoRegister::new(0x70, 0x70),
+        // PCM12, PCM34
+        0xFF76 | 0xFF77 => IoRegister::new(0xFF, 0x00).cgb_only(),
+
+        _ => IoRegister::UNUSED,
+    }
+}
+
+impl Emulator {
+    /// Read I/O register as the CPU would see it
+    pub fn read_io_register(&self, address: u16) -> u8 {
+        let register = io_register(address);
+        if register.cgb_only && !self.cgb_mode {
+            return 0xFF;
+        }
+
+        let value = match register.on_read {
+            Some(on_read) => on_read(self),
+            None => *self.get_force(address),
+        };
+
+        value | !register.read_mask
+    }
+
+    /// Write I/O register as the CPU would do it, only writable bits are
+    /// changed
+    pub fn write_io_register(&mut self, address: u16, value: u8) {
+        let register = io_register(address);
+        if register.cgb_only && !self.cgb_mode {
+            return;
+        }
+
+        let stored = self.get_mut_force(address);
+        *stored = (*stored & !register.write_mask) | (value & register.write_mask);
+
+        if let Some(on_write) = register.on_write {
+            on_write(self, address, value);
+        }
+    }
+}
@@ -0,0 +1,55 @@
+use crate::*;
+use bit_flag::{bit_flag, flag_mask};
+
+/// P1/JOYP: Joypad
+///
+/// Lower nibble contains state of the selected buttons. Buttons are active
+/// low, so `false` means pressed.
+#[derive(Copy, Clone, ControlRegister)]
+#[register(address = 0xFF00)]
+pub struct RegisterP1(pub u8);
+
+impl Default for RegisterP1 {
+    fn default() -> Self {
+        RegisterP1(0xCF)
+    }
+}
+
+#[bit_flag]
+impl RegisterP1 {
+    /// Mask of the input lines of the joypad
+    pub const INPUT_LINES: u8 = 0b0000_1111;
+
+    /// If `false`, buttons (A, B, Select, Start) can be read from the lower
+    /// nibble.
+    #[flag_mask]
+    pub const SELECT_BUTTONS: u8 = 0b0010_0000;
+
+    /// If `false`, directional keys can be read from the lower nibble.
+    #[flag_mask]
+    pub const SELECT_D_PAD: u8 = 0b0001_0000;
+
+    /// Start or Down, `false` if pressed.
+    #[flag_mask]
+    pub const START_DOWN: u8 = 0b0000_1000;
+
+    /// Select or Up, `false` if pressed.
+    #[flag_mask]
+    pub const SELECT_UP: u8 = 0b0000_0100;
+
+    /// B or Left, `false` if pressed.
+    #[flag_mask]
+    pub const B_LEFT: u8 = 0b0000_0010;
+
+    /// A or Right, `false` if pressed.
+    #[flag_mask]
+    pub const A_RIGHT: u8 = 0b0000_0001;
+}
+
+impl RegisterP1 {
+    /// Check if any of the input lines is low (e.g. any selected button is
+    /// pressed).
+    pub fn is_any_line_low(&self) -> bool {
+        self.0 & Self::INPUT_LINES != Self::INPUT_LINES
+    }
+}
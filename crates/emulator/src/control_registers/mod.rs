@@ -1,13 +1,29 @@
+mod banking;
+mod boot_rom_mapping;
+mod dma;
 mod interrupt;
+mod io_register;
+mod joypad;
 mod lcd_status;
 mod lcdc;
+mod palettes;
 mod register;
 mod scrolling;
+mod speed_switch;
 mod timer;
+mod vram_dma;
 
+pub use banking::*;
+pub use boot_rom_mapping::*;
+pub use dma::*;
 pub use interrupt::*;
+pub use io_register::*;
+pub use joypad::*;
 pub use lcd_status::*;
 pub use lcdc::*;
+pub use palettes::*;
 pub use register::*;
 pub use scrolling::*;
+pub use speed_switch::*;
 pub use timer::*;
+pub use vram_dma::*;
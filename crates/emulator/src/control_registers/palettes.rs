@@ -0,0 +1,67 @@
+use crate::*;
+
+/// BGP: BG palette data (Non-CGB Mode only)
+///
+/// Assigns shades (0 - white, 3 - black) to the color indices of the
+/// background and window tiles, 2 bits per index.
+#[derive(Copy, Clone, ControlRegister)]
+#[register(address = 0xFF47)]
+pub struct RegisterBGP(pub u8);
+
+impl Default for RegisterBGP {
+    fn default() -> Self {
+        RegisterBGP(0xFC)
+    }
+}
+
+impl RegisterBGP {
+    /// Shade assigned to the color index
+    pub fn shade(self, index: PaletteIndex) -> u8 {
+        palette_shade(self.0, index)
+    }
+}
+
+/// OBP0: OBJ palette 0 data (Non-CGB Mode only)
+///
+/// Same as [`RegisterBGP`], but color index 0 is transparent.
+#[derive(Copy, Clone, ControlRegister)]
+#[register(address = 0xFF48)]
+pub struct RegisterOBP0(pub u8);
+
+impl Default for RegisterOBP0 {
+    fn default() -> Self {
+        RegisterOBP0(0xFF)
+    }
+}
+
+impl RegisterOBP0 {
+    /// Shade assigned to the color index
+    pub fn shade(self, index: PaletteIndex) -> u8 {
+        palette_shade(self.0, index)
+    }
+}
+
+/// OBP1: OBJ palette 1 data (Non-CGB Mode only)
+///
+/// Same as [`RegisterBGP`], but color index 0 is transparent.
+#[derive(Copy, Clone, ControlRegister)]
+#[register(address = 0xFF49)]
+pub struct RegisterOBP1(pub u8);
+
+impl Default for RegisterOBP1 {
+    fn default() -> Self {
+        RegisterOBP1(0xFF)
+    }
+}
+
+impl RegisterOBP1 {
+    /// Shade assigned to the color index
+    pub fn shade(self, index: PaletteIndex) -> u8 {
+        palette_shade(self.0, index)
+    }
+}
+
+/// Shade assigned to the color index by the palette register value
+fn palette_shade(palette: u8, index: PaletteIndex) -> u8 {
+    (palette >> (index as u8 * 2)) & 0b11
+}
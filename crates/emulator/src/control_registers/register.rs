@@ -13,11 +13,19 @@ pub trait ControlRegister: From<u8> + Into<u8> + Copy {
     }
 }
 
+/// Check if the address belongs to I/O range or IE register.
+fn is_control_register_address(address: u16) -> bool {
+    let address = address as usize;
+
+    MEMORY_RANGE_IO_REGISTERS.contains(&address)
+        || address == MEMORY_ADDRESS_INTERRUPT_ENABLE_REGISTER
+}
+
 impl Emulator {
     /// Get control register
     pub fn reg<T: ControlRegister>(&self) -> &T {
         debug_assert!(
-            MEMORY_RANGE_IO_REGISTERS.contains(&(T::ADDRESS as usize)),
+            is_control_register_address(T::ADDRESS),
             "Control register out of IO memory range: {:04X}",
             T::ADDRESS
         );
@@ -28,7 +36,7 @@ impl Emulator {
     /// Get mutable reference to control register
     pub fn reg_mut<T: ControlRegister>(&mut self) -> &mut T {
         debug_assert!(
-            MEMORY_RANGE_IO_REGISTERS.contains(&(T::ADDRESS as usize)),
+            is_control_register_address(T::ADDRESS),
             "Control register out of IO memory range: {:04X}",
             T::ADDRESS
         );
@@ -0,0 +1,30 @@
+use crate::*;
+use bit_flag::{bit_flag, flag_mask};
+
+/// Amount of M-cycles the CPU is paused for after the speed switch.
+pub const SPEED_SWITCH_DURATION: usize = 2050;
+
+/// KEY1: Prepare speed switch (CGB Mode only)
+#[derive(Copy, Clone, ControlRegister)]
+#[register(address = 0xFF4D)]
+pub struct RegisterKEY1(pub u8);
+
+impl Default for RegisterKEY1 {
+    fn default() -> Self {
+        RegisterKEY1(0x7E)
+    }
+}
+
+#[bit_flag]
+impl RegisterKEY1 {
+    /// Current CPU speed, `true` if CPU runs in double speed mode.
+    ///
+    /// Read-only, updated by the STOP instruction.
+    #[flag_mask]
+    pub const CURRENT_SPEED: u8 = 0b1000_0000;
+
+    /// If set, the next STOP instruction will switch CPU speed instead of
+    /// entering low power mode.
+    #[flag_mask]
+    pub const SWITCH_ARMED: u8 = 0b0000_0001;
+}
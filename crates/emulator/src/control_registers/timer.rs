@@ -23,7 +23,7 @@ pub struct RegisterTMA(pub u8);
 
 /// Timer Control
 #[derive(Default, ControlRegister, Clone, Copy)]
-#[register(address = 0xFF06)]
+#[register(address = 0xFF07)]
 pub struct RegisterTAC(pub u8);
 
 #[bit_flag]
@@ -103,7 +103,7 @@ impl Emulator {
 
         if tima == 0xFF {
             self.reg_mut::<RegisterTIMA>().0 = self.reg::<RegisterTMA>().0;
-            self.reg_mut::<RegisterIF>().set_timer(true);
+            self.request_interrupt(Interrupt::Timer);
         } else {
             self.reg_mut::<RegisterTIMA>().increment();
         }
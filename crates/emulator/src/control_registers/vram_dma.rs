@@ -0,0 +1,44 @@
+use crate::*;
+
+/// HDMA1: VRAM DMA source high (CGB Mode only, write-only)
+#[derive(Default, Copy, Clone, ControlRegister)]
+#[register(address = 0xFF51)]
+pub struct RegisterHDMA1(pub u8);
+
+/// HDMA2: VRAM DMA source low (CGB Mode only, write-only)
+///
+/// Lower 4 bits are ignored.
+#[derive(Default, Copy, Clone, ControlRegister)]
+#[register(address = 0xFF52)]
+pub struct RegisterHDMA2(pub u8);
+
+/// HDMA3: VRAM DMA destination high (CGB Mode only, write-only)
+///
+/// Upper 3 bits are ignored, destination is always in VRAM.
+#[derive(Default, Copy, Clone, ControlRegister)]
+#[register(address = 0xFF53)]
+pub struct RegisterHDMA3(pub u8);
+
+/// HDMA4: VRAM DMA destination low (CGB Mode only, write-only)
+///
+/// Lower 4 bits are ignored.
+#[derive(Default, Copy, Clone, ControlRegister)]
+#[register(address = 0xFF54)]
+pub struct RegisterHDMA4(pub u8);
+
+/// HDMA5: VRAM DMA length/mode/start (CGB Mode only)
+///
+/// Writing starts the transfer of `(value & 0x7F) + 1` blocks of 16 bytes,
+/// bit 7 selects HBlank DMA. Reads return the status of the transfer, see
+/// [`VramDma::status`].
+#[derive(Default, Copy, Clone, ControlRegister)]
+#[register(address = 0xFF55)]
+pub struct RegisterHDMA5(pub u8);
+
+impl RegisterHDMA5 {
+    /// Bit 7 - Mode (0=General-Purpose DMA, 1=HBlank DMA) on write, for
+    /// HBlank DMA in progress clearing it cancels the transfer
+    pub const HBLANK_MODE: u8 = 0b1000_0000;
+    /// Bits 0-6 - Amount of 16-byte blocks minus 1
+    pub const LENGTH: u8 = 0b0111_1111;
+}
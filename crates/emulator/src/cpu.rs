@@ -0,0 +1,227 @@
+use crate::*;
+
+/// SM83 CPU core: registers and internal state.
+///
+/// Memory is accessed only through the [`Bus`], so the same core runs on the
+/// Game Boy memory map ([`Emulator`]) or on a flat RAM ([`FlatBus`]).
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Cpu {
+    /// **AF** register
+    pub accumulator_and_flags: CpuRegister,
+    /// **BC** register
+    pub register_bc: CpuRegister,
+    /// **DE** register
+    pub register_de: CpuRegister,
+    /// **HL** register
+    pub register_hl: CpuRegister,
+    /// **SP** register
+    pub stack_pointer: StackPointer,
+    /// **PC** register
+    pub program_counter: ProgramCounter,
+
+    /// **IR** register. Internal cpu register used to store the opcode of the
+    /// next instruction.
+    pub instruction_register: u8,
+
+    /// Indicate that IME flag should be set after the next instruction
+    pub delayed_ime_set: bool,
+    /// IME: Interrupt master enable flag
+    pub ime_flag: bool,
+
+    /// Execution state of the CPU (running, halted, stopped or locked)
+    pub state: CpuState,
+
+    /// Indicate that the next opcode fetch should not increment the PC (HALT
+    /// bug)
+    pub halt_bug: bool,
+
+    /// Value of [`Bus::cycles`] right after the opcode in IR register was
+    /// fetched
+    pub opcode_fetch_cycle: usize,
+
+    /// Address the opcode in IR register was fetched from
+    pub opcode_address: u16,
+
+    /// Interrupt dispatched while handling the last instruction, if any
+    pub dispatched_interrupt: Option<Interrupt>,
+}
+
+impl Cpu {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Check if the CPU is not executing instructions (e.g. halted, stopped or
+    /// locked).
+    pub fn is_suspended(&self) -> bool {
+        self.state != CpuState::Running
+    }
+
+    /// Read a u8 from the memory at the current program counter and advance the program counter by 1.
+    ///
+    /// Takes single M-cycle.
+    pub fn read_u8_at_pc<B: Bus>(&mut self, bus: &mut B) -> u8 {
+        let address = self.program_counter.post_increment(1);
+        bus.cpu_read(address)
+    }
+
+    /// Read an i8 from the memory at the current program counter and advance the program counter by 1.
+    ///
+    /// Takes single M-cycle.
+    pub fn read_i8_at_pc<B: Bus>(&mut self, bus: &mut B) -> i8 {
+        self.read_u8_at_pc(bus) as i8
+    }
+
+    /// Read a u16 from the memory at the current program counter and advance the program counter by 2.
+    ///
+    /// Takes 2 M-cycles.
+    pub fn read_u16_at_pc<B: Bus>(&mut self, bus: &mut B) -> u16 {
+        let low = self.read_u8_at_pc(bus);
+        let high = self.read_u8_at_pc(bus);
+
+        u16::from_le_bytes([low, high])
+    }
+
+    /// Handle given instruction without fetch and pc increment
+    pub fn handle_instruction<B: Bus>(&mut self, bus: &mut B, instruction: Instruction) {
+        let set_ime = self.delayed_ime_set;
+
+        let cycles = instruction.execute(self, bus);
+        bus.on_after_instruction(self, instruction);
+
+        if cycles == 0 {
+            self.lock(bus);
+            return;
+        }
+
+        debug_assert!(
+            instruction.info().is_valid_duration(cycles),
+            "duration of {instruction:?} doesn't match its info",
+        );
+        // every M-cycle is spent on the bus, the last one is the fetch of the
+        // next opcode
+        debug_assert_eq!(
+            bus.cycles() - self.opcode_fetch_cycle + 1,
+            cycles,
+            "bus activity of {instruction:?} doesn't match its duration",
+        );
+
+        self.process_interrupt(bus);
+
+        if set_ime {
+            self.ime_flag = true;
+            self.delayed_ime_set = false;
+        }
+    }
+
+    /// Handle single M-cycle while the CPU is halted.
+    ///
+    /// Timer and PPU keep running, the CPU wakes up as soon as any interrupt
+    /// becomes pending and services it if IME is set.
+    pub fn handle_halted_cycle<B: Bus>(&mut self, bus: &mut B) {
+        bus.cpu_idle();
+
+        if bus.pending_interrupts() != 0 {
+            self.state = CpuState::Running;
+            self.process_interrupt(bus);
+        }
+    }
+
+    /// Handle single M-cycle while the CPU is in very low power mode (STOP).
+    ///
+    /// Both CPU and PPU are stopped, the CPU wakes up as soon as any of the
+    /// joypad input lines goes low.
+    pub fn handle_stopped_cycle<B: Bus>(&mut self, bus: &mut B) {
+        if RegisterP1(bus.peek(RegisterP1::ADDRESS)).is_any_line_low() {
+            self.state = CpuState::Running;
+        }
+    }
+
+    /// Handle single M-cycle while the CPU is locked by an illegal opcode.
+    ///
+    /// The CPU doesn't access the bus and ignores interrupts, PPU and timer
+    /// keep running.
+    pub fn handle_locked_cycle<B: Bus>(&mut self, bus: &mut B) {
+        bus.cpu_idle();
+    }
+
+    /// Hard lock the CPU, as it happens after executing an illegal opcode.
+    pub fn lock<B: Bus>(&mut self, bus: &mut B) {
+        self.state = CpuState::Locked;
+
+        bus.on_cpu_locked(self);
+    }
+
+    /// Handle instruction from instruction register(IR) without fetch and pc
+    /// increment
+    ///
+    /// If the CPU is suspended, handles single M-cycle of HALT or STOP instead.
+    /// Illegal opcode locks the CPU, after that every call handles single
+    /// M-cycle and returns NOP.
+    pub fn handle_next_instruction_pre_fetch<B: Bus>(&mut self, bus: &mut B) -> Instruction {
+        self.dispatched_interrupt = None;
+
+        match self.state {
+            CpuState::Running => {}
+            CpuState::Halted => {
+                self.handle_halted_cycle(bus);
+
+                return InstructionHALT.into();
+            }
+            CpuState::Stopped => {
+                self.handle_stopped_cycle(bus);
+
+                return InstructionSTOP.into();
+            }
+            CpuState::Locked => {
+                self.handle_locked_cycle(bus);
+
+                return InstructionNOP.into();
+            }
+        }
+
+        let Some(instruction) = Instruction::read(self, bus) else {
+            self.lock(bus);
+            self.handle_locked_cycle(bus);
+
+            return InstructionNOP.into();
+        };
+
+        self.handle_instruction(bus, instruction);
+
+        instruction
+    }
+
+    /// Handle instruction from instruction register(IR) and fetch next
+    /// instruction opcode
+    ///
+    /// Returns the instruction that was executed
+    pub fn handle_next_instruction<B: Bus>(&mut self, bus: &mut B) -> Instruction {
+        let instruction = self.handle_next_instruction_pre_fetch(bus);
+
+        // IR is not updated while the CPU is suspended, next opcode will be
+        // fetched after wake-up
+        if !self.is_suspended() {
+            self.fetch_opcode(bus);
+        }
+
+        instruction
+    }
+
+    /// Fetch next instruction opcode, store it in the IR register and increment the PC
+    ///
+    /// If HALT bug was triggered, the PC is not incremented, so the same byte
+    /// is read twice.
+    pub fn fetch_opcode<B: Bus>(&mut self, bus: &mut B) {
+        self.opcode_address = self.program_counter.0;
+
+        if self.halt_bug {
+            self.halt_bug = false;
+            self.instruction_register = bus.cpu_read(self.program_counter.0);
+        } else {
+            self.instruction_register = self.read_u8_at_pc(bus);
+        }
+
+        self.opcode_fetch_cycle = bus.cycles();
+    }
+}
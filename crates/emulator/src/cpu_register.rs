@@ -88,19 +88,11 @@ impl CpuRegister {
     }
 }
 impl MemoryAddress for CpuRegister {
-    fn get_at(&self, emulator: &Emulator) -> u8 {
-        emulator.get(self.as_u16())
+    fn get_at<B: Bus>(&self, _cpu: &Cpu, bus: &mut B) -> u8 {
+        bus.cpu_read(self.as_u16())
     }
 
-    fn get_at_force(&self, emulator: &Emulator) -> u8 {
-        *emulator.get_force(self.as_u16())
-    }
-
-    fn set_at(&self, emulator: &mut Emulator, value: u8) {
-        emulator.set(self.as_u16(), value);
-    }
-
-    fn set_at_force(&self, emulator: &mut Emulator, value: u8) {
-        emulator.set_force(self.as_u16(), value);
+    fn set_at<B: Bus>(&self, _cpu: &Cpu, bus: &mut B, value: u8) {
+        bus.cpu_write(self.as_u16(), value);
     }
 }
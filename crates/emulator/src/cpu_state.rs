@@ -0,0 +1,15 @@
+/// Execution state of the CPU.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CpuState {
+    /// CPU is executing instructions
+    #[default]
+    Running,
+    /// CPU is halted by HALT instruction and waits for an interrupt to become
+    /// pending
+    Halted,
+    /// CPU is in very low power mode (STOP) and waits for a joypad input
+    Stopped,
+    /// CPU is hard locked after executing an illegal opcode. Only reset can
+    /// recover it, while the PPU and timer keep running.
+    Locked,
+}
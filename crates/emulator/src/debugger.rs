@@ -2,4 +2,7 @@ use crate::{Emulator, Instruction};
 
 pub trait EmulatorDebugger {
     fn on_after_instruction(&mut self, emulator: &Emulator, opcode: u8, instruction: Instruction);
+
+    /// Called when the CPU is locked by an illegal opcode.
+    fn on_cpu_locked(&mut self, _emulator: &Emulator, _opcode: u8) {}
 }
@@ -3,55 +3,56 @@ use crate::*;
 pub struct Emulator {
     pub debugger: Option<Box<dyn EmulatorDebugger>>,
 
-    /// **AF** register
-    pub accumulator_and_flags: CpuRegister,
-    /// **BC** register
-    pub register_bc: CpuRegister,
-    /// **DE** register
-    pub register_de: CpuRegister,
-    /// **HL** register
-    pub register_hl: CpuRegister,
-    /// **SP** register
-    pub stack_pointer: StackPointer,
-    /// **PC** register
-    pub program_counter: ProgramCounter,
+    pub cpu: Cpu,
+
+    /// Emulated hardware model
+    pub model: EmulatorModel,
 
     /// Internal timer register, increments every M-cycle
     pub internal_timer: CpuRegister,
 
+    /// Last finished frame, updated at the start of VBlank
     pub screen: Screen,
+    /// Frame being rendered by the PPU
+    pub screen_buffer: Screen,
 
-    pub rom: Option<Rom>,
+    pub rom: Rom,
 
-    /// Indicate that IME flag should be set after the next instruction
-    pub delayed_ime_set: bool,
-    /// IME: Interrupt master enable flag
-    pub ime_flag: bool,
+    /// Boot ROM mapped over the cartridge ROM, `None` after it's unmapped or
+    /// if the emulator starts from the post-boot state
+    pub boot_rom: Option<BootRom>,
 
     /// Number of M-cycles that have passed since the CPU was started
     pub cycles: usize,
 
-    pub is_in_low_power_mode: bool,
-
-    /// **IR** register. Internal cpu register used to store the opcode of the
-    /// next instruction.
-    pub instruction_register: u8,
+    /// Log of the CPU memory accesses, one entry per M-cycle. Recorded only
+    /// if set to `Some`, useful for testing and debugging.
+    pub bus_log: Option<Vec<BusActivity>>,
 
     /// If true, the CPU will run at double speed
     pub double_speed: bool,
 
+    /// If true, CGB specific features (e.g. speed switch) are enabled
+    pub cgb_mode: bool,
+
+    /// Amount of M-cycles left until the CPU resumes after the speed switch
+    pub speed_switch_delay: usize,
+
+    pub oam_dma: OamDma,
+
+    pub vram_dma: VramDma,
+
     /// Amount of dots since the last scanline change
     pub scanline_progress: usize,
 
     // dots spent in current current ppu mode
     pub dots_in_current_mode: usize,
 
-    pub rom_bank_00: Box<[u8; MEMORY_SIZE_ROM_BANK_00]>,
-    pub rom_bank_01: Box<[u8; MEMORY_SIZE_ROM_BANK_01]>,
-    pub vram: Box<[u8; MEMORY_SIZE_VRAM]>,
-    pub external_ram: Box<[u8; MEMORY_SIZE_EXTERNAL_RAM]>,
-    pub work_ram_0: Box<[u8; MEMORY_SIZE_WORK_RAM_0]>,
-    pub work_ram_1: Box<[u8; MEMORY_SIZE_WORK_RAM_1]>,
+    /// VRAM banks, only bank 0 is used outside of CGB mode
+    pub vram: Box<[[u8; MEMORY_SIZE_VRAM]; VRAM_BANKS]>,
+    /// WRAM banks, bank 0 is fixed at $C000-$CFFF, the selected one is mapped
+    /// to $D000-$DFFF. Only banks 0 and 1 are used outside of CGB mode
+    pub work_ram: Box<[[u8; MEMORY_SIZE_WORK_RAM_0]; WORK_RAM_BANKS]>,
     pub oam: Box<[u8; MEMORY_SIZE_OAM]>,
     pub not_usable: Box<[u8; MEMORY_SIZE_NOT_USABLE]>,
     pub io_registers: Box<[u8; MEMORY_SIZE_IO_REGISTERS]>,
@@ -64,7 +65,31 @@ pub struct Emulator {
     /// Indicate if a new frame is available to be rendered
     pub is_frame_available: bool,
 
+    /// Number of frames completed by the PPU since the start
+    pub frame_count: usize,
+
+    /// Duration of mode 3 of the last scanline in dots
     pub mode_3_duration: usize,
+
+    /// Objects selected by the OAM scan for the current scanline, in OAM order
+    pub line_objects: Vec<Object>,
+    /// Fetcher and pixel FIFOs of the current scanline
+    pub pixel_fifo: PixelFifo,
+
+    /// Internal line counter of the window, incremented only on scanlines
+    /// where the window is drawn
+    pub window_line: usize,
+    /// Indicate that LY was equal to WY during the current frame, the window
+    /// is not drawn before that
+    pub window_y_triggered: bool,
+
+    /// Path of the `.sav` file for the battery-backed RAM
+    pub save_file: Option<std::path::PathBuf>,
+    /// Save RAM to the save file every N frames if it was modified, disabled
+    /// if `None`
+    pub autosave_interval: Option<usize>,
+    /// Last error of the autosave
+    pub autosave_error: Option<SaveRamError>,
 }
 
 impl Default for Emulator {
@@ -76,36 +101,40 @@ impl Default for Emulator {
 impl Emulator {
     #[inline(always)]
     pub fn new() -> Self {
+        Self::new_with_model(EmulatorModel::default())
+    }
+
+    /// Create emulator without cartridge in the post-boot state of the given
+    /// model
+    pub fn new_with_model(model: EmulatorModel) -> Self {
         let mut emulator = Self {
             debugger: None,
-            accumulator_and_flags: CpuRegister::default(),
-            register_bc: CpuRegister::default(),
-            register_de: CpuRegister::default(),
-            register_hl: CpuRegister::default(),
-            stack_pointer: StackPointer::default(),
-            program_counter: ProgramCounter::default(),
-            delayed_ime_set: false,
-            ime_flag: false,
-            rom: None,
+            cpu: Cpu::new(),
+            model,
+            rom: Rom::new(),
+            boot_rom: None,
             cycles: 0,
+            bus_log: None,
             double_speed: false,
-            is_in_low_power_mode: false,
+            cgb_mode: false,
+            speed_switch_delay: 0,
+            oam_dma: OamDma::default(),
+            vram_dma: VramDma::default(),
             scanline_progress: 0,
             dots_in_current_mode: 0,
             mode_3_duration: MODE_3_BASE_DURATION,
+            line_objects: Vec::with_capacity(OBJECTS_PER_LINE),
+            pixel_fifo: PixelFifo::default(),
+            window_line: 0,
+            window_y_triggered: false,
             disable_registers_update: false,
             internal_timer: CpuRegister::default(),
 
             screen: Screen::new(),
+            screen_buffer: Screen::new(),
 
-            instruction_register: 0,
-
-            rom_bank_00: Box::new([0; MEMORY_SIZE_ROM_BANK_00]),
-            rom_bank_01: Box::new([0; MEMORY_SIZE_ROM_BANK_01]),
-            vram: Box::new([0; MEMORY_SIZE_VRAM]),
-            external_ram: Box::new([0; MEMORY_SIZE_EXTERNAL_RAM]),
-            work_ram_0: Box::new([0; MEMORY_SIZE_WORK_RAM_0]),
-            work_ram_1: Box::new([0; MEMORY_SIZE_WORK_RAM_1]),
+            vram: Box::new([[0; MEMORY_SIZE_VRAM]; VRAM_BANKS]),
+            work_ram: Box::new([[0; MEMORY_SIZE_WORK_RAM_0]; WORK_RAM_BANKS]),
             oam: Box::new([0; MEMORY_SIZE_OAM]),
             not_usable: Box::new([0; MEMORY_SIZE_NOT_USABLE]),
             io_registers: Box::new([0; MEMORY_SIZE_IO_REGISTERS]),
@@ -113,6 +142,11 @@ impl Emulator {
             interrupt_enable_register: 0,
 
             is_frame_available: false,
+            frame_count: 0,
+
+            save_file: None,
+            autosave_interval: None,
+            autosave_error: None,
         };
 
         emulator.init();
@@ -132,59 +166,52 @@ impl Emulator {
     fn init(&mut self) {
         self.init_cpu_registers();
         self.init_memory();
+        self.init_io_registers();
+        self.init_model_timer();
     }
 
-    fn init_cpu_registers(&mut self) {
-        self.accumulator_and_flags.set(0x1180);
-        // TODO set BC based on DMG mode [more info](https://gbdev.io/pandocs/Power_Up_Sequence.html#cgbdmg_b)
-        self.register_bc.set(0x0000);
-        self.register_de.set(0xFF56);
-        // TOTO set HL based on DMG mode [more info](https://gbdev.io/pandocs/Power_Up_Sequence.html#cgbdmg_hl)
-        self.register_hl.set(0x000D);
-        self.program_counter.0 = 0x0100;
-        self.stack_pointer.0 = 0xFFFE;
+    pub fn from_rom(rom: impl Into<Rom>) -> Self {
+        Self::from_rom_with_model(rom, EmulatorModel::default())
     }
 
-    pub fn from_rom(rom: impl Into<Rom>) -> Self {
-        let mut rom: Rom = rom.into();
-        let mut emulator = Self::new();
+    /// Create emulator with the cartridge in the post-boot state of the given
+    /// model. Post-boot registers depend on the cartridge header.
+    pub fn from_rom_with_model(rom: impl Into<Rom>, model: EmulatorModel) -> Self {
+        let rom: Rom = rom.into();
+        let mut emulator = Self::new_with_model(model);
 
-        emulator.rom_bank_00 = Box::new(
-            rom.read_range(MEMORY_RANGE_ROM_BANK_00)
-                .try_into()
-                .expect("Invalid ROM bank 00 size"),
-        );
+        emulator.cgb_mode = model.is_cgb() && rom.supports_cgb();
+        emulator.rom = rom;
+        emulator.init();
 
-        emulator.rom_bank_01 = Box::new(
-            rom.read_range(MEMORY_RANGE_ROM_BANK_01)
-                .try_into()
-                .expect("Invalid ROM bank 01 size"),
-        );
+        emulator
+    }
 
-        emulator.rom = Some(rom);
+    /// Run `f` with the CPU detached from the emulator, so it can use the
+    /// emulator as its [`Bus`].
+    ///
+    /// `self.cpu` is stale while `f` runs and is updated when it returns.
+    pub fn with_cpu<R>(&mut self, f: impl FnOnce(&mut Cpu, &mut Self) -> R) -> R {
+        let mut cpu = self.cpu;
+        let result = f(&mut cpu, self);
+        self.cpu = cpu;
 
-        emulator
+        result
     }
 
     /// Handle given instruction without fetch and pc increment, but with PPU processing
     pub fn handle_instruction(&mut self, instruction: Instruction) {
-        let set_ime = self.delayed_ime_set;
-
-        let opcode = self.instruction_register;
-        let cycles = instruction.execute(self);
-        if let Some(mut debugger) = self.debugger.take() {
-            debugger.on_after_instruction(self, opcode, instruction);
-            self.debugger = Some(debugger);
-        }
-
-        if cycles == 0 {
-            panic!("Instruction {:?} failed", instruction);
-        }
+        self.with_cpu(|cpu, bus| cpu.handle_instruction(bus, instruction));
+    }
 
+    /// Advance timer and PPU by given amount of M-cycles
+    pub fn tick_cycles(&mut self, cycles: usize) {
         self.cycles += cycles;
 
         for _ in 0..cycles {
             self.tick_internal_timer();
+            self.tick_oam_dma();
+            self.tick_vram_dma();
         }
 
         if !self.disable_registers_update {
@@ -195,27 +222,74 @@ impl Emulator {
             self.handle_dots_in_cycle();
         }
 
-        self.process_interrupt();
+        let dots = cycles * self.dots_per_cycle();
+        self.rom.mapper.tick(dots);
+    }
 
-        if set_ime {
-            self.ime_flag = true;
-            self.delayed_ime_set = false;
-        }
+    /// Handle single M-cycle of the CPU pause after the speed switch.
+    ///
+    /// Timer is paused, PPU keeps running at the new speed.
+    pub fn handle_speed_switch_cycle(&mut self) {
+        self.speed_switch_delay -= 1;
+        self.cycles += 1;
+
+        self.handle_dots_in_cycle();
+        self.rom.mapper.tick(self.dots_per_cycle());
+    }
+
+    /// Switch CPU between normal and double speed (CGB only).
+    pub fn switch_speed(&mut self) {
+        self.double_speed = !self.double_speed;
+        self.speed_switch_delay = SPEED_SWITCH_DURATION;
+
+        let double_speed = self.double_speed;
+        let key1 = self.reg_mut::<RegisterKEY1>();
+        key1.set_switch_armed(false);
+        key1.set_current_speed(double_speed);
+    }
+
+    /// Reset DIV register and the internal timer
+    pub fn reset_div(&mut self) {
+        *self.internal_timer.as_u16_mut() = 0;
+        self.reg_mut::<RegisterDIV>().0 = 0;
+    }
+
+    /// Check if the CPU is not executing instructions (e.g. halted, stopped,
+    /// locked, switching speed or halted by the VRAM DMA).
+    pub fn is_cpu_suspended(&self) -> bool {
+        self.cpu.is_suspended() || self.speed_switch_delay > 0 || self.vram_dma.is_copying()
+    }
+
+    /// Check if the rumble motor of the cartridge is turned on, always `false`
+    /// for cartridges without rumble.
+    pub fn is_rumble_active(&self) -> bool {
+        self.rom.mapper.is_rumble_active()
     }
 
     /// Handle instruction from instruction register(IR) without fetch and pc
     /// increment
+    ///
+    /// If the CPU is suspended, handles single M-cycle of HALT or STOP instead.
+    /// Illegal opcode locks the CPU, after that every call handles single
+    /// M-cycle and returns NOP.
     pub fn handle_next_instruction_pre_fetch(&mut self) -> Instruction {
-        let instruction = Instruction::read(self).unwrap_or_else(|| {
-            panic!(
-                "Failed to read next instruction, opcode: {:02X}",
-                self.instruction_register
-            )
-        });
+        if self.speed_switch_delay > 0 {
+            self.handle_speed_switch_cycle();
 
-        self.handle_instruction(instruction);
+            return InstructionSTOP.into();
+        }
 
-        instruction
+        if self.vram_dma.is_copying() {
+            // CPU is halted until the block is copied, the opcode in IR is
+            // executed after the pause
+            self.cpu.dispatched_interrupt = None;
+            self.tick_cycles(1);
+            self.cpu.opcode_fetch_cycle = self.cycles;
+
+            return InstructionNOP.into();
+        }
+
+        self.with_cpu(|cpu, bus| cpu.handle_next_instruction_pre_fetch(bus))
     }
 
     /// Handle instruction from instruction register(IR) and fetch next
@@ -223,16 +297,25 @@ impl Emulator {
     ///
     /// Returns the instruction that was executed
     pub fn handle_next_instruction(&mut self) -> Instruction {
+        // next opcode is already fetched when the VRAM DMA halts the CPU
+        let is_vram_dma_pause = self.vram_dma.is_copying();
         let instruction = self.handle_next_instruction_pre_fetch();
 
-        // fetch next instruction opcode
-        self.fetch_opcode();
+        // IR is not updated while the CPU is suspended, next opcode will be
+        // fetched after wake-up
+        if !self.is_cpu_suspended() && !is_vram_dma_pause {
+            // fetch next instruction opcode
+            self.fetch_opcode();
+        }
 
         instruction
     }
 
     /// Fetch next instruction opcode, store it in the IR register and increment the PC
+    ///
+    /// If HALT bug was triggered, the PC is not incremented, so the same byte
+    /// is read twice.
     pub fn fetch_opcode(&mut self) {
-        self.instruction_register = self.read_u8_at_pc();
+        self.with_cpu(|cpu, bus| cpu.fetch_opcode(bus));
     }
 }
@@ -1,4 +1,5 @@
 use crate::*;
+use std::fmt;
 
 /// Add input value plus the carry flag to A.
 ///
@@ -18,16 +19,15 @@ pub enum InstructionADC {
 }
 
 impl InstructionTrait for InstructionADC {
-    fn execute(&self, emulator: &mut Emulator) -> usize {
-        let (input_value, cycles) = match self {
-            InstructionADC::A_R8(arg @ ArgumentR8::AtHL) => (arg.get(emulator), 2),
-            InstructionADC::A_R8(arg) => (arg.get(emulator), 1),
-            InstructionADC::A_N8(arg) => (arg.get(emulator), 2),
+    fn execute<B: Bus>(&self, cpu: &mut Cpu, bus: &mut B) -> usize {
+        let input_value = match self {
+            InstructionADC::A_R8(arg) => arg.get(cpu, bus),
+            InstructionADC::A_N8(arg) => arg.get(cpu),
         };
 
-        let register_a = emulator.accumulator_and_flags.high();
+        let register_a = cpu.accumulator_and_flags.high();
 
-        let flags = emulator.accumulator_and_flags.low();
+        let flags = cpu.accumulator_and_flags.low();
         let carry = get_flag(flags, FLAG_CARRY) as u8;
 
         let result = register_a.wrapping_add(input_value).wrapping_add(carry);
@@ -35,14 +35,39 @@ impl InstructionTrait for InstructionADC {
         let half_carry = (register_a & 0xF) + (input_value & 0xF) + carry > 0xF;
         let carry = (register_a as u16) + (input_value as u16) + (carry as u16) > 0xFF;
 
-        let flags = emulator.accumulator_and_flags.low_mut();
+        let flags = cpu.accumulator_and_flags.low_mut();
         set_flag(flags, FLAG_ZERO, result == 0);
         set_flag(flags, FLAG_SUBTRACT, false);
         set_flag(flags, FLAG_HALF_CARRY, half_carry);
         set_flag(flags, FLAG_CARRY, carry);
 
-        emulator.accumulator_and_flags.set_high(result);
+        cpu.accumulator_and_flags.set_high(result);
 
-        cycles
+        self.info().cycles
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        match self {
+            Self::A_R8(r8) => vec![0x88 | r8.to_bits()],
+            Self::A_N8(n8) => vec![0xCE, n8.0],
+        }
+    }
+
+    fn info(&self) -> InstructionInfo {
+        const FLAGS: FlagsEffect = FlagsEffect::from_notation(b"Z0HC");
+
+        match self {
+            Self::A_R8(r8) => InstructionInfo::new(1, 1 + r8.access_cycles(), FLAGS),
+            Self::A_N8(_) => InstructionInfo::new(2, 2, FLAGS),
+        }
+    }
+}
+
+impl fmt::Display for InstructionADC {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::A_R8(r8) => write!(f, "adc a, {r8}"),
+            Self::A_N8(n8) => write!(f, "adc a, {n8}"),
+        }
     }
 }
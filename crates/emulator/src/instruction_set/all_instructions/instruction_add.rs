@@ -1,4 +1,5 @@
 use crate::*;
+use std::fmt;
 
 #[allow(non_camel_case_types)]
 #[derive(Copy, Debug, Clone, PartialEq, Eq, Ord, PartialOrd, Hash)]
@@ -42,34 +43,27 @@ pub enum InstructionADD {
 }
 
 impl InstructionTrait for InstructionADD {
-    fn execute(&self, emulator: &mut Emulator) -> usize {
+    fn execute<B: Bus>(&self, cpu: &mut Cpu, bus: &mut B) -> usize {
         match self {
             Self::A_R8(input) => {
-                exec_add_to_a(emulator, input.get(emulator));
-
-                if *input == ArgumentR8::AtHL {
-                    2
-                } else {
-                    1
-                }
+                let value = input.get(cpu, bus);
+                exec_add_to_a(cpu, value);
             }
             Self::A_N8(input) => {
-                exec_add_to_a(emulator, input.get(emulator));
-
-                2
+                let value = input.get(cpu);
+                exec_add_to_a(cpu, value);
             }
             Self::HL_R16(inout) => {
-                exec_add_to_hl(emulator, inout.get(emulator));
-
-                2
+                exec_add_to_hl(cpu, inout.get(cpu));
+                bus.cpu_idle();
             }
             Self::SP_E8(input) => {
-                let stack_pointer: u16 = emulator.stack_pointer.into();
+                let stack_pointer: u16 = cpu.stack_pointer.into();
                 let result = input.apply_offset(stack_pointer);
 
-                let input_u16 = input.get(emulator) as u16;
+                let input_u16 = input.get(cpu) as u16;
 
-                let flags = emulator.accumulator_and_flags.low_mut();
+                let flags = cpu.accumulator_and_flags.low_mut();
 
                 set_flag(flags, FLAG_ZERO, false);
                 set_flag(flags, FLAG_SUBTRACT, false);
@@ -80,34 +74,67 @@ impl InstructionTrait for InstructionADD {
                     (input_u16 & 0xFF) as u8,
                 );
 
-                emulator.stack_pointer = result.into();
-
-                4
+                cpu.stack_pointer = result.into();
+                bus.cpu_idle();
+                bus.cpu_idle();
             }
         }
+
+        self.info().cycles
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        match self {
+            Self::A_R8(r8) => vec![0x80 | r8.to_bits()],
+            Self::A_N8(n8) => vec![0xC6, n8.0],
+            Self::HL_R16(r16) => vec![0x09 | r16.to_bits() << 4],
+            Self::SP_E8(e8) => vec![0xE8, e8.as_u8()],
+        }
+    }
+
+    fn info(&self) -> InstructionInfo {
+        const FLAGS_A: FlagsEffect = FlagsEffect::from_notation(b"Z0HC");
+
+        match self {
+            Self::A_R8(r8) => InstructionInfo::new(1, 1 + r8.access_cycles(), FLAGS_A),
+            Self::A_N8(_) => InstructionInfo::new(2, 2, FLAGS_A),
+            Self::HL_R16(_) => InstructionInfo::new(1, 2, FlagsEffect::from_notation(b"-0HC")),
+            Self::SP_E8(_) => InstructionInfo::new(2, 4, FlagsEffect::from_notation(b"00HC")),
+        }
     }
 }
 
-fn exec_add_to_a(emulator: &mut Emulator, value: u8) {
-    let register_a = emulator.accumulator_and_flags.high();
+fn exec_add_to_a(cpu: &mut Cpu, value: u8) {
+    let register_a = cpu.accumulator_and_flags.high();
     let result = register_a.wrapping_add(value);
-    emulator.accumulator_and_flags.set_high(result);
+    cpu.accumulator_and_flags.set_high(result);
 
-    let flags = emulator.accumulator_and_flags.low_mut();
+    let flags = cpu.accumulator_and_flags.low_mut();
 
     set_flag(flags, FLAG_ZERO, result == 0);
     set_flag(flags, FLAG_SUBTRACT, false);
     update_carry_flags_add_u8(flags, register_a, value);
 }
 
-fn exec_add_to_hl(emulator: &mut Emulator, value: u16) {
-    let register_hl = emulator.register_hl.as_u16();
+fn exec_add_to_hl(cpu: &mut Cpu, value: u16) {
+    let register_hl = cpu.register_hl.as_u16();
     let result = register_hl.wrapping_add(value);
-    emulator.register_hl.set(result);
+    cpu.register_hl.set(result);
 
-    let flags = emulator.accumulator_and_flags.low_mut();
+    let flags = cpu.accumulator_and_flags.low_mut();
 
     set_flag(flags, FLAG_SUBTRACT, false);
 
     update_carry_flags_add_u16(flags, register_hl, value);
 }
+
+impl fmt::Display for InstructionADD {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::A_R8(r8) => write!(f, "add a, {r8}"),
+            Self::A_N8(n8) => write!(f, "add a, {n8}"),
+            Self::HL_R16(r16) => write!(f, "add hl, {r16}"),
+            Self::SP_E8(e8) => write!(f, "add sp, {e8}"),
+        }
+    }
+}
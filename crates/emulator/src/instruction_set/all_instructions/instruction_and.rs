@@ -1,4 +1,5 @@
 use crate::*;
+use std::fmt;
 
 /// Bitwise AND between input value and A.
 ///
@@ -18,25 +19,49 @@ pub enum InstructionAND {
 }
 
 impl InstructionTrait for InstructionAND {
-    fn execute(&self, emulator: &mut Emulator) -> usize {
-        let (value, cycles) = match self {
-            InstructionAND::A_R8(arg @ ArgumentR8::AtHL) => (arg.get(emulator), 2),
-            InstructionAND::A_R8(arg) => (arg.get(emulator), 1),
-            InstructionAND::A_N8(arg) => (arg.get(emulator), 2),
+    fn execute<B: Bus>(&self, cpu: &mut Cpu, bus: &mut B) -> usize {
+        let value = match self {
+            InstructionAND::A_R8(arg) => arg.get(cpu, bus),
+            InstructionAND::A_N8(arg) => arg.get(cpu),
         };
 
-        let register_a = emulator.accumulator_and_flags.high();
+        let register_a = cpu.accumulator_and_flags.high();
 
         let result = register_a & value;
-        emulator.accumulator_and_flags.set_high(result);
+        cpu.accumulator_and_flags.set_high(result);
 
-        let flags = emulator.accumulator_and_flags.low_mut();
+        let flags = cpu.accumulator_and_flags.low_mut();
 
         set_flag(flags, FLAG_ZERO, result == 0);
         set_flag(flags, FLAG_SUBTRACT, false);
         set_flag(flags, FLAG_HALF_CARRY, true);
         set_flag(flags, FLAG_CARRY, false);
 
-        cycles
+        self.info().cycles
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        match self {
+            Self::A_R8(r8) => vec![0xA0 | r8.to_bits()],
+            Self::A_N8(n8) => vec![0xE6, n8.0],
+        }
+    }
+
+    fn info(&self) -> InstructionInfo {
+        const FLAGS: FlagsEffect = FlagsEffect::from_notation(b"Z010");
+
+        match self {
+            Self::A_R8(r8) => InstructionInfo::new(1, 1 + r8.access_cycles(), FLAGS),
+            Self::A_N8(_) => InstructionInfo::new(2, 2, FLAGS),
+        }
+    }
+}
+
+impl fmt::Display for InstructionAND {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::A_R8(r8) => write!(f, "and a, {r8}"),
+            Self::A_N8(n8) => write!(f, "and a, {n8}"),
+        }
     }
 }
@@ -1,4 +1,5 @@
 use crate::*;
+use std::fmt;
 
 /// Test bit u3 in register r8, set the zero flag if bit not set.
 ///
@@ -14,24 +15,38 @@ use crate::*;
 pub struct InstructionBIT(pub ArgumentU3, pub ArgumentR8);
 
 impl InstructionTrait for InstructionBIT {
-    fn execute(&self, emulator: &mut Emulator) -> usize {
+    fn execute<B: Bus>(&self, cpu: &mut Cpu, bus: &mut B) -> usize {
         let (bit, register) = (self.0, self.1);
 
-        let value = register.get(emulator);
-        let bit = bit.get(emulator);
+        let value = register.get(cpu, bus);
+        let bit = bit.get(cpu);
 
         let bit_set = (value & (1 << bit)) != 0;
 
-        let flags = emulator.accumulator_and_flags.low_mut();
+        let flags = cpu.accumulator_and_flags.low_mut();
 
         set_flag(flags, FLAG_ZERO, !bit_set);
         set_flag(flags, FLAG_SUBTRACT, false);
         set_flag(flags, FLAG_HALF_CARRY, true);
 
-        if register == ArgumentR8::AtHL {
-            3
-        } else {
-            2
-        }
+        self.info().cycles
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        vec![0xCB, 0x40 | u8::from(self.0) << 3 | self.1.to_bits()]
+    }
+
+    fn info(&self) -> InstructionInfo {
+        InstructionInfo::new(
+            2,
+            2 + self.1.access_cycles(),
+            FlagsEffect::from_notation(b"Z01-"),
+        )
+    }
+}
+
+impl fmt::Display for InstructionBIT {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "bit {}, {}", self.0, self.1)
     }
 }
@@ -1,4 +1,5 @@
 use crate::*;
+use std::fmt;
 
 #[allow(non_camel_case_types)]
 #[derive(Copy, Debug, Clone, PartialEq, Eq, Ord, PartialOrd, Hash)]
@@ -12,26 +13,52 @@ pub enum InstructionCALL {
 }
 
 impl InstructionTrait for InstructionCALL {
-    fn execute(&self, emulator: &mut Emulator) -> usize {
-        match *self {
-            Self::N16(address) => {
-                exec_call(emulator, address);
-                6
+    fn execute<B: Bus>(&self, cpu: &mut Cpu, bus: &mut B) -> usize {
+        let (condition, address) = match *self {
+            Self::N16(address) => (true, address),
+            Self::CC_N16(condition, address) => (condition.get(cpu), address),
+        };
+
+        if condition {
+            exec_call(cpu, bus, address);
+        }
+
+        self.info().duration(condition)
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        match self {
+            Self::N16(n16) => {
+                let [low, high] = n16.to_bytes();
+                vec![0xCD, low, high]
             }
-            Self::CC_N16(condition, address) => {
-                if condition.get(emulator) {
-                    exec_call(emulator, address);
-                    6
-                } else {
-                    3
-                }
+            Self::CC_N16(cc, n16) => {
+                let [low, high] = n16.to_bytes();
+                vec![0xC4 | cc.to_bits() << 3, low, high]
             }
         }
     }
+
+    fn info(&self) -> InstructionInfo {
+        match self {
+            Self::N16(_) => InstructionInfo::new(3, 6, FlagsEffect::NONE),
+            Self::CC_N16(..) => InstructionInfo::conditional(3, 3, 6),
+        }
+    }
+}
+
+fn exec_call<B: Bus>(cpu: &mut Cpu, bus: &mut B, address: ArgumentN16) {
+    let return_address = cpu.program_counter;
+    bus.cpu_idle();
+    cpu.program_counter = address.get(cpu).into();
+    cpu.push_to_stack(bus, return_address);
 }
 
-fn exec_call(emulator: &mut Emulator, address: ArgumentN16) {
-    let return_address = emulator.program_counter;
-    emulator.program_counter = address.get(emulator).into();
-    emulator.push_to_stack(return_address);
+impl fmt::Display for InstructionCALL {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::N16(n16) => write!(f, "call {n16}"),
+            Self::CC_N16(cc, n16) => write!(f, "call {cc}, {n16}"),
+        }
+    }
 }
@@ -1,4 +1,5 @@
 use crate::*;
+use std::fmt;
 
 /// Complement Carry Flag
 ///
@@ -16,13 +17,27 @@ use crate::*;
 pub struct InstructionCCF;
 
 impl InstructionTrait for InstructionCCF {
-    fn execute(&self, emulator: &mut Emulator) -> usize {
-        let flags = emulator.accumulator_and_flags.low_mut();
+    fn execute<B: Bus>(&self, cpu: &mut Cpu, _bus: &mut B) -> usize {
+        let flags = cpu.accumulator_and_flags.low_mut();
 
         set_flag(flags, FLAG_SUBTRACT, false);
         set_flag(flags, FLAG_HALF_CARRY, false);
         invert_flag(flags, FLAG_CARRY);
 
-        1
+        self.info().cycles
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        vec![0x3F]
+    }
+
+    fn info(&self) -> InstructionInfo {
+        InstructionInfo::new(1, 1, FlagsEffect::from_notation(b"-00C"))
+    }
+}
+
+impl fmt::Display for InstructionCCF {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("ccf")
     }
 }
@@ -1,4 +1,5 @@
 use crate::*;
+use std::fmt;
 
 /// Subtract the input value from A and set flags accordingly, but don't store
 /// the result. This is useful for ComParing values.
@@ -19,42 +20,61 @@ pub enum InstructionCP {
 }
 
 impl InstructionTrait for InstructionCP {
-    fn execute(&self, emulator: &mut Emulator) -> usize {
+    fn execute<B: Bus>(&self, cpu: &mut Cpu, bus: &mut B) -> usize {
         match self {
             Self::A_R8(reg) => {
-                let register_a = emulator.accumulator_and_flags.high();
-                let value = reg.get(emulator);
+                let register_a = cpu.accumulator_and_flags.high();
+                let value = reg.get(cpu, bus);
 
-                update_flags(emulator, register_a, value);
-
-                if *reg == ArgumentR8::AtHL {
-                    2
-                } else {
-                    1
-                }
+                update_flags(cpu, register_a, value);
             }
             Self::A_N8(arg) => {
-                let register_a = emulator.accumulator_and_flags.high();
-                let value = arg.get(emulator);
-
-                update_flags(emulator, register_a, value);
+                let register_a = cpu.accumulator_and_flags.high();
+                let value = arg.get(cpu);
 
-                2
+                update_flags(cpu, register_a, value);
             }
         }
+
+        self.info().cycles
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        match self {
+            Self::A_R8(r8) => vec![0xB8 | r8.to_bits()],
+            Self::A_N8(n8) => vec![0xFE, n8.0],
+        }
+    }
+
+    fn info(&self) -> InstructionInfo {
+        const FLAGS: FlagsEffect = FlagsEffect::from_notation(b"Z1HC");
+
+        match self {
+            Self::A_R8(r8) => InstructionInfo::new(1, 1 + r8.access_cycles(), FLAGS),
+            Self::A_N8(_) => InstructionInfo::new(2, 2, FLAGS),
+        }
     }
 }
 
-fn update_flags(emulator: &mut Emulator, register_a: u8, value: u8) {
+fn update_flags(cpu: &mut Cpu, register_a: u8, value: u8) {
     let borrow = register_a.wrapping_sub(value);
 
     let half_carry = (register_a & 0x0F) < (value & 0x0F);
     let carry = register_a < value;
 
-    let flags = emulator.accumulator_and_flags.low_mut();
+    let flags = cpu.accumulator_and_flags.low_mut();
 
     set_flag(flags, FLAG_ZERO, borrow == 0);
     set_flag(flags, FLAG_SUBTRACT, true);
     set_flag(flags, FLAG_HALF_CARRY, half_carry);
     set_flag(flags, FLAG_CARRY, carry);
 }
+
+impl fmt::Display for InstructionCP {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::A_R8(r8) => write!(f, "cp a, {r8}"),
+            Self::A_N8(n8) => write!(f, "cp a, {n8}"),
+        }
+    }
+}
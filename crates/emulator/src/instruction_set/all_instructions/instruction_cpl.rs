@@ -1,4 +1,5 @@
 use crate::*;
+use std::fmt;
 
 /// ComPLement accumulator (A = ~A).
 ///
@@ -13,15 +14,29 @@ use crate::*;
 pub struct InstructionCPL;
 
 impl InstructionTrait for InstructionCPL {
-    fn execute(&self, emulator: &mut Emulator) -> usize {
-        let register_a = emulator.accumulator_and_flags.high_mut();
+    fn execute<B: Bus>(&self, cpu: &mut Cpu, _bus: &mut B) -> usize {
+        let register_a = cpu.accumulator_and_flags.high_mut();
         *register_a ^= 0xFF;
 
-        let flags = emulator.accumulator_and_flags.low_mut();
+        let flags = cpu.accumulator_and_flags.low_mut();
 
         set_flag(flags, FLAG_HALF_CARRY, true);
         set_flag(flags, FLAG_SUBTRACT, true);
 
-        1
+        self.info().cycles
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        vec![0x2F]
+    }
+
+    fn info(&self) -> InstructionInfo {
+        InstructionInfo::new(1, 1, FlagsEffect::from_notation(b"-11-"))
+    }
+}
+
+impl fmt::Display for InstructionCPL {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("cpl")
     }
 }
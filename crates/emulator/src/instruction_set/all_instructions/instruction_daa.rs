@@ -1,4 +1,5 @@
 use crate::*;
+use std::fmt;
 
 /// Decimal Adjust Accumulator to get a correct BCD representation after an arithmetic instruction.
 ///
@@ -14,10 +15,10 @@ use crate::*;
 pub struct InstructionDAA;
 
 impl InstructionTrait for InstructionDAA {
-    fn execute(&self, emulator: &mut Emulator) -> usize {
-        let mut value = emulator.accumulator_and_flags.high(); // B2 (178)
+    fn execute<B: Bus>(&self, cpu: &mut Cpu, _bus: &mut B) -> usize {
+        let mut value = cpu.accumulator_and_flags.high(); // B2 (178)
 
-        let flags = emulator.accumulator_and_flags.low(); // 0111
+        let flags = cpu.accumulator_and_flags.low(); // 0111
         let half_carry = get_flag(flags, FLAG_HALF_CARRY); // 1
         let carry = get_flag(flags, FLAG_CARRY); // 1
         let subtract = get_flag(flags, FLAG_SUBTRACT); // 1
@@ -43,7 +44,7 @@ impl InstructionTrait for InstructionDAA {
             }
         }
 
-        let flags = emulator.accumulator_and_flags.low_mut();
+        let flags = cpu.accumulator_and_flags.low_mut();
 
         set_flag(flags, FLAG_ZERO, value == 0);
         set_flag(flags, FLAG_HALF_CARRY, false);
@@ -51,8 +52,22 @@ impl InstructionTrait for InstructionDAA {
             set_flag(flags, FLAG_CARRY, true);
         }
 
-        emulator.accumulator_and_flags.set_high(value);
+        cpu.accumulator_and_flags.set_high(value);
 
-        1
+        self.info().cycles
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        vec![0x27]
+    }
+
+    fn info(&self) -> InstructionInfo {
+        InstructionInfo::new(1, 1, FlagsEffect::from_notation(b"Z-0C"))
+    }
+}
+
+impl fmt::Display for InstructionDAA {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("daa")
     }
 }
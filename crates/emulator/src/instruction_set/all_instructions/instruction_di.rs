@@ -1,4 +1,5 @@
 use crate::*;
+use std::fmt;
 
 /// Disable Interrupts by clearing the IME flag.
 ///
@@ -8,9 +9,23 @@ use crate::*;
 pub struct InstructionDI;
 
 impl InstructionTrait for InstructionDI {
-    fn execute(&self, emulator: &mut Emulator) -> usize {
-        emulator.ime_flag = false;
+    fn execute<B: Bus>(&self, cpu: &mut Cpu, _bus: &mut B) -> usize {
+        cpu.ime_flag = false;
 
-        1
+        self.info().cycles
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        vec![0xF3]
+    }
+
+    fn info(&self) -> InstructionInfo {
+        InstructionInfo::new(1, 1, FlagsEffect::NONE)
+    }
+}
+
+impl fmt::Display for InstructionDI {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("di")
     }
 }
@@ -1,4 +1,5 @@
 use crate::*;
+use std::fmt;
 
 /// Enable Interrupts by setting the IME flag. The flag is only set after the instruction following EI.
 #[allow(non_camel_case_types)]
@@ -6,9 +7,23 @@ use crate::*;
 pub struct InstructionEI;
 
 impl InstructionTrait for InstructionEI {
-    fn execute(&self, emulator: &mut Emulator) -> usize {
-        emulator.delayed_ime_set = true;
+    fn execute<B: Bus>(&self, cpu: &mut Cpu, _bus: &mut B) -> usize {
+        cpu.delayed_ime_set = true;
 
-        1
+        self.info().cycles
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        vec![0xFB]
+    }
+
+    fn info(&self) -> InstructionInfo {
+        InstructionInfo::new(1, 1, FlagsEffect::NONE)
+    }
+}
+
+impl fmt::Display for InstructionEI {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("ei")
     }
 }
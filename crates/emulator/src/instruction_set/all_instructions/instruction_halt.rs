@@ -1,4 +1,5 @@
 use crate::*;
+use std::fmt;
 
 /// Enter CPU low-power consumption mode until an interrupt occurs. The exact behavior of this instruction depends on the state of the IME flag.
 ///
@@ -15,7 +16,32 @@ use crate::*;
 pub struct InstructionHALT;
 
 impl InstructionTrait for InstructionHALT {
-    fn execute(&self, _cpu: &mut Emulator) -> usize {
-        todo!("Implement HALT instruction")
+    fn execute<B: Bus>(&self, cpu: &mut Cpu, bus: &mut B) -> usize {
+        let is_interrupt_pending = bus.pending_interrupts() != 0;
+
+        if !is_interrupt_pending {
+            cpu.state = CpuState::Halted;
+        } else if !cpu.ime_flag {
+            cpu.halt_bug = true;
+        }
+
+        // if IME is set and an interrupt is pending, it will be serviced right
+        // after this instruction without entering low-power mode
+
+        self.info().cycles
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        vec![0x76]
+    }
+
+    fn info(&self) -> InstructionInfo {
+        InstructionInfo::new(1, 1, FlagsEffect::NONE)
+    }
+}
+
+impl fmt::Display for InstructionHALT {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("halt")
     }
 }
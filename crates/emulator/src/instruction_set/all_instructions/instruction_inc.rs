@@ -1,4 +1,5 @@
 use crate::*;
+use std::fmt;
 
 #[allow(non_camel_case_types)]
 #[derive(Copy, Debug, Clone, PartialEq, Eq, Ord, PartialOrd, Hash)]
@@ -20,13 +21,13 @@ pub enum InstructionINC {
 }
 
 impl InstructionTrait for InstructionINC {
-    fn execute(&self, emulator: &mut Emulator) -> usize {
+    fn execute<B: Bus>(&self, cpu: &mut Cpu, bus: &mut B) -> usize {
         match self {
             Self::R8(reg) => {
-                let prev_value = reg.get(emulator);
+                let prev_value = reg.get(cpu, bus);
                 let result = prev_value.wrapping_add(1);
 
-                let flags = emulator.accumulator_and_flags.low_mut();
+                let flags = cpu.accumulator_and_flags.low_mut();
 
                 let half_carry = (prev_value & 0x0F) + 1 > 0x0F;
 
@@ -34,22 +35,44 @@ impl InstructionTrait for InstructionINC {
                 set_flag(flags, FLAG_SUBTRACT, false);
                 set_flag(flags, FLAG_HALF_CARRY, half_carry);
 
-                reg.set(emulator, result);
-
-                if *reg == ArgumentR8::AtHL {
-                    3
-                } else {
-                    1
-                }
+                reg.set(cpu, bus, result);
             }
             Self::R16(reg) => {
-                let value = reg.get(emulator);
+                let value = reg.get(cpu);
                 let result = value.wrapping_add(1);
 
-                reg.set(emulator, result);
-
-                2
+                reg.set(cpu, bus, result);
+                bus.cpu_idle();
             }
         }
+
+        self.info().cycles
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        match self {
+            Self::R8(r8) => vec![0x04 | r8.to_bits() << 3],
+            Self::R16(r16) => vec![0x03 | r16.to_bits() << 4],
+        }
+    }
+
+    fn info(&self) -> InstructionInfo {
+        match self {
+            Self::R8(r8) => InstructionInfo::new(
+                1,
+                1 + 2 * r8.access_cycles(),
+                FlagsEffect::from_notation(b"Z0H-"),
+            ),
+            Self::R16(_) => InstructionInfo::new(1, 2, FlagsEffect::NONE),
+        }
+    }
+}
+
+impl fmt::Display for InstructionINC {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::R8(r8) => write!(f, "inc {r8}"),
+            Self::R16(r16) => write!(f, "inc {r16}"),
+        }
     }
 }
@@ -1,4 +1,5 @@
 use crate::*;
+use std::fmt;
 
 #[allow(non_camel_case_types)]
 #[derive(Copy, Debug, Clone, PartialEq, Eq, Ord, PartialOrd, Hash)]
@@ -12,24 +13,59 @@ pub enum InstructionJP {
 }
 
 impl InstructionTrait for InstructionJP {
-    fn execute(&self, emulator: &mut Emulator) -> usize {
-        match self {
+    fn execute<B: Bus>(&self, cpu: &mut Cpu, bus: &mut B) -> usize {
+        let branch_taken = match self {
             Self::N16(to_address) => {
-                emulator.program_counter = to_address.get(emulator).into();
-                4
+                cpu.program_counter = to_address.get(cpu).into();
+                bus.cpu_idle();
+                true
             }
             Self::CC_N16(condition, to_address) => {
-                if condition.get(emulator) {
-                    emulator.program_counter = to_address.get(emulator).into();
-                    4
-                } else {
-                    3
+                let branch_taken = condition.get(cpu);
+                if branch_taken {
+                    cpu.program_counter = to_address.get(cpu).into();
+                    bus.cpu_idle();
                 }
+                branch_taken
             }
             Self::HL => {
-                emulator.program_counter = emulator.register_hl.as_u16().into();
-                1
+                cpu.program_counter = cpu.register_hl.as_u16().into();
+                true
+            }
+        };
+
+        self.info().duration(branch_taken)
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        match self {
+            Self::N16(n16) => {
+                let [low, high] = n16.to_bytes();
+                vec![0xC3, low, high]
             }
+            Self::CC_N16(cc, n16) => {
+                let [low, high] = n16.to_bytes();
+                vec![0xC2 | cc.to_bits() << 3, low, high]
+            }
+            Self::HL => vec![0xE9],
+        }
+    }
+
+    fn info(&self) -> InstructionInfo {
+        match self {
+            Self::N16(_) => InstructionInfo::new(3, 4, FlagsEffect::NONE),
+            Self::CC_N16(..) => InstructionInfo::conditional(3, 3, 4),
+            Self::HL => InstructionInfo::new(1, 1, FlagsEffect::NONE),
+        }
+    }
+}
+
+impl fmt::Display for InstructionJP {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::N16(n16) => write!(f, "jp {n16}"),
+            Self::CC_N16(cc, n16) => write!(f, "jp {cc}, {n16}"),
+            Self::HL => f.write_str("jp hl"),
         }
     }
 }
@@ -1,4 +1,5 @@
 use crate::*;
+use std::fmt;
 
 #[allow(non_camel_case_types)]
 #[derive(Copy, Debug, Clone, PartialEq, Eq, Ord, PartialOrd, Hash)]
@@ -8,22 +9,42 @@ pub enum InstructionJR {
 }
 
 impl InstructionTrait for InstructionJR {
-    fn execute(&self, emulator: &mut Emulator) -> usize {
+    fn execute<B: Bus>(&self, cpu: &mut Cpu, bus: &mut B) -> usize {
+        let (condition, offset) = match *self {
+            Self::E8(offset) => (true, offset),
+            Self::CC_E8(condition, offset) => (condition.get(cpu), offset),
+        };
+
+        if condition {
+            cpu.program_counter = offset.apply_offset(cpu.program_counter.into()).into();
+            bus.cpu_idle();
+        }
+
+        self.info().duration(condition)
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        match self {
+            Self::E8(e8) => vec![0x18, e8.as_u8()],
+            Self::CC_E8(cc, e8) => vec![0x20 | cc.to_bits() << 3, e8.as_u8()],
+        }
+    }
+
+    fn info(&self) -> InstructionInfo {
+        match self {
+            Self::E8(_) => InstructionInfo::new(2, 3, FlagsEffect::NONE),
+            Self::CC_E8(..) => InstructionInfo::conditional(2, 2, 3),
+        }
+    }
+}
+
+impl fmt::Display for InstructionJR {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // target is relative to the start of the instruction (`@`), the offset
+        // itself is relative to the end of the 2 bytes long instruction
         match self {
-            Self::E8(offset) => {
-                emulator.program_counter =
-                    offset.apply_offset(emulator.program_counter.into()).into();
-                3
-            }
-            Self::CC_E8(condition, offset) => {
-                if condition.get(emulator) {
-                    emulator.program_counter =
-                        offset.apply_offset(emulator.program_counter.into()).into();
-                    3
-                } else {
-                    2
-                }
-            }
+            Self::E8(e8) => write!(f, "jr @{:+}", e8.0 as i16 + 2),
+            Self::CC_E8(cc, e8) => write!(f, "jr {cc}, @{:+}", e8.0 as i16 + 2),
         }
     }
 }
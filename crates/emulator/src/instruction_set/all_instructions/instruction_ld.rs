@@ -1,4 +1,5 @@
 use crate::*;
+use std::fmt;
 
 /// Load instructions. These instructions are used to move data from
 /// register/value/memory on the right to location on the left.
@@ -37,111 +38,156 @@ pub enum InstructionLD {
 }
 
 impl InstructionTrait for InstructionLD {
-    fn execute(&self, emulator: &mut Emulator) -> usize {
+    fn execute<B: Bus>(&self, cpu: &mut Cpu, bus: &mut B) -> usize {
         match *self {
+            Self::R8_R8(ArgumentR8::AtHL, ArgumentR8::AtHL) => {
+                // impossible, encoded as HALT
+                return 0;
+            }
             Self::R8_R8(to, from) => {
-                to.set(emulator, from.get(emulator));
-
-                match (to, from) {
-                    // impossible
-                    (ArgumentR8::AtHL, ArgumentR8::AtHL) => 0,
-                    (ArgumentR8::AtHL, _) => 2,
-                    (_, ArgumentR8::AtHL) => 2,
-                    _ => 1,
-                }
+                let value = from.get(cpu, bus);
+                to.set(cpu, bus, value);
             }
             Self::R8_N8(to, from) => {
-                to.set(emulator, from.get(emulator));
-
-                match to {
-                    ArgumentR8::AtHL => 3,
-                    _ => 2,
-                }
+                let value = from.get(cpu);
+                to.set(cpu, bus, value);
             }
             Self::R16_N16(to, from) => {
-                to.set(emulator, from.get(emulator));
-
-                3
+                let value = from.get(cpu);
+                to.set(cpu, bus, value);
             }
             Self::AtR16_A(to) => {
-                to.set_at(emulator, emulator.accumulator_and_flags.high());
-
-                2
+                to.set_at(cpu, bus, cpu.accumulator_and_flags.high());
             }
             Self::AtN16_A(to) => {
-                to.set_at(emulator, emulator.accumulator_and_flags.high());
-
-                4
+                to.set_at(cpu, bus, cpu.accumulator_and_flags.high());
             }
             Self::A_AtR16(from) => {
-                emulator
-                    .accumulator_and_flags
-                    .set_high(from.get_at(emulator));
-
-                2
+                let value = from.get_at(cpu, bus);
+                cpu.accumulator_and_flags.set_high(value);
             }
             Self::A_AtN16(from) => {
-                emulator
-                    .accumulator_and_flags
-                    .set_high(from.get_at(emulator));
-
-                4
+                let value = from.get_at(cpu, bus);
+                cpu.accumulator_and_flags.set_high(value);
             }
             Self::AtHLI_A => {
-                let value = emulator.accumulator_and_flags.high();
-                emulator.register_hl.clone().set_at(emulator, value);
-                emulator.register_hl.increment();
-
-                2
+                let value = cpu.accumulator_and_flags.high();
+                cpu.register_hl.clone().set_at(cpu, bus, value);
+                cpu.register_hl.increment();
             }
             Self::AtHLD_A => {
-                let value = emulator.accumulator_and_flags.high();
-                emulator.register_hl.clone().set_at(emulator, value);
-                emulator.register_hl.decrement();
-
-                2
+                let value = cpu.accumulator_and_flags.high();
+                cpu.register_hl.clone().set_at(cpu, bus, value);
+                cpu.register_hl.decrement();
             }
             Self::A_AtHLI => {
-                emulator
-                    .accumulator_and_flags
-                    .set_high(emulator.register_hl.get_at(emulator));
-                emulator.register_hl.increment();
-
-                2
+                let value = cpu.register_hl.clone().get_at(cpu, bus);
+                cpu.accumulator_and_flags.set_high(value);
+                cpu.register_hl.increment();
             }
             Self::A_AtHLD => {
-                emulator
-                    .accumulator_and_flags
-                    .set_high(emulator.register_hl.get_at(emulator));
-                emulator.register_hl.decrement();
-
-                2
+                let value = cpu.register_hl.clone().get_at(cpu, bus);
+                cpu.accumulator_and_flags.set_high(value);
+                cpu.register_hl.decrement();
             }
             Self::AtN16_SP(to) => {
-                let value: u16 = emulator.stack_pointer.into();
-                let address = to.get(emulator);
-
-                emulator.set(address, (value & 0xFF) as u8);
-                emulator.set(address.wrapping_add(1), (value >> 8) as u8);
+                let value: u16 = cpu.stack_pointer.into();
+                let address = to.get(cpu);
 
-                5
+                bus.cpu_write(address, (value & 0xFF) as u8);
+                bus.cpu_write(address.wrapping_add(1), (value >> 8) as u8);
             }
             Self::HL_SP_E8(offset) => {
-                let flags = emulator.accumulator_and_flags.low_mut();
-                let value = offset.apply_offset_with_flags(emulator.stack_pointer.into(), flags);
+                let flags = cpu.accumulator_and_flags.low_mut();
+                let value = offset.apply_offset_with_flags(cpu.stack_pointer.into(), flags);
 
                 set_flag(flags, FLAG_ZERO, false);
                 set_flag(flags, FLAG_SUBTRACT, false);
 
-                *emulator.register_hl.as_u16_mut() = value;
-
-                3
+                *cpu.register_hl.as_u16_mut() = value;
+                bus.cpu_idle();
             }
             Self::SP_HL => {
-                emulator.stack_pointer = emulator.register_hl.as_u16().into();
+                cpu.stack_pointer = cpu.register_hl.as_u16().into();
+                bus.cpu_idle();
+            }
+        }
+
+        self.info().cycles
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        match self {
+            Self::R8_R8(to, from) => vec![0x40 | to.to_bits() << 3 | from.to_bits()],
+            Self::R8_N8(to, n8) => vec![0x06 | to.to_bits() << 3, n8.0],
+            Self::R16_N16(to, n16) => {
+                let [low, high] = n16.to_bytes();
+                vec![0x01 | to.to_bits() << 4, low, high]
+            }
+            Self::AtR16_A(to) => vec![0x02 | to.to_bits() << 4],
+            Self::AtN16_A(n16) => {
+                let [low, high] = n16.to_bytes();
+                vec![0xEA, low, high]
+            }
+            Self::A_AtR16(from) => vec![0x0A | from.to_bits() << 4],
+            Self::A_AtN16(n16) => {
+                let [low, high] = n16.to_bytes();
+                vec![0xFA, low, high]
+            }
+            Self::AtHLI_A => vec![0x22],
+            Self::AtHLD_A => vec![0x32],
+            Self::A_AtHLI => vec![0x2A],
+            Self::A_AtHLD => vec![0x3A],
+            Self::AtN16_SP(n16) => {
+                let [low, high] = n16.to_bytes();
+                vec![0x08, low, high]
+            }
+            Self::HL_SP_E8(e8) => vec![0xF8, e8.as_u8()],
+            Self::SP_HL => vec![0xF9],
+        }
+    }
+
+    fn info(&self) -> InstructionInfo {
+        let flags = FlagsEffect::NONE;
+
+        match self {
+            Self::R8_R8(to, from) => {
+                InstructionInfo::new(1, 1 + to.access_cycles() + from.access_cycles(), flags)
+            }
+            Self::R8_N8(to, _) => InstructionInfo::new(2, 2 + to.access_cycles(), flags),
+            Self::R16_N16(..) => InstructionInfo::new(3, 3, flags),
+            Self::AtR16_A(_) | Self::A_AtR16(_) => InstructionInfo::new(1, 2, flags),
+            Self::AtN16_A(_) | Self::A_AtN16(_) => InstructionInfo::new(3, 4, flags),
+            Self::AtHLI_A | Self::AtHLD_A | Self::A_AtHLI | Self::A_AtHLD => {
+                InstructionInfo::new(1, 2, flags)
+            }
+            Self::AtN16_SP(_) => InstructionInfo::new(3, 5, flags),
+            Self::HL_SP_E8(_) => InstructionInfo::new(2, 3, FlagsEffect::from_notation(b"00HC")),
+            Self::SP_HL => InstructionInfo::new(1, 2, flags),
+        }
+    }
+}
 
-                2
+impl fmt::Display for InstructionLD {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::R8_R8(to, from) => write!(f, "ld {to}, {from}"),
+            Self::R8_N8(to, n8) => write!(f, "ld {to}, {n8}"),
+            Self::R16_N16(to, n16) => write!(f, "ld {to}, {n16}"),
+            Self::AtR16_A(to) => write!(f, "ld [{to}], a"),
+            Self::AtN16_A(n16) => write!(f, "ld [{n16}], a"),
+            Self::A_AtR16(from) => write!(f, "ld a, [{from}]"),
+            Self::A_AtN16(n16) => write!(f, "ld a, [{n16}]"),
+            Self::AtHLI_A => f.write_str("ld [hl+], a"),
+            Self::AtHLD_A => f.write_str("ld [hl-], a"),
+            Self::A_AtHLI => f.write_str("ld a, [hl+]"),
+            Self::A_AtHLD => f.write_str("ld a, [hl-]"),
+            Self::AtN16_SP(n16) => write!(f, "ld [{n16}], sp"),
+            Self::HL_SP_E8(e8) => {
+                let sign = if e8.0 < 0 { '-' } else { '+' };
+                write!(f, "ld hl, sp {sign} {}", e8.0.unsigned_abs())
             }
+            Self::SP_HL => f.write_str("ld sp, hl"),
         }
     }
 }
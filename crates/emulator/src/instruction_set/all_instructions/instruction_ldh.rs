@@ -1,4 +1,5 @@
 use crate::*;
+use std::fmt;
 
 #[allow(non_camel_case_types)]
 #[derive(Copy, Debug, Clone, PartialEq, Eq, Ord, PartialOrd, Hash)]
@@ -14,37 +15,55 @@ pub enum InstructionLDH {
 }
 
 impl InstructionTrait for InstructionLDH {
-    fn execute(&self, emulator: &mut Emulator) -> usize {
+    fn execute<B: Bus>(&self, cpu: &mut Cpu, bus: &mut B) -> usize {
         match self {
             Self::AtN8_A(to) => {
-                emulator.set(
-                    0xFF00 + to.get(emulator) as u16,
-                    emulator.accumulator_and_flags.high(),
-                );
-
-                3
+                let address = 0xFF00 + to.get(cpu) as u16;
+                bus.cpu_write(address, cpu.accumulator_and_flags.high());
             }
             Self::AtC_A => {
-                emulator.set(
-                    0xFF00 + emulator.register_bc.low() as u16,
-                    emulator.accumulator_and_flags.high(),
-                );
-
-                2
+                let address = 0xFF00 + cpu.register_bc.low() as u16;
+                bus.cpu_write(address, cpu.accumulator_and_flags.high());
             }
             Self::A_AtN8(from) => {
-                let value = emulator.get(0xFF00 + from.get(emulator) as u16);
-                emulator.accumulator_and_flags.set_high(value);
-
-                3
+                let address = 0xFF00 + from.get(cpu) as u16;
+                let value = bus.cpu_read(address);
+                cpu.accumulator_and_flags.set_high(value);
             }
             Self::A_AtC => {
-                emulator
-                    .accumulator_and_flags
-                    .set_high(emulator.get(0xFF00 + emulator.register_bc.low() as u16));
-
-                2
+                let address = 0xFF00 + cpu.register_bc.low() as u16;
+                let value = bus.cpu_read(address);
+                cpu.accumulator_and_flags.set_high(value);
             }
         }
+
+        self.info().cycles
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        match self {
+            Self::AtN8_A(n8) => vec![0xE0, n8.0],
+            Self::AtC_A => vec![0xE2],
+            Self::A_AtN8(n8) => vec![0xF0, n8.0],
+            Self::A_AtC => vec![0xF2],
+        }
+    }
+
+    fn info(&self) -> InstructionInfo {
+        match self {
+            Self::AtN8_A(_) | Self::A_AtN8(_) => InstructionInfo::new(2, 3, FlagsEffect::NONE),
+            Self::AtC_A | Self::A_AtC => InstructionInfo::new(1, 2, FlagsEffect::NONE),
+        }
+    }
+}
+
+impl fmt::Display for InstructionLDH {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::AtN8_A(n8) => write!(f, "ldh [${:04X}], a", 0xFF00 + n8.0 as u16),
+            Self::AtC_A => f.write_str("ldh [c], a"),
+            Self::A_AtN8(n8) => write!(f, "ldh a, [${:04X}]", 0xFF00 + n8.0 as u16),
+            Self::A_AtC => f.write_str("ldh a, [c]"),
+        }
     }
 }
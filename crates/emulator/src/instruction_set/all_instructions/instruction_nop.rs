@@ -1,11 +1,26 @@
 use crate::*;
+use std::fmt;
 
 #[allow(non_camel_case_types)]
 #[derive(Copy, Debug, Clone, PartialEq, Eq, Ord, PartialOrd, Hash)]
 pub struct InstructionNOP;
 
 impl InstructionTrait for InstructionNOP {
-    fn execute(&self, _cpu: &mut Emulator) -> usize {
-        1
+    fn execute<B: Bus>(&self, _cpu: &mut Cpu, _bus: &mut B) -> usize {
+        self.info().cycles
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        vec![0x00]
+    }
+
+    fn info(&self) -> InstructionInfo {
+        InstructionInfo::new(1, 1, FlagsEffect::NONE)
+    }
+}
+
+impl fmt::Display for InstructionNOP {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("nop")
     }
 }
@@ -1,4 +1,5 @@
 use crate::*;
+use std::fmt;
 
 /// Store into A the bitwise OR of input value and A.
 ///
@@ -18,25 +19,49 @@ pub enum InstructionOR {
 }
 
 impl InstructionTrait for InstructionOR {
-    fn execute(&self, emulator: &mut Emulator) -> usize {
-        let (input_value, cycles) = match self {
-            Self::A_R8(arg @ ArgumentR8::AtHL) => (arg.get(emulator), 2),
-            Self::A_R8(arg) => (arg.get(emulator), 1),
-            Self::A_N8(arg) => (arg.get(emulator), 2),
+    fn execute<B: Bus>(&self, cpu: &mut Cpu, bus: &mut B) -> usize {
+        let input_value = match self {
+            Self::A_R8(arg) => arg.get(cpu, bus),
+            Self::A_N8(arg) => arg.get(cpu),
         };
 
-        let register_a = emulator.accumulator_and_flags.high_mut();
+        let register_a = cpu.accumulator_and_flags.high_mut();
 
         let result = *register_a | input_value;
         *register_a = result;
 
-        let flags = emulator.accumulator_and_flags.low_mut();
+        let flags = cpu.accumulator_and_flags.low_mut();
 
         set_flag(flags, FLAG_ZERO, result == 0);
         set_flag(flags, FLAG_SUBTRACT, false);
         set_flag(flags, FLAG_HALF_CARRY, false);
         set_flag(flags, FLAG_CARRY, false);
 
-        cycles
+        self.info().cycles
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        match self {
+            Self::A_R8(r8) => vec![0xB0 | r8.to_bits()],
+            Self::A_N8(n8) => vec![0xF6, n8.0],
+        }
+    }
+
+    fn info(&self) -> InstructionInfo {
+        const FLAGS: FlagsEffect = FlagsEffect::from_notation(b"Z000");
+
+        match self {
+            Self::A_R8(r8) => InstructionInfo::new(1, 1 + r8.access_cycles(), FLAGS),
+            Self::A_N8(_) => InstructionInfo::new(2, 2, FLAGS),
+        }
+    }
+}
+
+impl fmt::Display for InstructionOR {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::A_R8(r8) => write!(f, "or a, {r8}"),
+            Self::A_N8(n8) => write!(f, "or a, {n8}"),
+        }
     }
 }
@@ -1,4 +1,5 @@
 use crate::*;
+use std::fmt;
 
 /// Pop value from stack and store it in 16-bit register.
 ///
@@ -11,16 +12,36 @@ use crate::*;
 pub struct InstructionPOP(pub ArgumentStkR16);
 
 impl InstructionTrait for InstructionPOP {
-    fn execute(&self, emulator: &mut Emulator) -> usize {
+    fn execute<B: Bus>(&self, cpu: &mut Cpu, bus: &mut B) -> usize {
         let Self(reg) = *self;
 
-        let value = emulator.pop_from_stack();
+        let value = cpu.pop_from_stack(bus);
 
-        reg.set(emulator, value);
+        reg.set(cpu, bus, value);
         if reg == ArgumentStkR16::AF {
-            *emulator.accumulator_and_flags.low_mut() &= FLAGS_MASK;
+            *cpu.accumulator_and_flags.low_mut() &= FLAGS_MASK;
         }
 
-        3
+        self.info().cycles
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        vec![0xC1 | self.0.to_bits() << 4]
+    }
+
+    fn info(&self) -> InstructionInfo {
+        // POP AF sets all flags from the stack
+        let flags = match self.0 {
+            ArgumentStkR16::AF => FlagsEffect::from_notation(b"ZNHC"),
+            _ => FlagsEffect::NONE,
+        };
+
+        InstructionInfo::new(1, 3, flags)
+    }
+}
+
+impl fmt::Display for InstructionPOP {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "pop {}", self.0)
     }
 }
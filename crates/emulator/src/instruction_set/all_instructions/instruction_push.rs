@@ -1,4 +1,5 @@
 use crate::*;
+use std::fmt;
 
 /// Push input value to the stack.
 ///
@@ -8,11 +9,27 @@ use crate::*;
 pub struct InstructionPUSH(pub ArgumentStkR16);
 
 impl InstructionTrait for InstructionPUSH {
-    fn execute(&self, emulator: &mut Emulator) -> usize {
+    fn execute<B: Bus>(&self, cpu: &mut Cpu, bus: &mut B) -> usize {
         let Self(reg) = *self;
 
-        emulator.push_to_stack(reg.get(emulator));
+        let value = reg.get(cpu);
+        bus.cpu_idle();
+        cpu.push_to_stack(bus, value);
 
-        4
+        self.info().cycles
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        vec![0xC5 | self.0.to_bits() << 4]
+    }
+
+    fn info(&self) -> InstructionInfo {
+        InstructionInfo::new(1, 4, FlagsEffect::NONE)
+    }
+}
+
+impl fmt::Display for InstructionPUSH {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "push {}", self.0)
     }
 }
@@ -1,4 +1,5 @@
 use crate::*;
+use std::fmt;
 
 /// Set bit u3 in r8 to 0. Bit 0 is the rightmost one, bit 7 the leftmost one.
 ///
@@ -8,18 +9,28 @@ use crate::*;
 pub struct InstructionRES(pub ArgumentU3, pub ArgumentR8);
 
 impl InstructionTrait for InstructionRES {
-    fn execute(&self, emulator: &mut Emulator) -> usize {
+    fn execute<B: Bus>(&self, cpu: &mut Cpu, bus: &mut B) -> usize {
         let Self(bit, reg) = *self;
 
-        let bit = bit.get(emulator);
+        let bit = bit.get(cpu);
 
-        let value = reg.get(emulator);
-        reg.set(emulator, value & !(1 << bit));
+        let value = reg.get(cpu, bus);
+        reg.set(cpu, bus, value & !(1 << bit));
 
-        if reg == ArgumentR8::AtHL {
-            4
-        } else {
-            2
-        }
+        self.info().cycles
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        vec![0xCB, 0x80 | u8::from(self.0) << 3 | self.1.to_bits()]
+    }
+
+    fn info(&self) -> InstructionInfo {
+        InstructionInfo::new(2, 2 + 2 * self.1.access_cycles(), FlagsEffect::NONE)
+    }
+}
+
+impl fmt::Display for InstructionRES {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "res {}, {}", self.0, self.1)
     }
 }
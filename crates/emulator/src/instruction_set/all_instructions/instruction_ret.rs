@@ -1,4 +1,5 @@
 use crate::*;
+use std::fmt;
 
 /// Return from subroutine. This is basically a POP PC (if such an instruction
 /// existed).
@@ -12,19 +13,40 @@ use crate::*;
 pub struct InstructionRET(pub Option<ArgumentCC>);
 
 impl InstructionTrait for InstructionRET {
-    fn execute(&self, emulator: &mut Emulator) -> usize {
-        let mut cycles = 4;
-
+    fn execute<B: Bus>(&self, cpu: &mut Cpu, bus: &mut B) -> usize {
         if let Some(cc) = self.0 {
-            if !cc.get(emulator) {
-                return 2;
+            bus.cpu_idle();
+            if !cc.get(cpu) {
+                return self.info().duration(false);
             }
+        }
+
+        cpu.program_counter = cpu.pop_from_stack(bus);
+        bus.cpu_idle();
+
+        self.info().duration(true)
+    }
 
-            cycles += 1
+    fn encode(&self) -> Vec<u8> {
+        match self.0 {
+            Some(cc) => vec![0xC0 | cc.to_bits() << 3],
+            None => vec![0xC9],
         }
+    }
 
-        emulator.program_counter = emulator.pop_from_stack();
+    fn info(&self) -> InstructionInfo {
+        match self.0 {
+            Some(_) => InstructionInfo::conditional(1, 2, 5),
+            None => InstructionInfo::new(1, 4, FlagsEffect::NONE),
+        }
+    }
+}
 
-        cycles
+impl fmt::Display for InstructionRET {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.0 {
+            Some(cc) => write!(f, "ret {cc}"),
+            None => f.write_str("ret"),
+        }
     }
 }
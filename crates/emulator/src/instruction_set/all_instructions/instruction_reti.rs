@@ -1,14 +1,30 @@
 use crate::*;
+use std::fmt;
 
 #[allow(non_camel_case_types)]
 #[derive(Copy, Debug, Clone, PartialEq, Eq, Ord, PartialOrd, Hash)]
 pub struct InstructionRETI;
 
 impl InstructionTrait for InstructionRETI {
-    fn execute(&self, emulator: &mut Emulator) -> usize {
-        emulator.delayed_ime_set = true;
-        emulator.program_counter = emulator.pop_from_stack();
+    fn execute<B: Bus>(&self, cpu: &mut Cpu, bus: &mut B) -> usize {
+        cpu.delayed_ime_set = true;
+        cpu.program_counter = cpu.pop_from_stack(bus);
+        bus.cpu_idle();
 
-        4
+        self.info().cycles
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        vec![0xD9]
+    }
+
+    fn info(&self) -> InstructionInfo {
+        InstructionInfo::new(1, 4, FlagsEffect::NONE)
+    }
+}
+
+impl fmt::Display for InstructionRETI {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("reti")
     }
 }
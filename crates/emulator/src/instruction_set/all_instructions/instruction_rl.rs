@@ -1,4 +1,5 @@
 use crate::*;
+use std::fmt;
 
 /// Rotate bits in register r8 left, through the carry flag.
 ///
@@ -15,37 +16,51 @@ use crate::*;
 pub struct InstructionRL(pub ArgumentR8);
 
 impl InstructionTrait for InstructionRL {
-    fn execute(&self, emulator: &mut Emulator) -> usize {
+    fn execute<B: Bus>(&self, cpu: &mut Cpu, bus: &mut B) -> usize {
         let Self(reg) = *self;
 
-        let result = rotate_register_left_carry(emulator, reg);
+        let result = rotate_register_left_carry(cpu, bus, reg);
 
-        let flags = emulator.accumulator_and_flags.low_mut();
+        let flags = cpu.accumulator_and_flags.low_mut();
         set_flag(flags, FLAG_ZERO, result == 0);
         set_flag(flags, FLAG_SUBTRACT, false);
         set_flag(flags, FLAG_HALF_CARRY, false);
 
-        if reg == ArgumentR8::AtHL {
-            4
-        } else {
-            2
-        }
+        self.info().cycles
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        vec![0xCB, 0x10 | self.0.to_bits()]
+    }
+
+    fn info(&self) -> InstructionInfo {
+        InstructionInfo::new(
+            2,
+            2 + 2 * self.0.access_cycles(),
+            FlagsEffect::from_notation(b"Z00C"),
+        )
     }
 }
 
 /// Rotate value in register left, through the carry flag.
-pub fn rotate_register_left_carry(emulator: &mut Emulator, reg: ArgumentR8) -> u8 {
-    let value = reg.get(emulator);
+pub fn rotate_register_left_carry<B: Bus>(cpu: &mut Cpu, bus: &mut B, reg: ArgumentR8) -> u8 {
+    let value = reg.get(cpu, bus);
 
-    let flags = emulator.accumulator_and_flags.low();
+    let flags = cpu.accumulator_and_flags.low();
     let old_carry = get_flag(flags, FLAG_CARRY) as u8;
 
     let new_carry = value & 0b1000_0000 != 0;
-    let flags = emulator.accumulator_and_flags.low_mut();
+    let flags = cpu.accumulator_and_flags.low_mut();
     set_flag(flags, FLAG_CARRY, new_carry);
 
     let result = (value << 1) | old_carry;
-    reg.set(emulator, result);
+    reg.set(cpu, bus, result);
 
     result
 }
+
+impl fmt::Display for InstructionRL {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "rl {}", self.0)
+    }
+}
@@ -1,4 +1,5 @@
 use crate::*;
+use std::fmt;
 
 /// Rotate r8 left.
 ///
@@ -15,34 +16,48 @@ use crate::*;
 pub struct InstructionRLC(pub ArgumentR8);
 
 impl InstructionTrait for InstructionRLC {
-    fn execute(&self, emulator: &mut Emulator) -> usize {
+    fn execute<B: Bus>(&self, cpu: &mut Cpu, bus: &mut B) -> usize {
         let Self(reg) = *self;
 
-        let result = rotate_register_left(emulator, reg);
+        let result = rotate_register_left(cpu, bus, reg);
 
-        let flags = emulator.accumulator_and_flags.low_mut();
+        let flags = cpu.accumulator_and_flags.low_mut();
         set_flag(flags, FLAG_ZERO, result == 0);
         set_flag(flags, FLAG_SUBTRACT, false);
         set_flag(flags, FLAG_HALF_CARRY, false);
 
-        if reg == ArgumentR8::AtHL {
-            4
-        } else {
-            2
-        }
+        self.info().cycles
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        vec![0xCB, self.0.to_bits()]
+    }
+
+    fn info(&self) -> InstructionInfo {
+        InstructionInfo::new(
+            2,
+            2 + 2 * self.0.access_cycles(),
+            FlagsEffect::from_notation(b"Z00C"),
+        )
     }
 }
 
 /// Rotate register left. Set carry flag to the value of the bit that was rotated out.
-pub fn rotate_register_left(emulator: &mut Emulator, reg: ArgumentR8) -> u8 {
-    let value = reg.get(emulator);
+pub fn rotate_register_left<B: Bus>(cpu: &mut Cpu, bus: &mut B, reg: ArgumentR8) -> u8 {
+    let value = reg.get(cpu, bus);
 
     let carry = value & 0b1000_0000 != 0;
-    let flags = emulator.accumulator_and_flags.low_mut();
+    let flags = cpu.accumulator_and_flags.low_mut();
     set_flag(flags, FLAG_CARRY, carry);
 
     let result = (value << 1) | carry as u8;
-    reg.set(emulator, result);
+    reg.set(cpu, bus, result);
 
     result
 }
+
+impl fmt::Display for InstructionRLC {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "rlc {}", self.0)
+    }
+}
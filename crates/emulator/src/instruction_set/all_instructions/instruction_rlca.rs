@@ -1,4 +1,5 @@
 use crate::*;
+use std::fmt;
 
 /// Rotate register A left.
 ///
@@ -15,14 +16,28 @@ use crate::*;
 pub struct InstructionRLCA;
 
 impl InstructionTrait for InstructionRLCA {
-    fn execute(&self, emulator: &mut Emulator) -> usize {
-        rotate_register_left(emulator, ArgumentR8::A);
+    fn execute<B: Bus>(&self, cpu: &mut Cpu, bus: &mut B) -> usize {
+        rotate_register_left(cpu, bus, ArgumentR8::A);
 
-        let flags = emulator.accumulator_and_flags.low_mut();
+        let flags = cpu.accumulator_and_flags.low_mut();
         set_flag(flags, FLAG_ZERO, false);
         set_flag(flags, FLAG_SUBTRACT, false);
         set_flag(flags, FLAG_HALF_CARRY, false);
 
-        1
+        self.info().cycles
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        vec![0x07]
+    }
+
+    fn info(&self) -> InstructionInfo {
+        InstructionInfo::new(1, 1, FlagsEffect::from_notation(b"000C"))
+    }
+}
+
+impl fmt::Display for InstructionRLCA {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("rlca")
     }
 }
@@ -1,4 +1,5 @@
 use crate::*;
+use std::fmt;
 
 /// Rotate register A right, through the carry flag.
 ///
@@ -15,14 +16,28 @@ use crate::*;
 pub struct InstructionRRA;
 
 impl InstructionTrait for InstructionRRA {
-    fn execute(&self, emulator: &mut Emulator) -> usize {
-        rotate_register_right_carry(emulator, ArgumentR8::A);
+    fn execute<B: Bus>(&self, cpu: &mut Cpu, bus: &mut B) -> usize {
+        rotate_register_right_carry(cpu, bus, ArgumentR8::A);
 
-        let flags = emulator.accumulator_and_flags.low_mut();
+        let flags = cpu.accumulator_and_flags.low_mut();
         set_flag(flags, FLAG_ZERO, false);
         set_flag(flags, FLAG_SUBTRACT, false);
         set_flag(flags, FLAG_HALF_CARRY, false);
 
-        1
+        self.info().cycles
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        vec![0x1F]
+    }
+
+    fn info(&self) -> InstructionInfo {
+        InstructionInfo::new(1, 1, FlagsEffect::from_notation(b"000C"))
+    }
+}
+
+impl fmt::Display for InstructionRRA {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("rra")
     }
 }
@@ -1,4 +1,5 @@
 use crate::*;
+use std::fmt;
 
 /// Rotate register A right.
 ///
@@ -15,14 +16,28 @@ use crate::*;
 pub struct InstructionRRCA;
 
 impl InstructionTrait for InstructionRRCA {
-    fn execute(&self, emulator: &mut Emulator) -> usize {
-        rotate_register_right(emulator, ArgumentR8::A);
+    fn execute<B: Bus>(&self, cpu: &mut Cpu, bus: &mut B) -> usize {
+        rotate_register_right(cpu, bus, ArgumentR8::A);
 
-        let flags = emulator.accumulator_and_flags.low_mut();
+        let flags = cpu.accumulator_and_flags.low_mut();
         set_flag(flags, FLAG_ZERO, false);
         set_flag(flags, FLAG_SUBTRACT, false);
         set_flag(flags, FLAG_HALF_CARRY, false);
 
-        1
+        self.info().cycles
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        vec![0x0F]
+    }
+
+    fn info(&self) -> InstructionInfo {
+        InstructionInfo::new(1, 1, FlagsEffect::from_notation(b"000C"))
+    }
+}
+
+impl fmt::Display for InstructionRRCA {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("rrca")
     }
 }
@@ -1,4 +1,5 @@
 use crate::*;
+use std::fmt;
 
 /// Call address vec. This is a shorter and faster equivalent to CALL for suitable values of vec.
 ///
@@ -8,13 +9,28 @@ use crate::*;
 pub struct InstructionRST(pub ArgumentVec);
 
 impl InstructionTrait for InstructionRST {
-    fn execute(&self, emulator: &mut Emulator) -> usize {
-        let vec = self.0.get(emulator);
+    fn execute<B: Bus>(&self, cpu: &mut Cpu, bus: &mut B) -> usize {
+        let vec = self.0.get(cpu);
 
-        emulator.push_to_stack(emulator.program_counter);
+        bus.cpu_idle();
+        cpu.push_to_stack(bus, cpu.program_counter);
 
-        emulator.program_counter = (vec as u16).into();
+        cpu.program_counter = (vec as u16).into();
 
-        4
+        self.info().cycles
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        vec![0xC7 | self.0.to_bits() << 3]
+    }
+
+    fn info(&self) -> InstructionInfo {
+        InstructionInfo::new(1, 4, FlagsEffect::NONE)
+    }
+}
+
+impl fmt::Display for InstructionRST {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "rst {}", self.0)
     }
 }
@@ -1,4 +1,5 @@
 use crate::*;
+use std::fmt;
 
 /// Set Carry Flag.
 ///
@@ -14,12 +15,26 @@ use crate::*;
 pub struct InstructionSCF;
 
 impl InstructionTrait for InstructionSCF {
-    fn execute(&self, emulator: &mut Emulator) -> usize {
-        let flags = emulator.accumulator_and_flags.low_mut();
+    fn execute<B: Bus>(&self, cpu: &mut Cpu, _bus: &mut B) -> usize {
+        let flags = cpu.accumulator_and_flags.low_mut();
         set_flag(flags, FLAG_SUBTRACT, false);
         set_flag(flags, FLAG_HALF_CARRY, false);
         set_flag(flags, FLAG_CARRY, true);
 
-        1
+        self.info().cycles
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        vec![0x37]
+    }
+
+    fn info(&self) -> InstructionInfo {
+        InstructionInfo::new(1, 1, FlagsEffect::from_notation(b"-001"))
+    }
+}
+
+impl fmt::Display for InstructionSCF {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("scf")
     }
 }
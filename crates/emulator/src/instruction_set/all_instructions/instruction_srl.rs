@@ -1,4 +1,5 @@
 use crate::*;
+use std::fmt;
 
 /// Shift Right Logically register r8.
 ///
@@ -8,27 +9,41 @@ use crate::*;
 pub struct InstructionSRL(pub ArgumentR8);
 
 impl InstructionTrait for InstructionSRL {
-    fn execute(&self, emulator: &mut Emulator) -> usize {
+    fn execute<B: Bus>(&self, cpu: &mut Cpu, bus: &mut B) -> usize {
         let Self(reg) = *self;
 
-        let value = reg.get(emulator);
+        let value = reg.get(cpu, bus);
 
         let carry = value & 0b1 != 0;
         let result = value >> 1;
 
-        let flags = emulator.accumulator_and_flags.low_mut();
+        let flags = cpu.accumulator_and_flags.low_mut();
 
         set_flag(flags, FLAG_ZERO, result == 0);
         set_flag(flags, FLAG_SUBTRACT, false);
         set_flag(flags, FLAG_HALF_CARRY, false);
         set_flag(flags, FLAG_CARRY, carry);
 
-        reg.set(emulator, result);
+        reg.set(cpu, bus, result);
 
-        if reg == ArgumentR8::AtHL {
-            4
-        } else {
-            2
-        }
+        self.info().cycles
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        vec![0xCB, 0x38 | self.0.to_bits()]
+    }
+
+    fn info(&self) -> InstructionInfo {
+        InstructionInfo::new(
+            2,
+            2 + 2 * self.0.access_cycles(),
+            FlagsEffect::from_notation(b"Z00C"),
+        )
+    }
+}
+
+impl fmt::Display for InstructionSRL {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "srl {}", self.0)
     }
 }
@@ -1,17 +1,45 @@
 use crate::*;
+use std::fmt;
 
 /// Enter CPU very low power mode. Also used to switch between double and normal speed CPU modes in GBC.
 ///
+/// **Speed switch armed (CGB only)**:
+/// - CPU speed is switched, the CPU is paused for `SPEED_SWITCH_DURATION`
+///   M-cycles and then resumes execution.
+///
+/// **Otherwise**:
+/// - Both CPU and PPU are stopped until any of the joypad input lines goes
+///   low.
+///
+/// In both cases the DIV register is reset. The byte after STOP is consumed
+/// during the instruction read.
+///
 /// Flags: None
 #[allow(non_camel_case_types)]
 #[derive(Copy, Debug, Clone, PartialEq, Eq, Ord, PartialOrd, Hash)]
 pub struct InstructionSTOP;
 
 impl InstructionTrait for InstructionSTOP {
-    fn execute(&self, emulator: &mut Emulator) -> usize {
-        emulator.is_in_low_power_mode = true;
+    fn execute<B: Bus>(&self, cpu: &mut Cpu, bus: &mut B) -> usize {
+        if bus.stop() {
+            cpu.state = CpuState::Stopped;
+        }
+
+        self.info().cycles
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        // second byte is ignored by the CPU, but must be present
+        vec![0x10, 0x00]
+    }
+
+    fn info(&self) -> InstructionInfo {
+        InstructionInfo::new(2, 2, FlagsEffect::NONE)
+    }
+}
 
-        // TODO return proper value of cycles
-        1
+impl fmt::Display for InstructionSTOP {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("stop")
     }
 }
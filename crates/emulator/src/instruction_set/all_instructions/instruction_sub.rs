@@ -1,4 +1,5 @@
 use crate::*;
+use std::fmt;
 
 /// Subtract input value from A and store the result in A.
 ///
@@ -18,28 +19,52 @@ pub enum InstructionSUB {
 }
 
 impl InstructionTrait for InstructionSUB {
-    fn execute(&self, emulator: &mut Emulator) -> usize {
-        let (input_value, cycles) = match self {
-            Self::A_R8(arg @ ArgumentR8::AtHL) => (arg.get(emulator), 2),
-            Self::A_R8(arg) => (arg.get(emulator), 1),
-            Self::A_N8(arg) => (arg.get(emulator), 2),
+    fn execute<B: Bus>(&self, cpu: &mut Cpu, bus: &mut B) -> usize {
+        let input_value = match self {
+            Self::A_R8(arg) => arg.get(cpu, bus),
+            Self::A_N8(arg) => arg.get(cpu),
         };
 
-        let register_a = emulator.accumulator_and_flags.high();
+        let register_a = cpu.accumulator_and_flags.high();
 
         let result = register_a.wrapping_sub(input_value);
 
         let half_carry = (register_a & 0xF) < (input_value & 0xF);
         let carry = register_a < input_value;
 
-        let flags = emulator.accumulator_and_flags.low_mut();
+        let flags = cpu.accumulator_and_flags.low_mut();
         set_flag(flags, FLAG_ZERO, result == 0);
         set_flag(flags, FLAG_SUBTRACT, true);
         set_flag(flags, FLAG_HALF_CARRY, half_carry);
         set_flag(flags, FLAG_CARRY, carry);
 
-        emulator.accumulator_and_flags.set_high(result);
+        cpu.accumulator_and_flags.set_high(result);
 
-        cycles
+        self.info().cycles
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        match self {
+            Self::A_R8(r8) => vec![0x90 | r8.to_bits()],
+            Self::A_N8(n8) => vec![0xD6, n8.0],
+        }
+    }
+
+    fn info(&self) -> InstructionInfo {
+        const FLAGS: FlagsEffect = FlagsEffect::from_notation(b"Z1HC");
+
+        match self {
+            Self::A_R8(r8) => InstructionInfo::new(1, 1 + r8.access_cycles(), FLAGS),
+            Self::A_N8(_) => InstructionInfo::new(2, 2, FLAGS),
+        }
+    }
+}
+
+impl fmt::Display for InstructionSUB {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::A_R8(r8) => write!(f, "sub a, {r8}"),
+            Self::A_N8(n8) => write!(f, "sub a, {n8}"),
+        }
     }
 }
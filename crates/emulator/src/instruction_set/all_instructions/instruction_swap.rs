@@ -1,4 +1,5 @@
 use crate::*;
+use std::fmt;
 
 /// Swap the upper 4 bits in r8 and the lower 4 ones.
 ///
@@ -15,27 +16,35 @@ use crate::*;
 pub struct InstructionSWAP(pub ArgumentR8);
 
 impl InstructionTrait for InstructionSWAP {
-    fn execute(&self, emulator: &mut Emulator) -> usize {
+    fn execute<B: Bus>(&self, cpu: &mut Cpu, bus: &mut B) -> usize {
         let Self(reg) = *self;
 
-        let value = reg.get(emulator);
+        let value = reg.get(cpu, bus);
 
         let result = swap_halves(value);
 
-        let flags = emulator.accumulator_and_flags.low_mut();
+        let flags = cpu.accumulator_and_flags.low_mut();
 
         set_flag(flags, FLAG_ZERO, result == 0);
         set_flag(flags, FLAG_SUBTRACT, false);
         set_flag(flags, FLAG_HALF_CARRY, false);
         set_flag(flags, FLAG_CARRY, false);
 
-        reg.set(emulator, result);
+        reg.set(cpu, bus, result);
 
-        if reg == ArgumentR8::AtHL {
-            4
-        } else {
-            2
-        }
+        self.info().cycles
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        vec![0xCB, 0x30 | self.0.to_bits()]
+    }
+
+    fn info(&self) -> InstructionInfo {
+        InstructionInfo::new(
+            2,
+            2 + 2 * self.0.access_cycles(),
+            FlagsEffect::from_notation(b"Z000"),
+        )
     }
 }
 
@@ -54,3 +63,9 @@ fn test_swap_halves() {
     assert_eq!(swap_halves(0x33), 0x33);
     assert_eq!(swap_halves(0x01), 0x10);
 }
+
+impl fmt::Display for InstructionSWAP {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "swap {}", self.0)
+    }
+}
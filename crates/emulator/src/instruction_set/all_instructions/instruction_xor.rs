@@ -1,4 +1,5 @@
 use crate::*;
+use std::fmt;
 
 /// Bitwise XOR between input value and A.
 ///
@@ -18,44 +19,69 @@ pub enum InstructionXOR {
 }
 
 impl InstructionTrait for InstructionXOR {
-    fn execute(&self, emulator: &mut Emulator) -> usize {
-        let (input_value, cycles) = match self {
-            Self::A_R8(arg @ ArgumentR8::AtHL) => (arg.get(emulator), 2),
-            Self::A_R8(arg) => (arg.get(emulator), 1),
-            Self::A_N8(arg) => (arg.get(emulator), 2),
+    fn execute<B: Bus>(&self, cpu: &mut Cpu, bus: &mut B) -> usize {
+        let input_value = match self {
+            Self::A_R8(arg) => arg.get(cpu, bus),
+            Self::A_N8(arg) => arg.get(cpu),
         };
 
-        let register_a = emulator.accumulator_and_flags.high();
+        let register_a = cpu.accumulator_and_flags.high();
 
         let result = register_a ^ input_value;
 
-        let flags = emulator.accumulator_and_flags.low_mut();
+        let flags = cpu.accumulator_and_flags.low_mut();
 
         set_flag(flags, FLAG_ZERO, result == 0);
         set_flag(flags, FLAG_SUBTRACT, false);
         set_flag(flags, FLAG_HALF_CARRY, false);
         set_flag(flags, FLAG_CARRY, false);
 
-        emulator.accumulator_and_flags.set_high(result);
+        cpu.accumulator_and_flags.set_high(result);
 
-        cycles
+        self.info().cycles
+    }
+
+    fn encode(&self) -> Vec<u8> {
+        match self {
+            Self::A_R8(r8) => vec![0xA8 | r8.to_bits()],
+            Self::A_N8(n8) => vec![0xEE, n8.0],
+        }
+    }
+
+    fn info(&self) -> InstructionInfo {
+        const FLAGS: FlagsEffect = FlagsEffect::from_notation(b"Z000");
+
+        match self {
+            Self::A_R8(r8) => InstructionInfo::new(1, 1 + r8.access_cycles(), FLAGS),
+            Self::A_N8(_) => InstructionInfo::new(2, 2, FLAGS),
+        }
     }
 }
 
 #[test]
 fn test_xor_flags() {
-    let mut emulator = Emulator::default();
+    let mut cpu = Cpu::default();
+    let mut bus = FlatBus::default();
 
     // reset flags
-    emulator.accumulator_and_flags.set_low(0x00);
+    cpu.accumulator_and_flags.set_low(0x00);
     // reset A register
-    emulator.accumulator_and_flags.set_high(0x00);
+    cpu.accumulator_and_flags.set_high(0x00);
 
-    InstructionXOR::A_R8(ArgumentR8::A).execute(&mut emulator);
+    InstructionXOR::A_R8(ArgumentR8::A).execute(&mut cpu, &mut bus);
 
     // 0x00 ^ 0x00 = 0x00
-    assert_eq!(emulator.accumulator_and_flags.high(), 0x00);
+    assert_eq!(cpu.accumulator_and_flags.high(), 0x00);
 
     // Z flag should be set, all other flags should be reset
-    assert_eq!(emulator.accumulator_and_flags.low(), 0b1000_0000);
+    assert_eq!(cpu.accumulator_and_flags.low(), 0b1000_0000);
+}
+
+impl fmt::Display for InstructionXOR {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::A_R8(r8) => write!(f, "xor a, {r8}"),
+            Self::A_N8(n8) => write!(f, "xor a, {n8}"),
+        }
+    }
 }
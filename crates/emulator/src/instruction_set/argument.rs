@@ -1,4 +1,5 @@
 use bit_flag::U3;
+use std::fmt;
 
 use crate::*;
 
@@ -29,39 +30,71 @@ impl ArgumentR8 {
             (true, true, true) => Self::A,
         }
     }
+
+    /// Bits of the opcode used to encode the register, inverse of [`Self::from_bits`]
+    pub fn to_bits(self) -> u8 {
+        match self {
+            Self::B => 0,
+            Self::C => 1,
+            Self::D => 2,
+            Self::E => 3,
+            Self::H => 4,
+            Self::L => 5,
+            Self::AtHL => 6,
+            Self::A => 7,
+        }
+    }
+}
+
+impl fmt::Display for ArgumentR8 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Self::B => "b",
+            Self::C => "c",
+            Self::D => "d",
+            Self::E => "e",
+            Self::H => "h",
+            Self::L => "l",
+            Self::AtHL => "[hl]",
+            Self::A => "a",
+        };
+
+        f.write_str(name)
+    }
 }
 
 impl ArgumentWrite for ArgumentR8 {
     type Value = u8;
 
-    fn set(&self, emulator: &mut Emulator, value: Self::Value) {
+    fn set<B: Bus>(&self, cpu: &mut Cpu, bus: &mut B, value: Self::Value) {
         match self {
-            Self::B => emulator.register_bc.set_high(value),
-            Self::C => emulator.register_bc.set_low(value),
-            Self::D => emulator.register_de.set_high(value),
-            Self::E => emulator.register_de.set_low(value),
-            Self::H => emulator.register_hl.set_high(value),
-            Self::L => emulator.register_hl.set_low(value),
-            Self::AtHL => emulator.set(emulator.register_hl.as_u16(), value),
-            Self::A => emulator.accumulator_and_flags.set_high(value),
+            Self::B => cpu.register_bc.set_high(value),
+            Self::C => cpu.register_bc.set_low(value),
+            Self::D => cpu.register_de.set_high(value),
+            Self::E => cpu.register_de.set_low(value),
+            Self::H => cpu.register_hl.set_high(value),
+            Self::L => cpu.register_hl.set_low(value),
+            Self::AtHL => bus.cpu_write(cpu.register_hl.as_u16(), value),
+            Self::A => cpu.accumulator_and_flags.set_high(value),
         }
     }
 }
 
-impl ArgumentRead for ArgumentR8 {
-    type Result = u8;
-
+impl ArgumentR8 {
     /// Returns the value of the 8-bit register or memory at HL.
-    fn get(&self, emulator: &Emulator) -> Self::Result {
+    ///
+    /// Reading memory at HL is done through the CPU bus and takes single
+    /// M-cycle.
+    pub fn get<B: Bus>(&self, cpu: &Cpu, bus: &mut B) -> u8 {
         match self {
-            Self::B => emulator.register_bc.high(),
-            Self::C => emulator.register_bc.low(),
-            Self::D => emulator.register_de.high(),
-            Self::E => emulator.register_de.low(),
-            Self::H => emulator.register_hl.high(),
-            Self::L => emulator.register_hl.low(),
-            Self::AtHL => emulator.get(emulator.register_hl.as_u16()),
-            Self::A => emulator.accumulator_and_flags.high(),
+            Self::B => cpu.register_bc.high(),
+            Self::C => cpu.register_bc.low(),
+            Self::D => cpu.register_de.high(),
+            Self::E => cpu.register_de.low(),
+            Self::H => cpu.register_hl.high(),
+            Self::L => cpu.register_hl.low(),
+            Self::AtHL => bus.cpu_read(cpu.register_hl.as_u16()),
+            Self::A => cpu.accumulator_and_flags.high(),
         }
     }
 }
@@ -85,17 +118,40 @@ impl ArgumentR16 {
             (true, true) => Self::SP,
         }
     }
+
+    /// Bits of the opcode used to encode the register, inverse of [`Self::from_bits`]
+    pub fn to_bits(self) -> u8 {
+        match self {
+            Self::BC => 0,
+            Self::DE => 1,
+            Self::HL => 2,
+            Self::SP => 3,
+        }
+    }
+}
+
+impl fmt::Display for ArgumentR16 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Self::BC => "bc",
+            Self::DE => "de",
+            Self::HL => "hl",
+            Self::SP => "sp",
+        };
+
+        f.write_str(name)
+    }
 }
 
 impl ArgumentWrite for ArgumentR16 {
     type Value = u16;
 
-    fn set(&self, emulator: &mut Emulator, value: Self::Value) {
+    fn set<B: Bus>(&self, cpu: &mut Cpu, _bus: &mut B, value: Self::Value) {
         match self {
-            Self::BC => emulator.register_bc.set(value),
-            Self::DE => emulator.register_de.set(value),
-            Self::HL => emulator.register_hl.set(value),
-            Self::SP => emulator.stack_pointer.0 = value,
+            Self::BC => cpu.register_bc.set(value),
+            Self::DE => cpu.register_de.set(value),
+            Self::HL => cpu.register_hl.set(value),
+            Self::SP => cpu.stack_pointer.0 = value,
         }
     }
 }
@@ -104,12 +160,12 @@ impl ArgumentRead for ArgumentR16 {
     type Result = u16;
 
     /// Returns the value of the 16-bit register.
-    fn get(&self, emulator: &Emulator) -> Self::Result {
+    fn get(&self, cpu: &Cpu) -> Self::Result {
         match self {
-            Self::BC => emulator.register_bc.as_u16(),
-            Self::DE => emulator.register_de.as_u16(),
-            Self::HL => emulator.register_hl.as_u16(),
-            Self::SP => emulator.stack_pointer.into(),
+            Self::BC => cpu.register_bc.as_u16(),
+            Self::DE => cpu.register_de.as_u16(),
+            Self::HL => cpu.register_hl.as_u16(),
+            Self::SP => cpu.stack_pointer.into(),
         }
     }
 }
@@ -133,17 +189,40 @@ impl ArgumentStkR16 {
             (true, true) => Self::AF,
         }
     }
+
+    /// Bits of the opcode used to encode the register, inverse of [`Self::from_bits`]
+    pub fn to_bits(self) -> u8 {
+        match self {
+            Self::BC => 0,
+            Self::DE => 1,
+            Self::HL => 2,
+            Self::AF => 3,
+        }
+    }
+}
+
+impl fmt::Display for ArgumentStkR16 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Self::BC => "bc",
+            Self::DE => "de",
+            Self::HL => "hl",
+            Self::AF => "af",
+        };
+
+        f.write_str(name)
+    }
 }
 
 impl ArgumentWrite for ArgumentStkR16 {
     type Value = u16;
 
-    fn set(&self, emulator: &mut Emulator, value: Self::Value) {
+    fn set<B: Bus>(&self, cpu: &mut Cpu, _bus: &mut B, value: Self::Value) {
         match self {
-            Self::BC => emulator.register_bc.set(value),
-            Self::DE => emulator.register_de.set(value),
-            Self::HL => emulator.register_hl.set(value),
-            Self::AF => emulator.accumulator_and_flags.set(value),
+            Self::BC => cpu.register_bc.set(value),
+            Self::DE => cpu.register_de.set(value),
+            Self::HL => cpu.register_hl.set(value),
+            Self::AF => cpu.accumulator_and_flags.set(value),
         }
     }
 }
@@ -152,12 +231,12 @@ impl ArgumentRead for ArgumentStkR16 {
     type Result = u16;
 
     /// Returns the value of the 16-bit register.
-    fn get(&self, emulator: &Emulator) -> Self::Result {
+    fn get(&self, cpu: &Cpu) -> Self::Result {
         match self {
-            Self::BC => emulator.register_bc.as_u16(),
-            Self::DE => emulator.register_de.as_u16(),
-            Self::HL => emulator.register_hl.as_u16(),
-            Self::AF => emulator.accumulator_and_flags.as_u16(),
+            Self::BC => cpu.register_bc.as_u16(),
+            Self::DE => cpu.register_de.as_u16(),
+            Self::HL => cpu.register_hl.as_u16(),
+            Self::AF => cpu.accumulator_and_flags.as_u16(),
         }
     }
 }
@@ -166,11 +245,17 @@ impl ArgumentRead for ArgumentStkR16 {
 #[derive(Copy, Debug, Clone, PartialEq, Eq, Ord, PartialOrd, Hash)]
 pub struct ArgumentN8(pub u8);
 
+impl fmt::Display for ArgumentN8 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "${:02X}", self.0)
+    }
+}
+
 impl ArgumentRead for ArgumentN8 {
     type Result = u8;
 
     /// Returns the value of the 8-bit integer constant.
-    fn get(&self, _cpu: &Emulator) -> Self::Result {
+    fn get(&self, _cpu: &Cpu) -> Self::Result {
         self.0
     }
 }
@@ -179,11 +264,24 @@ impl ArgumentRead for ArgumentN8 {
 #[derive(Copy, Debug, Clone, PartialEq, Eq, Ord, PartialOrd, Hash)]
 pub struct ArgumentN16(pub u16);
 
+impl ArgumentN16 {
+    /// Little-endian bytes of the constant, as stored after the opcode
+    pub fn to_bytes(self) -> [u8; 2] {
+        self.0.to_le_bytes()
+    }
+}
+
+impl fmt::Display for ArgumentN16 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "${:04X}", self.0)
+    }
+}
+
 impl ArgumentRead for ArgumentN16 {
     type Result = u16;
 
     /// Returns the value of the 16-bit integer constant.
-    fn get(&self, _cpu: &Emulator) -> Self::Result {
+    fn get(&self, _cpu: &Cpu) -> Self::Result {
         self.0
     }
 }
@@ -226,11 +324,17 @@ impl ArgumentE8 {
     }
 }
 
+impl fmt::Display for ArgumentE8 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
 impl ArgumentRead for ArgumentE8 {
     type Result = i8;
 
     /// Returns the value of the 8-bit offset.
-    fn get(&self, _cpu: &Emulator) -> Self::Result {
+    fn get(&self, _cpu: &Cpu) -> Self::Result {
         self.0
     }
 }
@@ -243,7 +347,7 @@ impl ArgumentRead for ArgumentU3 {
     type Result = u8;
 
     /// Returns the value of the 3-bit unsigned integer constant.
-    fn get(&self, _cpu: &Emulator) -> Self::Result {
+    fn get(&self, _cpu: &Cpu) -> Self::Result {
         (*self).into()
     }
 }
@@ -254,6 +358,12 @@ impl ArgumentU3 {
     }
 }
 
+impl fmt::Display for ArgumentU3 {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", u8::from(*self))
+    }
+}
+
 impl From<u8> for ArgumentU3 {
     fn from(value: u8) -> Self {
         ArgumentU3(value.into())
@@ -288,13 +398,36 @@ impl ArgumentCC {
             (true, true) => Self::C,
         }
     }
+
+    /// Bits of the opcode used to encode the condition, inverse of [`Self::from_bits`]
+    pub fn to_bits(self) -> u8 {
+        match self {
+            Self::NZ => 0,
+            Self::Z => 1,
+            Self::NC => 2,
+            Self::C => 3,
+        }
+    }
+}
+
+impl fmt::Display for ArgumentCC {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Self::NZ => "nz",
+            Self::Z => "z",
+            Self::NC => "nc",
+            Self::C => "c",
+        };
+
+        f.write_str(name)
+    }
 }
 
 impl ArgumentRead for ArgumentCC {
     type Result = bool;
 
-    fn get(&self, emulator: &Emulator) -> Self::Result {
-        let flags = emulator.accumulator_and_flags.low();
+    fn get(&self, cpu: &Cpu) -> Self::Result {
+        let flags = cpu.accumulator_and_flags.low();
 
         match self {
             Self::NZ => !get_flag(flags, FLAG_ZERO),
@@ -308,35 +441,23 @@ impl ArgumentRead for ArgumentCC {
 pub trait ArgumentRead {
     type Result;
 
-    fn get(&self, emulator: &Emulator) -> Self::Result;
+    fn get(&self, cpu: &Cpu) -> Self::Result;
 }
 
 impl<T: ArgumentRead<Result = u16>> MemoryAddress for T {
-    fn get_at(&self, emulator: &Emulator) -> u8 {
-        let address = self.get(emulator);
-        emulator.get(address)
-    }
-
-    fn get_at_force(&self, emulator: &Emulator) -> u8 {
-        let address = self.get(emulator);
-        *emulator.get_force(address)
+    fn get_at<B: Bus>(&self, cpu: &Cpu, bus: &mut B) -> u8 {
+        bus.cpu_read(self.get(cpu))
     }
 
-    fn set_at(&self, emulator: &mut Emulator, value: u8) {
-        let address = self.get(emulator);
-        emulator.set(address, value);
-    }
-
-    fn set_at_force(&self, emulator: &mut Emulator, value: u8) {
-        let address = self.get(emulator);
-        emulator.set_force(address, value);
+    fn set_at<B: Bus>(&self, cpu: &Cpu, bus: &mut B, value: u8) {
+        bus.cpu_write(self.get(cpu), value);
     }
 }
 
 pub trait ArgumentWrite {
     type Value;
 
-    fn set(&self, emulator: &mut Emulator, value: Self::Value);
+    fn set<B: Bus>(&self, cpu: &mut Cpu, bus: &mut B, value: Self::Value);
 }
 
 #[repr(u8)]
@@ -365,26 +486,37 @@ impl ArgumentVec {
             (true, true, true) => Self::Value0x38,
         }
     }
+
+    /// Bits of the opcode used to encode the vector, inverse of [`Self::from_bits`]
+    pub fn to_bits(self) -> u8 {
+        (self as u8) >> 3
+    }
+}
+
+impl fmt::Display for ArgumentVec {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "${:02X}", *self as u8)
+    }
 }
 
 impl ArgumentRead for ArgumentVec {
     type Result = u8;
 
-    fn get(&self, _cpu: &Emulator) -> Self::Result {
+    fn get(&self, _cpu: &Cpu) -> Self::Result {
         *self as u8
     }
 }
 
 #[test]
 fn test_vec() {
-    let emulator = Emulator::default();
-
-    assert_eq!(ArgumentVec::Value0x00.get(&emulator), 0x00);
-    assert_eq!(ArgumentVec::Value0x08.get(&emulator), 0x08);
-    assert_eq!(ArgumentVec::Value0x10.get(&emulator), 0x10);
-    assert_eq!(ArgumentVec::Value0x18.get(&emulator), 0x18);
-    assert_eq!(ArgumentVec::Value0x20.get(&emulator), 0x20);
-    assert_eq!(ArgumentVec::Value0x28.get(&emulator), 0x28);
-    assert_eq!(ArgumentVec::Value0x30.get(&emulator), 0x30);
-    assert_eq!(ArgumentVec::Value0x38.get(&emulator), 0x38);
+    let cpu = Cpu::default();
+
+    assert_eq!(ArgumentVec::Value0x00.get(&cpu), 0x00);
+    assert_eq!(ArgumentVec::Value0x08.get(&cpu), 0x08);
+    assert_eq!(ArgumentVec::Value0x10.get(&cpu), 0x10);
+    assert_eq!(ArgumentVec::Value0x18.get(&cpu), 0x18);
+    assert_eq!(ArgumentVec::Value0x20.get(&cpu), 0x20);
+    assert_eq!(ArgumentVec::Value0x28.get(&cpu), 0x28);
+    assert_eq!(ArgumentVec::Value0x30.get(&cpu), 0x30);
+    assert_eq!(ArgumentVec::Value0x38.get(&cpu), 0x38);
 }
@@ -1,5 +1,6 @@
 use crate::*;
 use enum_dispatch::enum_dispatch;
+use std::fmt;
 
 #[enum_dispatch]
 #[derive(Copy, Debug, Clone, PartialEq, Eq, Ord, PartialOrd, Hash)]
@@ -50,12 +51,71 @@ pub enum Instruction {
     XOR(InstructionXOR),
 }
 
+/// Instruction in RGBDS assembly syntax, e.g. `ld hl, $C000`
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Self::CCF(instruction) => instruction.fmt(f),
+            Self::DI(instruction) => instruction.fmt(f),
+            Self::EI(instruction) => instruction.fmt(f),
+            Self::NOP(instruction) => instruction.fmt(f),
+            Self::RLA(instruction) => instruction.fmt(f),
+            Self::RRA(instruction) => instruction.fmt(f),
+            Self::RRCA(instruction) => instruction.fmt(f),
+            Self::RST(instruction) => instruction.fmt(f),
+            Self::SCF(instruction) => instruction.fmt(f),
+            Self::STOP(instruction) => instruction.fmt(f),
+            Self::CPL(instruction) => instruction.fmt(f),
+            Self::DAA(instruction) => instruction.fmt(f),
+            Self::RETI(instruction) => instruction.fmt(f),
+            Self::RLCA(instruction) => instruction.fmt(f),
+            Self::HALT(instruction) => instruction.fmt(f),
+            Self::ADC(instruction) => instruction.fmt(f),
+            Self::ADD(instruction) => instruction.fmt(f),
+            Self::AND(instruction) => instruction.fmt(f),
+            Self::BIT(instruction) => instruction.fmt(f),
+            Self::CALL(instruction) => instruction.fmt(f),
+            Self::CP(instruction) => instruction.fmt(f),
+            Self::DEC(instruction) => instruction.fmt(f),
+            Self::INC(instruction) => instruction.fmt(f),
+            Self::JP(instruction) => instruction.fmt(f),
+            Self::JR(instruction) => instruction.fmt(f),
+            Self::LD(instruction) => instruction.fmt(f),
+            Self::LDH(instruction) => instruction.fmt(f),
+            Self::OR(instruction) => instruction.fmt(f),
+            Self::POP(instruction) => instruction.fmt(f),
+            Self::PUSH(instruction) => instruction.fmt(f),
+            Self::RES(instruction) => instruction.fmt(f),
+            Self::RET(instruction) => instruction.fmt(f),
+            Self::RL(instruction) => instruction.fmt(f),
+            Self::RLC(instruction) => instruction.fmt(f),
+            Self::RR(instruction) => instruction.fmt(f),
+            Self::RRC(instruction) => instruction.fmt(f),
+            Self::SBC(instruction) => instruction.fmt(f),
+            Self::SET(instruction) => instruction.fmt(f),
+            Self::SLA(instruction) => instruction.fmt(f),
+            Self::SRA(instruction) => instruction.fmt(f),
+            Self::SRL(instruction) => instruction.fmt(f),
+            Self::SUB(instruction) => instruction.fmt(f),
+            Self::SWAP(instruction) => instruction.fmt(f),
+            Self::XOR(instruction) => instruction.fmt(f),
+        }
+    }
+}
+
 #[enum_dispatch(Instruction)]
 pub trait InstructionTrait {
     /// Execute the instruction and return the number of M-cycles it took.
     ///
     /// Returns 0 if the instruction is impossible.
-    fn execute(&self, emulator: &mut Emulator) -> usize;
+    fn execute<B: Bus>(&self, cpu: &mut Cpu, bus: &mut B) -> usize;
+
+    /// Encode the instruction to its bytes, inverse of [`Instruction::read`].
+    fn encode(&self) -> Vec<u8>;
+
+    /// Static information about the instruction: length, duration and
+    /// affected flags.
+    fn info(&self) -> InstructionInfo;
 }
 
 macro_rules! bit {
@@ -77,12 +137,35 @@ macro_rules! bits {
 }
 
 impl Instruction {
-    /// Read instruction from instruction register and emulator.
+    /// Read instruction from instruction register and the bus.
     ///
-    /// Returns the instruction and the number of bytes read.
-    pub fn read(emulator: &mut Emulator) -> Option<Self> {
-        let opcode = emulator.instruction_register;
+    /// Operands are read at PC, which is advanced past the instruction.
+    pub fn read<B: Bus>(cpu: &mut Cpu, bus: &mut B) -> Option<Self> {
+        let opcode = cpu.instruction_register;
 
+        Self::decode(opcode, || cpu.read_u8_at_pc(bus))
+    }
+
+    /// Decode instruction at given address without any side effects, memory
+    /// is read with [`Bus::peek`].
+    ///
+    /// Returns the instruction and its length in bytes.
+    pub fn decode_at<B: Bus>(bus: &B, address: u16) -> Option<(Self, u16)> {
+        let opcode = bus.peek(address);
+
+        let mut length = 1;
+        let instruction = Self::decode(opcode, || {
+            let value = bus.peek(address.wrapping_add(length));
+            length += 1;
+            value
+        })?;
+
+        Some((instruction, length))
+    }
+
+    /// Decode instruction with given opcode, operands and the 0xCB prefixed
+    /// opcode are taken from `read_u8` one byte at a time.
+    fn decode(opcode: u8, mut read_u8: impl FnMut() -> u8) -> Option<Self> {
         let bits = to_bits(opcode);
 
         match bits {
@@ -93,7 +176,7 @@ impl Instruction {
             // ld r16, n16	0	0	Dest (r16)	0	0	0	1
             bits![0, 0, b1, b0, 0, 0, 0, 1] => {
                 let r16 = ArgumentR16::from_bits(b0, b1);
-                let n16 = emulator.read_u16_at_pc();
+                let n16 = u16::from_le_bytes([read_u8(), read_u8()]);
 
                 Some(Self::LD(InstructionLD::R16_N16(r16, ArgumentN16(n16))))
             }
@@ -123,7 +206,7 @@ impl Instruction {
             }
             // ld [n16], sp	0	0	0	0	1	0	0	0
             bits![0, 0, 0, 0, 1, 0, 0, 0] => {
-                let n16 = emulator.read_u16_at_pc();
+                let n16 = u16::from_le_bytes([read_u8(), read_u8()]);
 
                 Some(Self::LD(InstructionLD::AtN16_SP(ArgumentN16(n16))))
             }
@@ -164,7 +247,7 @@ impl Instruction {
             bits![0, 0, b2, b1, b0, 1, 1, 0] => {
                 let r8 = ArgumentR8::from_bits(b0, b1, b2);
 
-                let n8 = emulator.read_u8_at_pc();
+                let n8 = read_u8();
 
                 Some(Self::LD(InstructionLD::R8_N8(r8, ArgumentN8(n8))))
             }
@@ -188,20 +271,25 @@ impl Instruction {
 
             // jr e8	0	0	0	1	1	0	0	0
             bits![0, 0, 0, 1, 1, 0, 0, 0] => {
-                let e8 = emulator.read_i8_at_pc();
+                let e8 = read_u8() as i8;
 
                 Some(Self::JR(InstructionJR::E8(ArgumentE8(e8))))
             }
             // jr cond, e8	0	0	1	Condition (cond)	0	0	0
             bits![0, 0, 1, b1, b0, 0, 0, 0] => {
                 let cond = ArgumentCC::from_bits(b0, b1);
-                let e8 = emulator.read_i8_at_pc();
+                let e8 = read_u8() as i8;
 
                 Some(Self::JR(InstructionJR::CC_E8(cond, ArgumentE8(e8))))
             }
 
             // stop	0	0	0	1	0	0	0	0
-            bits![0, 0, 0, 1, 0, 0, 0, 0] => Some(Self::STOP(InstructionSTOP)),
+            bits![0, 0, 0, 1, 0, 0, 0, 0] => {
+                // STOP is 2 bytes long, the second byte is ignored
+                read_u8();
+
+                Some(Self::STOP(InstructionSTOP))
+            }
 
             // ======= Block 1: 8-bit register-to-register loads =======
 
@@ -271,49 +359,49 @@ impl Instruction {
 
             // add a, n8	1	1	0	0	0	1	1	0
             bits![1, 1, 0, 0, 0, 1, 1, 0] => {
-                let n8 = emulator.read_u8_at_pc();
+                let n8 = read_u8();
 
                 Some(Self::ADD(InstructionADD::A_N8(ArgumentN8(n8))))
             }
             // adc a, n8	1	1	0	0	1	1	1	0
             bits![1, 1, 0, 0, 1, 1, 1, 0] => {
-                let n8 = emulator.read_u8_at_pc();
+                let n8 = read_u8();
 
                 Some(Self::ADC(InstructionADC::A_N8(ArgumentN8(n8))))
             }
             // sub a, n8	1	1	0	1	0	1	1	0
             bits![1, 1, 0, 1, 0, 1, 1, 0] => {
-                let n8 = emulator.read_u8_at_pc();
+                let n8 = read_u8();
 
                 Some(Self::SUB(InstructionSUB::A_N8(ArgumentN8(n8))))
             }
             // sbc a, n8	1	1	0	1	1	1	1	0
             bits![1, 1, 0, 1, 1, 1, 1, 0] => {
-                let n8 = emulator.read_u8_at_pc();
+                let n8 = read_u8();
 
                 Some(Self::SBC(InstructionSBC::A_N8(ArgumentN8(n8))))
             }
             // and a, n8	1	1	1	0	0	1	1	0
             bits![1, 1, 1, 0, 0, 1, 1, 0] => {
-                let n8 = emulator.read_u8_at_pc();
+                let n8 = read_u8();
 
                 Some(Self::AND(InstructionAND::A_N8(ArgumentN8(n8))))
             }
             // xor a, n8	1	1	1	0	1	1	1	0
             bits![1, 1, 1, 0, 1, 1, 1, 0] => {
-                let n8 = emulator.read_u8_at_pc();
+                let n8 = read_u8();
 
                 Some(Self::XOR(InstructionXOR::A_N8(ArgumentN8(n8))))
             }
             // or a, n8	1	1	1	1	0	1	1	0
             bits![1, 1, 1, 1, 0, 1, 1, 0] => {
-                let n8 = emulator.read_u8_at_pc();
+                let n8 = read_u8();
 
                 Some(Self::OR(InstructionOR::A_N8(ArgumentN8(n8))))
             }
             // cp a, n8	1	1	1	1	1	1	1	0
             bits![1, 1, 1, 1, 1, 1, 1, 0] => {
-                let n8 = emulator.read_u8_at_pc();
+                let n8 = read_u8();
 
                 Some(Self::CP(InstructionCP::A_N8(ArgumentN8(n8))))
             }
@@ -331,13 +419,13 @@ impl Instruction {
             // jp cond, n16	1	1	0	Condition (cond)	0	1	0
             bits![1, 1, 0, b1, b0, 0, 1, 0] => {
                 let cond = ArgumentCC::from_bits(b0, b1);
-                let n16 = emulator.read_u16_at_pc();
+                let n16 = u16::from_le_bytes([read_u8(), read_u8()]);
 
                 Some(Self::JP(InstructionJP::CC_N16(cond, ArgumentN16(n16))))
             }
             // jp n16	1	1	0	0	0	0	1	1
             bits![1, 1, 0, 0, 0, 0, 1, 1] => {
-                let n16 = emulator.read_u16_at_pc();
+                let n16 = u16::from_le_bytes([read_u8(), read_u8()]);
 
                 Some(Self::JP(InstructionJP::N16(ArgumentN16(n16))))
             }
@@ -346,13 +434,13 @@ impl Instruction {
             // call cond, n16	1	1	0	Condition (cond)	1	0	0
             bits![1, 1, 0, b1, b0, 1, 0, 0] => {
                 let cond = ArgumentCC::from_bits(b0, b1);
-                let n16 = emulator.read_u16_at_pc();
+                let n16 = u16::from_le_bytes([read_u8(), read_u8()]);
 
                 Some(Self::CALL(InstructionCALL::CC_N16(cond, ArgumentN16(n16))))
             }
             // call n16	1	1	0	0	1	1	0	1
             bits![1, 1, 0, 0, 1, 1, 0, 1] => {
-                let n16 = emulator.read_u16_at_pc();
+                let n16 = u16::from_le_bytes([read_u8(), read_u8()]);
 
                 Some(Self::CALL(InstructionCALL::N16(ArgumentN16(n16))))
             }
@@ -380,13 +468,13 @@ impl Instruction {
             bits![1, 1, 1, 0, 0, 0, 1, 0] => Some(Self::LDH(InstructionLDH::AtC_A)),
             // ldh [n8], a	1	1	1	0	0	0	0	0
             bits![1, 1, 1, 0, 0, 0, 0, 0] => {
-                let n8 = emulator.read_u8_at_pc();
+                let n8 = read_u8();
 
                 Some(Self::LDH(InstructionLDH::AtN8_A(ArgumentN8(n8))))
             }
             // ld [n16], a	1	1	1	0	1	0	1	0
             bits![1, 1, 1, 0, 1, 0, 1, 0] => {
-                let n16 = emulator.read_u16_at_pc();
+                let n16 = u16::from_le_bytes([read_u8(), read_u8()]);
 
                 Some(Self::LD(InstructionLD::AtN16_A(ArgumentN16(n16))))
             }
@@ -394,26 +482,26 @@ impl Instruction {
             bits![1, 1, 1, 1, 0, 0, 1, 0] => Some(Self::LDH(InstructionLDH::A_AtC)),
             // ldh a, [n8]	1	1	1	1	0	0	0	0
             bits![1, 1, 1, 1, 0, 0, 0, 0] => {
-                let n8 = emulator.read_u8_at_pc();
+                let n8 = read_u8();
 
                 Some(Self::LDH(InstructionLDH::A_AtN8(ArgumentN8(n8))))
             }
             // ld a, [n16]	1	1	1	1	1	0	1	0
             bits![1, 1, 1, 1, 1, 0, 1, 0] => {
-                let n16 = emulator.read_u16_at_pc();
+                let n16 = u16::from_le_bytes([read_u8(), read_u8()]);
 
                 Some(Self::LD(InstructionLD::A_AtN16(ArgumentN16(n16))))
             }
 
             // add sp, e8	1	1	1	0	1	0	0	0
             bits![1, 1, 1, 0, 1, 0, 0, 0] => {
-                let e8 = emulator.read_i8_at_pc();
+                let e8 = read_u8() as i8;
 
                 Some(Self::ADD(InstructionADD::SP_E8(ArgumentE8(e8))))
             }
             // ld hl, sp + e8	1	1	1	1	1	0	0	0
             bits![1, 1, 1, 1, 1, 0, 0, 0] => {
-                let e8 = emulator.read_i8_at_pc();
+                let e8 = read_u8() as i8;
 
                 Some(Self::LD(InstructionLD::HL_SP_E8(ArgumentE8(e8))))
             }
@@ -427,7 +515,7 @@ impl Instruction {
 
             // Prefix (see block below)	1	1	0	0	1	0	1	1
             bits![1, 1, 0, 0, 1, 0, 1, 1] => {
-                let opcode = emulator.read_u8_at_pc();
+                let opcode = read_u8();
                 let bits = to_bits(opcode);
 
                 match bits {
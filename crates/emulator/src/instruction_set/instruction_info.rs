@@ -0,0 +1,113 @@
+use crate::*;
+
+/// Effect of an instruction on a single flag.
+#[derive(Copy, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum FlagEffect {
+    /// Flag is not changed
+    Unaffected,
+    /// Flag is always set to 0
+    Reset,
+    /// Flag is always set to 1
+    Set,
+    /// Flag is set depending on the result
+    Affected,
+}
+
+impl FlagEffect {
+    /// Parse single flag of the notation used by opcode tables: `-` for
+    /// unaffected, `0`, `1` or the flag letter if affected.
+    const fn from_notation(notation: u8) -> Self {
+        match notation {
+            b'-' => Self::Unaffected,
+            b'0' => Self::Reset,
+            b'1' => Self::Set,
+            _ => Self::Affected,
+        }
+    }
+}
+
+/// Effect of an instruction on Z, N, H and C flags.
+#[derive(Copy, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct FlagsEffect {
+    pub zero: FlagEffect,
+    pub subtract: FlagEffect,
+    pub half_carry: FlagEffect,
+    pub carry: FlagEffect,
+}
+
+impl FlagsEffect {
+    /// No flags are affected
+    pub const NONE: Self = Self::from_notation(b"----");
+
+    /// Create from the notation used by opcode tables, e.g. `b"Z0HC"` for
+    /// `ADD A, r8`.
+    pub const fn from_notation(notation: &[u8; 4]) -> Self {
+        Self {
+            zero: FlagEffect::from_notation(notation[0]),
+            subtract: FlagEffect::from_notation(notation[1]),
+            half_carry: FlagEffect::from_notation(notation[2]),
+            carry: FlagEffect::from_notation(notation[3]),
+        }
+    }
+}
+
+/// Static information about an instruction.
+#[derive(Copy, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct InstructionInfo {
+    /// Length of the encoded instruction in bytes, including operands and
+    /// the 0xCB prefix
+    pub length: u8,
+    /// Number of M-cycles the instruction takes, for conditional instructions
+    /// when the condition is not met
+    pub cycles: usize,
+    /// Number of M-cycles conditional instruction takes when the condition
+    /// is met, `None` for unconditional instructions
+    pub cycles_branch_taken: Option<usize>,
+    pub flags: FlagsEffect,
+}
+
+impl InstructionInfo {
+    pub const fn new(length: u8, cycles: usize, flags: FlagsEffect) -> Self {
+        Self {
+            length,
+            cycles,
+            cycles_branch_taken: None,
+            flags,
+        }
+    }
+
+    /// Info of conditional jump, call or return, none of them affect flags
+    pub const fn conditional(length: u8, cycles: usize, cycles_branch_taken: usize) -> Self {
+        Self {
+            length,
+            cycles,
+            cycles_branch_taken: Some(cycles_branch_taken),
+            flags: FlagsEffect::NONE,
+        }
+    }
+
+    /// Number of M-cycles the instruction takes, `branch_taken` is ignored by
+    /// unconditional instructions
+    pub fn duration(&self, branch_taken: bool) -> usize {
+        match self.cycles_branch_taken {
+            Some(cycles) if branch_taken => cycles,
+            _ => self.cycles,
+        }
+    }
+
+    /// Check if the instruction could have taken given number of M-cycles
+    pub fn is_valid_duration(&self, cycles: usize) -> bool {
+        self.cycles == cycles || self.cycles_branch_taken == Some(cycles)
+    }
+}
+
+impl ArgumentR8 {
+    /// Extra M-cycles needed to access the operand, 1 for memory at HL
+    pub fn access_cycles(self) -> usize {
+        if self == Self::AtHL {
+            1
+        } else {
+            0
+        }
+    }
+}
@@ -1,7 +1,9 @@
 mod all_instructions;
 mod argument;
 mod instruction;
+mod instruction_info;
 
 pub use all_instructions::*;
 pub use argument::*;
 pub use instruction::*;
+pub use instruction_info::*;
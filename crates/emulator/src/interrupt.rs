@@ -6,36 +6,105 @@ pub const TIMER_INTERRUPT: u16 = 0x0050;
 pub const SERIAL_INTERRUPT: u16 = 0x0058;
 pub const JOYPAD_INTERRUPT: u16 = 0x0060;
 
-impl Emulator {
-    pub fn process_interrupt(&mut self) {
-        if !self.ime_flag {
-            return;
+/// Mask of the bits used by IE and IF registers
+pub const INTERRUPTS_MASK: u8 = 0b0001_1111;
+
+/// Amount of M-cycles it takes to dispatch an interrupt, including the fetch
+/// of the first opcode of the handler.
+pub const INTERRUPT_DISPATCH_DURATION: usize = 5;
+
+/// Interrupt sources, ordered by priority (highest first).
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, Ord, PartialOrd)]
+pub enum Interrupt {
+    VBlank,
+    Lcd,
+    Timer,
+    Serial,
+    Joypad,
+}
+
+impl Interrupt {
+    pub const ALL: [Interrupt; 5] = [
+        Interrupt::VBlank,
+        Interrupt::Lcd,
+        Interrupt::Timer,
+        Interrupt::Serial,
+        Interrupt::Joypad,
+    ];
+
+    /// Bit of the interrupt in IE and IF registers
+    pub fn mask(self) -> u8 {
+        match self {
+            Interrupt::VBlank => RegisterIF::V_BLANK,
+            Interrupt::Lcd => RegisterIF::LCD,
+            Interrupt::Timer => RegisterIF::TIMER,
+            Interrupt::Serial => RegisterIF::SERIAL,
+            Interrupt::Joypad => RegisterIF::JOYPAD,
         }
+    }
 
-        let reg_if = self.reg::<RegisterIF>().clone();
-        let reg_ie = self.reg_mut::<RegisterIE>();
-
-        let interrupt_handler = if reg_ie.get_v_blank() && reg_if.get_v_blank() {
-            reg_ie.set_v_blank(false);
-            V_BLANK_INTERRUPT
-        } else if reg_ie.get_lcd() && reg_if.get_lcd() {
-            reg_ie.set_lcd(false);
-            LCD_STAT_INTERRUPT
-        } else if reg_ie.get_timer() && reg_if.get_timer() {
-            reg_ie.set_timer(false);
-            TIMER_INTERRUPT
-        } else if reg_ie.get_serial() && reg_if.get_serial() {
-            reg_ie.set_serial(false);
-            SERIAL_INTERRUPT
-        } else if reg_ie.get_joypad() && reg_if.get_joypad() {
-            reg_ie.set_joypad(false);
-            JOYPAD_INTERRUPT
-        } else {
-            return;
-        };
+    /// Address of the interrupt handler
+    pub fn handler_address(self) -> u16 {
+        match self {
+            Interrupt::VBlank => V_BLANK_INTERRUPT,
+            Interrupt::Lcd => LCD_STAT_INTERRUPT,
+            Interrupt::Timer => TIMER_INTERRUPT,
+            Interrupt::Serial => SERIAL_INTERRUPT,
+            Interrupt::Joypad => JOYPAD_INTERRUPT,
+        }
+    }
+
+    /// Get interrupt with the highest priority from the given interrupt bits.
+    pub fn highest_priority(interrupts: u8) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|interrupt| interrupts & interrupt.mask() != 0)
+    }
+}
+
+impl Cpu {
+    /// Dispatch pending interrupt with the highest priority if IME is set.
+    ///
+    /// Dispatch takes 5 M-cycles: the opcode at PC is read and discarded, 1
+    /// internal cycle, 2 cycles to push PC to the stack and the last one is
+    /// the fetch of the handler opcode (done by the regular opcode fetch).
+    ///
+    /// The interrupt is selected after the high byte of PC is pushed, so if
+    /// that push overwrites IE (SP was $0000), the dispatch may be redirected
+    /// to another interrupt or canceled, in which case PC is set to $0000.
+    ///
+    /// Returns the dispatched interrupt.
+    pub fn process_interrupt<B: Bus>(&mut self, bus: &mut B) -> Option<Interrupt> {
+        if !self.ime_flag || bus.pending_interrupts() == 0 {
+            return None;
+        }
 
         self.ime_flag = false;
-        self.push_to_stack(self.program_counter.0);
-        self.program_counter.0 = interrupt_handler;
+
+        let [pc_low, pc_high] = self.program_counter.0.to_le_bytes();
+
+        bus.cpu_read(self.program_counter.0);
+        bus.cpu_idle();
+
+        self.stack_pointer.decrement();
+        bus.cpu_write(self.stack_pointer.into(), pc_high);
+
+        let interrupt = Interrupt::highest_priority(bus.pending_interrupts());
+
+        self.stack_pointer.decrement();
+        bus.cpu_write(self.stack_pointer.into(), pc_low);
+
+        self.dispatched_interrupt = interrupt;
+        self.program_counter.0 = match interrupt {
+            Some(interrupt) => {
+                // acknowledge interrupt
+                let reg_if = bus.peek(RegisterIF::ADDRESS);
+                bus.write(RegisterIF::ADDRESS, reg_if & !interrupt.mask());
+                interrupt.handler_address()
+            }
+            None => 0x0000,
+        };
+
+        interrupt
     }
 }
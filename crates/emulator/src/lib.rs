@@ -4,30 +4,50 @@ compile_error!("This crate does not support big-endian targets");
 
 pub use emulator_derive::*;
 
+mod boot_rom;
+mod bus;
+mod cartridge;
 mod control_registers;
+mod cpu;
 mod cpu_register;
+mod cpu_state;
 mod debugger;
 mod emulator;
 mod flags;
 mod instruction_set;
 mod interrupt;
 mod memory;
+mod model;
+mod oam_dma;
 mod program_counter;
 mod rendering;
 mod rom;
+mod save_ram;
 mod stack_handlers;
 mod stack_pointer;
+mod step;
+mod vram_dma;
 
+pub use boot_rom::*;
+pub use bus::*;
+pub use cartridge::*;
 pub use control_registers::*;
+pub use cpu::*;
 pub use cpu_register::*;
+pub use cpu_state::*;
 pub use debugger::*;
 pub use emulator::*;
 pub use flags::*;
 pub use instruction_set::*;
 pub use interrupt::*;
 pub use memory::*;
+pub use model::*;
+pub use oam_dma::*;
 pub use program_counter::*;
 pub use rendering::*;
 pub use rom::*;
+pub use save_ram::*;
 pub use stack_handlers::*;
 pub use stack_pointer::*;
+pub use step::*;
+pub use vram_dma::*;
@@ -39,6 +39,11 @@ pub const MEMORY_RANGE_WORK_RAM_1: std::ops::Range<usize> = 0xD000..0xE000;
 pub const MEMORY_SIZE_WORK_RAM_1: usize =
     MEMORY_RANGE_WORK_RAM_1.end - MEMORY_RANGE_WORK_RAM_1.start;
 
+/// Amount of VRAM banks in CGB mode
+pub const VRAM_BANKS: usize = 2;
+/// Amount of WRAM banks in CGB mode, including the fixed bank 0
+pub const WORK_RAM_BANKS: usize = 8;
+
 /// Echo RAM (mirror of C000–DDFF)
 ///
 /// Nintendo says use of this area is prohibited.
@@ -83,6 +88,28 @@ pub const MEMORY_RANGE_OBJECT_TILES: std::ops::Range<usize> = 0x8000..0x8FFF;
 
 // TODO add I/O range
 
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum BankedMemory {
+    Vram,
+    WorkRam,
+}
+
+/// Banked memory and offset in the bank of `address`
+fn banked_memory_offset(address: u16) -> Option<(BankedMemory, usize)> {
+    let mut index = address as usize;
+    if MEMORY_RANGE_ECHO_RAM.contains(&index) {
+        index = echo_ram_source(address) as usize;
+    }
+
+    if MEMORY_RANGE_VRAM.contains(&index) {
+        Some((BankedMemory::Vram, index - MEMORY_RANGE_VRAM.start))
+    } else if MEMORY_RANGE_WORK_RAM_1.contains(&index) {
+        Some((BankedMemory::WorkRam, index - MEMORY_RANGE_WORK_RAM_1.start))
+    } else {
+        None
+    }
+}
+
 /// Check if the `address` is accessible by the CPU based on the current PPU mode.
 fn is_address_accessible_ppu(address: u16, ppu_mode: PpuMode) -> bool {
     let address = address as usize;
@@ -100,7 +127,57 @@ fn is_address_accessible_ppu(address: u16, ppu_mode: PpuMode) -> bool {
     true
 }
 
+/// Address in $C000-$DDFF mirrored by the echo RAM `address`
+fn echo_ram_source(address: u16) -> u16 {
+    address - (MEMORY_RANGE_ECHO_RAM.start - MEMORY_RANGE_WORK_RAM_0.start) as u16
+}
+
 impl Emulator {
+    /// VRAM bank mapped to $8000-$9FFF, always 0 outside of CGB mode
+    pub fn vram_bank(&self) -> usize {
+        if self.cgb_mode {
+            self.reg::<RegisterVBK>().bank()
+        } else {
+            0
+        }
+    }
+
+    /// WRAM bank mapped to $D000-$DFFF, always 1 outside of CGB mode
+    pub fn work_ram_bank(&self) -> usize {
+        if self.cgb_mode {
+            self.reg::<RegisterSVBK>().bank()
+        } else {
+            1
+        }
+    }
+
+    /// Get value from the given bank of VRAM ($8000-$9FFF) or WRAM
+    /// ($D000-$DFFF and its echo), regardless of the selected bank. Useful for
+    /// debuggers.
+    ///
+    /// Returns `None` if the address is not banked or the bank doesn't exist.
+    pub fn get_banked(&self, address: u16, bank: usize) -> Option<&u8> {
+        let (memory, offset) = banked_memory_offset(address)?;
+
+        match memory {
+            BankedMemory::Vram => self.vram.get(bank).map(|vram| &vram[offset]),
+            BankedMemory::WorkRam => self.work_ram.get(bank).map(|work_ram| &work_ram[offset]),
+        }
+    }
+
+    /// Mutable version of [`Emulator::get_banked`]
+    pub fn get_banked_mut(&mut self, address: u16, bank: usize) -> Option<&mut u8> {
+        let (memory, offset) = banked_memory_offset(address)?;
+
+        match memory {
+            BankedMemory::Vram => self.vram.get_mut(bank).map(|vram| &mut vram[offset]),
+            BankedMemory::WorkRam => self
+                .work_ram
+                .get_mut(bank)
+                .map(|work_ram| &mut work_ram[offset]),
+        }
+    }
+
     pub fn is_address_accessible(&self, address: u16) -> bool {
         if self.reg::<RegisterLCDC>().get_lcd_and_ppu_enable() {
             let ppu_mode = self.reg::<RegisterSTAT>().get_ppu_mode();
@@ -119,42 +196,45 @@ impl Emulator {
             return 0xFF;
         }
 
+        if let Some(value) = self.oam_dma_conflict(address) {
+            return value;
+        }
+
+        if MEMORY_RANGE_IO_REGISTERS.contains(&(address as usize)) {
+            return self.read_io_register(address);
+        }
+
         *self.get_force(address)
     }
 
     /// Get current value in memory even if it's not accessible by the CPU.
     pub fn get_force(&self, address: u16) -> &u8 {
         let index = address as usize;
-        if MEMORY_RANGE_ROM_BANK_00.contains(&index) {
-            return &self.rom_bank_00[index];
-        }
-
-        if MEMORY_RANGE_ROM_BANK_01.contains(&index) {
-            return &self.rom_bank_01[index - MEMORY_RANGE_ROM_BANK_01.start];
+        if MEMORY_RANGE_ROM_BANK_00.contains(&index) || MEMORY_RANGE_ROM_BANK_01.contains(&index) {
+            if let Some(byte) = self.boot_rom.as_ref().and_then(|rom| rom.byte(address)) {
+                return byte;
+            }
+            return self.rom.rom_byte(address);
         }
 
         if MEMORY_RANGE_VRAM.contains(&index) {
-            return &self.vram[index - MEMORY_RANGE_VRAM.start];
+            return &self.vram[self.vram_bank()][index - MEMORY_RANGE_VRAM.start];
         }
 
         if MEMORY_RANGE_EXTERNAL_RAM.contains(&index) {
-            return &self.external_ram[index - MEMORY_RANGE_EXTERNAL_RAM.start];
+            return self.rom.ram_byte(address);
         }
 
         if MEMORY_RANGE_WORK_RAM_0.contains(&index) {
-            return &self.work_ram_0[index - MEMORY_RANGE_WORK_RAM_0.start];
+            return &self.work_ram[0][index - MEMORY_RANGE_WORK_RAM_0.start];
         }
 
         if MEMORY_RANGE_WORK_RAM_1.contains(&index) {
-            return &self.work_ram_1[index - MEMORY_RANGE_WORK_RAM_1.start];
+            return &self.work_ram[self.work_ram_bank()][index - MEMORY_RANGE_WORK_RAM_1.start];
         }
 
         if MEMORY_RANGE_ECHO_RAM.contains(&index) {
-            let index = index - MEMORY_RANGE_ECHO_RAM.start;
-            if index < MEMORY_SIZE_WORK_RAM_0 {
-                return &self.work_ram_0[index];
-            }
-            return &self.work_ram_1[index - MEMORY_SIZE_WORK_RAM_0];
+            return self.get_force(echo_ram_source(address));
         }
 
         if MEMORY_RANGE_OAM.contains(&index) {
@@ -192,57 +272,35 @@ impl Emulator {
         u16::from_le_bytes([byte0, byte1])
     }
 
-    /// Read a u8 from the memory at the current program counter and advance the program counter by 1.
-    pub fn read_u8_at_pc(&mut self) -> u8 {
-        let address = self.program_counter.post_increment(1);
-        self.get(address)
-    }
-
-    /// Read an i8 from the memory at the current program counter and advance the program counter by 1.
-    pub fn read_i8_at_pc(&mut self) -> i8 {
-        let address = self.program_counter.post_increment(1);
-        self.get_i8(address)
-    }
-
-    /// Read a u16 from the memory at the current program counter and advance the program counter by 2.
-    pub fn read_u16_at_pc(&mut self) -> u16 {
-        let address = self.program_counter.post_increment(2);
-        self.get_u16(address)
-    }
-
     /// Get mutable reference to value in memory even if it's not accessible by the CPU.
+    ///
+    /// Cartridge memory is banked by the mapper, so it can't be referenced
+    /// directly, use [`Emulator::set_force`] instead.
     pub fn get_mut_force(&mut self, address: u16) -> &mut u8 {
         let index = address as usize;
-        if MEMORY_RANGE_ROM_BANK_00.contains(&index) {
-            return &mut self.rom_bank_00[index];
-        }
-
-        if MEMORY_RANGE_ROM_BANK_01.contains(&index) {
-            return &mut self.rom_bank_01[index - MEMORY_RANGE_ROM_BANK_01.start];
+        if MEMORY_RANGE_ROM_BANK_00.contains(&index)
+            || MEMORY_RANGE_ROM_BANK_01.contains(&index)
+            || MEMORY_RANGE_EXTERNAL_RAM.contains(&index)
+        {
+            panic!("Cartridge memory can't be referenced: {address:#04X}");
         }
 
         if MEMORY_RANGE_VRAM.contains(&index) {
-            return &mut self.vram[index - MEMORY_RANGE_VRAM.start];
-        }
-
-        if MEMORY_RANGE_EXTERNAL_RAM.contains(&index) {
-            return &mut self.external_ram[index - MEMORY_RANGE_EXTERNAL_RAM.start];
+            let bank = self.vram_bank();
+            return &mut self.vram[bank][index - MEMORY_RANGE_VRAM.start];
         }
 
         if MEMORY_RANGE_WORK_RAM_0.contains(&index) {
-            return &mut self.work_ram_0[index - MEMORY_RANGE_WORK_RAM_0.start];
+            return &mut self.work_ram[0][index - MEMORY_RANGE_WORK_RAM_0.start];
         }
 
         if MEMORY_RANGE_WORK_RAM_1.contains(&index) {
-            return &mut self.work_ram_1[index - MEMORY_RANGE_WORK_RAM_1.start];
+            let bank = self.work_ram_bank();
+            return &mut self.work_ram[bank][index - MEMORY_RANGE_WORK_RAM_1.start];
         }
 
         if MEMORY_RANGE_ECHO_RAM.contains(&index) {
-            let index = index - MEMORY_RANGE_ECHO_RAM.start;
-            if index < MEMORY_SIZE_WORK_RAM_0 {
-                return &mut self.work_ram_0[index];
-            }
-            return &mut self.work_ram_1[index - MEMORY_SIZE_WORK_RAM_0];
+            return self.get_mut_force(echo_ram_source(address));
         }
 
         if MEMORY_RANGE_OAM.contains(&index) {
@@ -271,32 +329,54 @@ impl Emulator {
     /// Set value in memory if it's accessible by the CPU (check `get` method for details).
     #[inline(always)]
     pub fn set(&mut self, address: u16, value: u8) {
-        if !self.is_address_accessible(address) {
+        if !self.is_address_accessible(address) || self.oam_dma_conflict(address).is_some() {
             return;
         }
 
-        if address == RegisterDIV::ADDRESS {
-            *self.internal_timer.as_u16_mut() = 0;
+        let index = address as usize;
+        if MEMORY_RANGE_ROM_BANK_00.contains(&index) || MEMORY_RANGE_ROM_BANK_01.contains(&index) {
+            // ROM can't be written, writes are handled by the mapper
+            self.rom.write_rom(address, value);
+            return;
+        }
+
+        if MEMORY_RANGE_IO_REGISTERS.contains(&index) {
+            self.write_io_register(address, value);
+            return;
         }
 
         self.set_force(address, value);
     }
 
     /// Set value in memory even if it's not accessible by the CPU.
+    ///
+    /// Writes to the ROM area patch the currently mapped ROM byte.
     pub fn set_force(&mut self, address: u16, value: u8) {
+        let index = address as usize;
+        if MEMORY_RANGE_ROM_BANK_00.contains(&index) || MEMORY_RANGE_ROM_BANK_01.contains(&index) {
+            if let Some(byte) = self.rom.rom_byte_mut(address) {
+                *byte = value;
+            }
+            return;
+        }
+
+        if MEMORY_RANGE_EXTERNAL_RAM.contains(&index) {
+            self.rom.write_ram(address, value);
+            return;
+        }
+
         *self.get_mut_force(address) = value;
     }
 
     pub(crate) fn init_memory(&mut self) {
-        // TODO add rest IO registers
-        self.reg_reset::<RegisterLCDC>();
-        self.reg_reset::<RegisterSCY>();
-        self.reg_reset::<RegisterSCX>();
-        self.reg_reset::<RegisterSTAT>();
-        self.reg_reset::<RegisterLY>();
-        self.reg_reset::<RegisterLYC>();
-        self.reg_reset::<RegisterWY>();
-        self.reg_reset::<RegisterWX>();
+        // registers set by the boot ROM are initialized by `init_io_registers`
+        self.reg_reset::<RegisterKEY1>();
+        self.reg_reset::<RegisterBANK>();
+        self.reg_reset::<RegisterVBK>();
+        self.reg_reset::<RegisterSVBK>();
+        self.reg_reset::<RegisterIE>();
+        self.reg_reset::<RegisterOBP0>();
+        self.reg_reset::<RegisterOBP1>();
     }
 
     /// Update registers after a cycle.
@@ -320,17 +400,13 @@ impl Emulator {
 }
 
 pub trait MemoryAddress {
-    /// Get value at the current address as CPU would see it.
-    fn get_at(&self, emulator: &Emulator) -> u8;
-
-    /// Get value at the current address even if it's not accessible by the CPU.
-    fn get_at_force(&self, emulator: &Emulator) -> u8;
-
-    /// Set value at the current address if it's accessible by the CPU.
-    fn set_at(&self, emulator: &mut Emulator, value: u8);
+    /// Read value at the current address as CPU would do it. Takes single
+    /// M-cycle.
+    fn get_at<B: Bus>(&self, cpu: &Cpu, bus: &mut B) -> u8;
 
-    /// Set value at the current address even if it's not accessible by the CPU.
-    fn set_at_force(&self, emulator: &mut Emulator, value: u8);
+    /// Write value at the current address as CPU would do it. Takes single
+    /// M-cycle.
+    fn set_at<B: Bus>(&self, cpu: &Cpu, bus: &mut B, value: u8);
 }
 
 #[test]
@@ -0,0 +1,221 @@
+use crate::*;
+
+/// Emulated hardware model.
+///
+/// Affects the post-boot state and the model-specific features (e.g. CGB
+/// mode is available only on CGB and AGB).
+///
+/// [more info](https://gbdev.io/pandocs/Power_Up_Sequence.html)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum EmulatorModel {
+    /// Early DMG with a different boot ROM
+    Dmg0,
+    /// Original Game Boy
+    Dmg,
+    /// Game Boy Pocket
+    Mgb,
+    /// Super Game Boy
+    Sgb,
+    /// Super Game Boy 2
+    Sgb2,
+    /// Game Boy Color
+    #[default]
+    Cgb,
+    /// Game Boy Advance running in the CGB compatibility mode
+    Agb,
+}
+
+impl EmulatorModel {
+    pub const ALL: [EmulatorModel; 7] = [
+        Self::Dmg0,
+        Self::Dmg,
+        Self::Mgb,
+        Self::Sgb,
+        Self::Sgb2,
+        Self::Cgb,
+        Self::Agb,
+    ];
+
+    /// Check if the model supports CGB mode
+    pub fn is_cgb(self) -> bool {
+        matches!(self, Self::Cgb | Self::Agb)
+    }
+
+    /// Check if the model is a Super Game Boy, its boot ROM leaves the audio
+    /// in a different state
+    pub fn is_sgb(self) -> bool {
+        matches!(self, Self::Sgb | Self::Sgb2)
+    }
+
+    /// Value of the internal timer (DIV is its upper byte) when the boot ROM
+    /// hands off to the cartridge.
+    ///
+    /// It's `0` on SGB and CGB models, since there it depends on the boot ROM
+    /// timing (e.g. the logo animation), which is not emulated.
+    pub fn post_boot_internal_timer(self) -> u16 {
+        match self {
+            Self::Dmg0 => 0x1800,
+            Self::Dmg | Self::Mgb => 0xABCC,
+            Self::Sgb | Self::Sgb2 | Self::Cgb | Self::Agb => 0x0000,
+        }
+    }
+}
+
+/// I/O registers left with the same value by the boot ROM of every model
+const POST_BOOT_IO_REGISTERS: [(u16, u8); 33] = [
+    (RegisterP1::ADDRESS, 0xCF),
+    // SB
+    (0xFF01, 0x00),
+    (RegisterTIMA::ADDRESS, 0x00),
+    (RegisterTMA::ADDRESS, 0x00),
+    (RegisterTAC::ADDRESS, 0xF8),
+    (RegisterIF::ADDRESS, 0xE1),
+    // NR10-NR14
+    (0xFF10, 0x80),
+    (0xFF11, 0xBF),
+    (0xFF12, 0xF3),
+    (0xFF13, 0xFF),
+    (0xFF14, 0xBF),
+    // NR21-NR24
+    (0xFF16, 0x3F),
+    (0xFF17, 0x00),
+    (0xFF18, 0xFF),
+    (0xFF19, 0xBF),
+    // NR30-NR34
+    (0xFF1A, 0x7F),
+    (0xFF1B, 0xFF),
+    (0xFF1C, 0x9F),
+    (0xFF1D, 0xFF),
+    (0xFF1E, 0xBF),
+    // NR41-NR44
+    (0xFF20, 0xFF),
+    (0xFF21, 0x00),
+    (0xFF22, 0x00),
+    (0xFF23, 0xBF),
+    // NR50, NR51
+    (0xFF24, 0x77),
+    (0xFF25, 0xF3),
+    (RegisterLCDC::ADDRESS, 0x91),
+    (RegisterSCY::ADDRESS, 0x00),
+    (RegisterSCX::ADDRESS, 0x00),
+    (RegisterLYC::ADDRESS, 0x00),
+    (RegisterBGP::ADDRESS, 0xFC),
+    (RegisterWY::ADDRESS, 0x00),
+    (RegisterWX::ADDRESS, 0x00),
+];
+
+impl EmulatorModel {
+    /// Values of the I/O registers when the boot ROM hands off to the
+    /// cartridge, as read by the CPU.
+    ///
+    /// Registers unknown on some models (STAT and LY on SGB and CGB) use the
+    /// DMG values, OBJ palettes and CGB registers are left at their defaults.
+    pub fn post_boot_io_registers(self) -> Vec<(u16, u8)> {
+        let mut registers = POST_BOOT_IO_REGISTERS.to_vec();
+
+        let (sc, dma) = if self.is_cgb() {
+            (0x7F, 0x00)
+        } else {
+            (0x7E, 0xFF)
+        };
+        // SGB boot ROM doesn't play the chime, channel 1 is left off
+        let nr52 = if self.is_sgb() { 0xF0 } else { 0xF1 };
+        let (stat, ly) = if self == Self::Dmg0 {
+            (0x81, 0x91)
+        } else {
+            (0x85, 0x00)
+        };
+
+        registers.extend([
+            (0xFF02, sc),
+            (0xFF26, nr52),
+            (RegisterSTAT::ADDRESS, stat),
+            (RegisterLY::ADDRESS, ly),
+            (RegisterDMA::ADDRESS, dma),
+        ]);
+        registers
+    }
+}
+
+/// Sum of the title bytes, used by the CGB boot ROM to pick the palette for
+/// DMG games.
+fn title_checksum(rom: &Rom) -> u8 {
+    let Some(title) = rom.data.get(ROM_RANGE_TITLE) else {
+        return 0;
+    };
+
+    let is_nintendo = CartridgeHeader::decode(&rom.data).is_ok_and(|header| {
+        matches!(
+            header.licensee,
+            Licensee::Old(0x01) | Licensee::New([b'0', b'1'])
+        )
+    });
+    if !is_nintendo {
+        return 0;
+    }
+
+    title.iter().fold(0, |sum, byte| sum.wrapping_add(*byte))
+}
+
+impl Emulator {
+    /// Set CPU registers to the values left by the boot ROM of the model.
+    pub(crate) fn init_cpu_registers(&mut self) {
+        let header_checksum = self
+            .rom
+            .data
+            .get(ROM_ADDRESS_HEADER_CHECKSUM)
+            .copied()
+            .unwrap_or(0);
+        // carry and half-carry are set by the header checksum verification
+        let dmg_flags = if header_checksum == 0 { 0x80 } else { 0xB0 };
+
+        let [af, bc, de, hl] = match self.model {
+            EmulatorModel::Dmg0 => [0x0100, 0xFF13, 0x00C1, 0x8403],
+            EmulatorModel::Dmg => [0x0100 | dmg_flags, 0x0013, 0x00D8, 0x014D],
+            EmulatorModel::Mgb => [0xFF00 | dmg_flags, 0x0013, 0x00D8, 0x014D],
+            EmulatorModel::Sgb => [0x0100, 0x0014, 0x0000, 0xC060],
+            EmulatorModel::Sgb2 => [0xFF00, 0x0014, 0x0000, 0xC060],
+            EmulatorModel::Cgb | EmulatorModel::Agb => {
+                let f = if self.model == EmulatorModel::Cgb {
+                    0x80
+                } else {
+                    0x00
+                };
+                let b = (self.model == EmulatorModel::Agb) as u8;
+
+                if self.cgb_mode {
+                    [0x1100 | f, (b as u16) << 8, 0xFF56, 0x000D]
+                } else {
+                    let b = title_checksum(&self.rom).wrapping_add(b);
+                    let hl = if b == 0x43 || b == 0x58 {
+                        0x991A
+                    } else {
+                        0x007C
+                    };
+                    [0x1100 | f, (b as u16) << 8, 0x0008, hl]
+                }
+            }
+        };
+
+        self.cpu.accumulator_and_flags.set(af);
+        self.cpu.register_bc.set(bc);
+        self.cpu.register_de.set(de);
+        self.cpu.register_hl.set(hl);
+        self.cpu.program_counter.0 = 0x0100;
+        self.cpu.stack_pointer.0 = 0xFFFE;
+    }
+
+    /// Set I/O registers to the values left by the boot ROM of the model
+    pub(crate) fn init_io_registers(&mut self) {
+        for (address, value) in self.model.post_boot_io_registers() {
+            *self.get_mut_force(address) = value;
+        }
+    }
+
+    /// Set timer to the post-boot value of the model
+    pub(crate) fn init_model_timer(&mut self) {
+        *self.internal_timer.as_u16_mut() = self.model.post_boot_internal_timer();
+        let internal_timer = self.internal_timer;
+        self.reg_mut::<RegisterDIV>().0 = internal_timer.high();
+    }
+}
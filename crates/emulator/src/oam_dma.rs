@@ -0,0 +1,122 @@
+use crate::*;
+
+/// Number of bytes copied to OAM by a single transfer, one per M-cycle
+pub const OAM_DMA_LENGTH: u8 = 0xA0;
+
+/// Amount of M-cycles between the write to the DMA register and the start of
+/// the transfer, including the cycle of the write
+pub const OAM_DMA_START_DELAY: u8 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct OamDmaTransfer {
+    /// Start address of the source page
+    pub source: u16,
+    /// Index of the next byte to copy
+    pub index: u8,
+}
+
+impl OamDmaTransfer {
+    /// Source address of the next byte.
+    ///
+    /// Pages above $DF are read from the echo RAM.
+    pub fn source_address(&self) -> u16 {
+        let source = self.source + self.index as u16;
+        if source as usize >= MEMORY_RANGE_ECHO_RAM.start {
+            source - 0x2000
+        } else {
+            source
+        }
+    }
+}
+
+/// OAM DMA controller.
+///
+/// [more info](https://gbdev.io/pandocs/OAM_DMA_Transfer.html)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct OamDma {
+    /// Transfer in progress, OAM and the source bus are blocked for the CPU
+    pub active: Option<OamDmaTransfer>,
+    /// Requested transfer and amount of M-cycles until it replaces the active
+    /// one. The active transfer continues during the start delay.
+    pub pending: Option<(u16, u8)>,
+}
+
+/// Memory buses which can conflict with the OAM DMA
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+enum MemoryBus {
+    /// Cartridge ROM, external RAM and work RAM
+    External,
+    Video,
+    Oam,
+}
+
+impl MemoryBus {
+    /// Bus used to access `address`, `None` for I/O registers and HRAM, which
+    /// are always accessible
+    fn of(address: u16) -> Option<Self> {
+        let address = address as usize;
+
+        if MEMORY_RANGE_VRAM.contains(&address) {
+            Some(Self::Video)
+        } else if address < MEMORY_RANGE_OAM.start {
+            Some(Self::External)
+        } else if address < MEMORY_RANGE_IO_REGISTERS.start {
+            Some(Self::Oam)
+        } else {
+            None
+        }
+    }
+}
+
+impl Emulator {
+    /// Handle write to the DMA register
+    pub fn start_oam_dma(&mut self, page: u8) {
+        self.oam_dma.pending = Some(((page as u16) << 8, OAM_DMA_START_DELAY));
+    }
+
+    pub fn is_oam_dma_active(&self) -> bool {
+        self.oam_dma.active.is_some()
+    }
+
+    /// Copy single byte of the active transfer and advance the start delay
+    /// of the pending one. Called every M-cycle.
+    pub fn tick_oam_dma(&mut self) {
+        if let Some(mut transfer) = self.oam_dma.active {
+            let value = *self.get_force(transfer.source_address());
+            self.oam[transfer.index as usize] = value;
+
+            transfer.index += 1;
+            self.oam_dma.active = (transfer.index < OAM_DMA_LENGTH).then_some(transfer);
+        }
+
+        if let Some((source, delay)) = &mut self.oam_dma.pending {
+            *delay -= 1;
+            if *delay == 0 {
+                let source = *source;
+                self.oam_dma.active = Some(OamDmaTransfer { source, index: 0 });
+                self.oam_dma.pending = None;
+            }
+        }
+    }
+
+    /// Value seen by the CPU at `address` while the OAM DMA is active, `None`
+    /// if the access doesn't conflict with the transfer.
+    ///
+    /// OAM reads as $FF, the bus used by the transfer returns the byte being
+    /// copied.
+    pub fn oam_dma_conflict(&self, address: u16) -> Option<u8> {
+        let transfer = self.oam_dma.active?;
+
+        let bus = MemoryBus::of(address)?;
+        if bus == MemoryBus::Oam {
+            return Some(0xFF);
+        }
+
+        let source_address = transfer.source_address();
+        if MemoryBus::of(source_address) == Some(bus) {
+            return Some(*self.get_force(source_address));
+        }
+
+        None
+    }
+}
@@ -1,9 +1,15 @@
+mod object;
+mod pixel_fifo;
 mod ppu;
 mod render;
+mod scanline;
 mod screen;
 mod tile;
 
+pub use object::*;
+pub use pixel_fifo::*;
 pub use ppu::*;
 pub use render::*;
+pub use scanline::*;
 pub use screen::*;
 pub use tile::*;
@@ -0,0 +1,119 @@
+use crate::*;
+use bit_flag::{bit_flag, flag_mask};
+
+/// Size of a single OAM entry in bytes
+pub const OBJECT_ENTRY_SIZE: usize = 4;
+/// Amount of objects in OAM
+pub const OBJECTS_COUNT: usize = MEMORY_SIZE_OAM / OBJECT_ENTRY_SIZE;
+/// Maximum amount of objects selected by the OAM scan for a single scanline
+pub const OBJECTS_PER_LINE: usize = 10;
+
+/// Object Y position of the top edge of the screen
+pub const OBJECT_Y_OFFSET: usize = 16;
+/// Object X position of the left edge of the screen
+pub const OBJECT_X_OFFSET: usize = 8;
+
+/// Object (sprite) from OAM.
+///
+/// Check [Object Attribute Memory](https://gbdev.io/pandocs/OAM.html) for more
+/// information.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Object {
+    /// Vertical position plus 16
+    pub y: u8,
+    /// Horizontal position plus 8
+    pub x: u8,
+    pub tile_index: u8,
+    pub attributes: ObjectAttributes,
+    /// Index of the entry in OAM
+    pub oam_index: u8,
+}
+
+impl Object {
+    /// Read OAM entry `oam_index`
+    pub fn read(oam: &[u8; MEMORY_SIZE_OAM], oam_index: usize) -> Self {
+        let entry = &oam[oam_index * OBJECT_ENTRY_SIZE..][..OBJECT_ENTRY_SIZE];
+
+        Self {
+            y: entry[0],
+            x: entry[1],
+            tile_index: entry[2],
+            attributes: ObjectAttributes(entry[3]),
+            oam_index: oam_index as u8,
+        }
+    }
+
+    /// Check if the object overlaps the scanline `ly`
+    pub fn is_on_line(&self, ly: usize, height: usize) -> bool {
+        let top = self.y as usize;
+        let line = ly + OBJECT_Y_OFFSET;
+
+        (top..top + height).contains(&line)
+    }
+}
+
+/// Flags of the object (byte 3 of the OAM entry)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ObjectAttributes(pub u8);
+
+impl From<u8> for ObjectAttributes {
+    fn from(value: u8) -> Self {
+        Self(value)
+    }
+}
+
+impl From<ObjectAttributes> for u8 {
+    fn from(value: ObjectAttributes) -> Self {
+        value.0
+    }
+}
+
+#[bit_flag]
+impl ObjectAttributes {
+    /// If set, background and window colors 1-3 are drawn over the object
+    #[flag_mask]
+    pub const PRIORITY: u8 = 0b1000_0000;
+
+    #[flag_mask]
+    pub const Y_FLIP: u8 = 0b0100_0000;
+
+    #[flag_mask]
+    pub const X_FLIP: u8 = 0b0010_0000;
+
+    /// Selects OBP1 instead of OBP0 (Non-CGB Mode only)
+    #[flag_mask]
+    pub const DMG_PALETTE: u8 = 0b0001_0000;
+
+    /// Selects VRAM bank 1 for the tile data (CGB Mode only)
+    #[flag_mask]
+    pub const BANK: u8 = 0b0000_1000;
+}
+
+impl Emulator {
+    /// Height of the objects selected by LCDC bit 2, 8 or 16 pixels
+    pub fn object_height(&self) -> usize {
+        if self.reg::<RegisterLCDC>().get_object_size() {
+            TILE_HEIGHT * 2
+        } else {
+            TILE_HEIGHT
+        }
+    }
+
+    /// Check single OAM entry during the OAM scan (mode 2), objects which
+    /// overlap the current scanline are selected until the limit is reached.
+    pub fn scan_oam_entry(&mut self, oam_index: usize) {
+        if oam_index == 0 {
+            self.line_objects.clear();
+        }
+
+        if self.line_objects.len() == OBJECTS_PER_LINE {
+            return;
+        }
+
+        let ly = self.reg::<RegisterLY>().0 as usize;
+        let object = Object::read(&self.oam, oam_index);
+        if object.is_on_line(ly, self.object_height()) {
+            self.line_objects.push(object);
+        }
+    }
+}
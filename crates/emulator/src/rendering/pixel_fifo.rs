@@ -0,0 +1,174 @@
+use std::collections::VecDeque;
+
+use crate::*;
+
+/// Dots taken by each fetcher step except `Push`
+pub const FETCHER_STEP_DURATION: u8 = 2;
+/// Dots taken by the first fetch of the scanline, its tile is discarded
+pub const FETCHER_STARTUP_DURATION: u8 = 6;
+/// Dots taken by the object fetch, once the background fetcher has its tile
+/// data ready
+pub const OBJECT_FETCH_DURATION: u8 = 6;
+
+/// Step of the background/window fetcher
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum FetcherStep {
+    /// Read the tile index from the tile map
+    #[default]
+    Tile,
+    /// Read the low byte of the tile row
+    DataLow,
+    /// Read the high byte of the tile row
+    DataHigh,
+    /// Wait until the background FIFO is empty and push the 8 pixels
+    Push,
+}
+
+impl FetcherStep {
+    fn next(self) -> Self {
+        match self {
+            Self::Tile => Self::DataLow,
+            Self::DataLow => Self::DataHigh,
+            Self::DataHigh => Self::Push,
+            Self::Push => Self::Tile,
+        }
+    }
+}
+
+/// Pixel of an object waiting in the object FIFO
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ObjectPixel {
+    pub index: PaletteIndex,
+    pub attributes: ObjectAttributes,
+    pub oam_index: u8,
+}
+
+/// State of the pixel FIFOs and the fetcher during mode 3.
+///
+/// The length of mode 3 depends on how long the fetcher stalls the LCD,
+/// 172 dots without scrolling, window and objects.
+///
+/// [more info](https://gbdev.io/pandocs/pixel_fifo.html)
+#[derive(Debug, Clone, Default)]
+pub struct PixelFifo {
+    /// Background or window color indices
+    pub bg: VecDeque<PaletteIndex>,
+    /// Object pixels aligned with `bg`, `None` where no object was fetched
+    pub objects: VecDeque<Option<ObjectPixel>>,
+
+    pub step: FetcherStep,
+    /// Dots spent in the current fetcher step
+    pub step_dots: u8,
+    /// Tile column of the next fetch, relative to the left edge of the
+    /// screen or the window
+    pub fetcher_x: u8,
+    pub tile_index: u8,
+    pub data_low: u8,
+    pub data_high: u8,
+    /// Indicate that the fetcher reads the window instead of the background
+    pub is_window: bool,
+
+    /// Dots left of the first fetch of the scanline
+    pub startup_dots: u8,
+    /// Pixels shifted out without reaching the LCD, used for the SCX fine
+    /// scroll and the window placed before the left edge of the screen
+    pub discard: u8,
+    /// Screen column of the next pixel sent to the LCD
+    pub lcd_x: usize,
+
+    /// Index in `line_objects` of the object being fetched and the dots left
+    pub object_fetch: Option<(usize, u8)>,
+    /// Bit per entry of `line_objects`, set once the object is fetched
+    pub fetched_objects: u16,
+}
+
+impl PixelFifo {
+    /// State at the start of mode 3
+    pub fn new(scx: u8) -> Self {
+        Self {
+            startup_dots: FETCHER_STARTUP_DURATION,
+            discard: scx % TILE_WIDTH as u8,
+            ..Default::default()
+        }
+    }
+
+    /// Restart the fetcher at the first tile of the window, pixels of the
+    /// background waiting in the FIFO are dropped
+    pub fn start_window(&mut self, discard: u8) {
+        self.bg.clear();
+        self.is_window = true;
+        self.step = FetcherStep::Tile;
+        self.step_dots = 0;
+        self.fetcher_x = 0;
+        self.discard = discard;
+    }
+
+    /// Advance the current fetcher step by a dot, returns the step which
+    /// was completed
+    pub fn tick_step(&mut self) -> Option<FetcherStep> {
+        self.step_dots += 1;
+        if self.step_dots < FETCHER_STEP_DURATION {
+            return None;
+        }
+
+        let step = self.step;
+        self.step = step.next();
+        self.step_dots = 0;
+
+        Some(step)
+    }
+
+    /// Push the fetched tile row into the background FIFO, only possible if
+    /// it's empty. The fetcher starts the next tile on the same dot.
+    pub fn try_push(&mut self) -> bool {
+        if !self.bg.is_empty() {
+            return false;
+        }
+
+        for x in 0..TILE_WIDTH {
+            self.bg
+                .push_back(tile_row_pixel(self.data_low, self.data_high, x));
+        }
+
+        self.fetcher_x = self.fetcher_x.wrapping_add(1);
+        self.step = FetcherStep::Tile;
+        self.step_dots = 1;
+
+        true
+    }
+
+    /// Mix the object row into the object FIFO. `row` starts at the first
+    /// visible column of the object. Non-transparent pixels already in the
+    /// FIFO are kept, unless `by_oam_index` is set and the new object has a
+    /// smaller OAM index (CGB mode).
+    pub fn merge_object(&mut self, row: &[ObjectPixel], by_oam_index: bool) {
+        if self.objects.len() < row.len() {
+            self.objects.resize(row.len(), None);
+        }
+
+        for (slot, &pixel) in self.objects.iter_mut().zip(row) {
+            if pixel.index == PaletteIndex::I0 {
+                continue;
+            }
+
+            let is_replaced = match slot {
+                None => true,
+                Some(current) => {
+                    current.index == PaletteIndex::I0
+                        || (by_oam_index && pixel.oam_index < current.oam_index)
+                }
+            };
+            if is_replaced {
+                *slot = Some(pixel);
+            }
+        }
+    }
+
+    /// Shift the next pixel out of both FIFOs
+    pub fn pop(&mut self) -> Option<(PaletteIndex, Option<ObjectPixel>)> {
+        let bg = self.bg.pop_front()?;
+        let object = self.objects.pop_front().flatten();
+
+        Some((bg, object))
+    }
+}
@@ -14,7 +14,7 @@ pub const LAST_SCANLINE: usize = TOTAL_SCANLINES - 1;
 pub const MODE_0_BASE_DURATION: usize = 376;
 pub const MODE_1_DURATION: usize = SCANLINE_DURATION * 10;
 pub const MODE_2_DURATION: usize = 80;
-// pub const MODE_3_BASE_DURATION: usize = 190;
+/// Duration of mode 3 without SCX fine scroll, window and objects
 pub const MODE_3_BASE_DURATION: usize = 172;
 
 impl Emulator {
@@ -47,6 +47,11 @@ impl Emulator {
                     self.scanline_progress,
                 );
 
+                // single OAM entry is checked every 2 dots
+                if self.scanline_progress.is_multiple_of(2) {
+                    self.scan_oam_entry(self.scanline_progress / 2 - 1);
+                }
+
                 if self.scanline_progress == MODE_2_DURATION {
                     PpuMode::Mode3.into()
                 } else {
@@ -55,7 +60,8 @@ impl Emulator {
             }
             // Sending pixels to the LCD
             PpuMode::Mode3 => {
-                if self.scanline_progress >= MODE_2_DURATION + self.mode_3_duration {
+                if self.handle_pixel_fifo_dot() {
+                    self.mode_3_duration = self.dots_in_current_mode;
                     PpuMode::Mode0.into()
                 } else {
                     None
@@ -72,8 +78,10 @@ impl Emulator {
                     );
                     if current_scanline + 1 == SCREEN_HEIGHT {
                         // screen end reached
+                        self.finish_frame();
                         self.is_frame_available = true;
-                        self.reg_mut::<RegisterIF>().set_lcd(true);
+                        self.frame_count += 1;
+                        self.request_interrupt(Interrupt::VBlank);
                         PpuMode::Mode1.into()
                     } else {
                         // scanline ended, start new one
@@ -101,6 +109,12 @@ impl Emulator {
         if let Some(new_mode) = new_mode {
             self.reg_mut::<RegisterSTAT>().set_ppu_mode(new_mode);
             self.dots_in_current_mode = 0;
+
+            match new_mode {
+                PpuMode::Mode3 => self.start_scanline(),
+                PpuMode::Mode0 => self.handle_hblank_dma(),
+                PpuMode::Mode1 | PpuMode::Mode2 => {}
+            }
         }
     }
 
@@ -122,9 +136,16 @@ impl Emulator {
     }
 
     /// Run emulator until next is available.
+    ///
+    /// Returns early if the CPU is in very low power mode (STOP), since PPU
+    /// doesn't produce frames in this mode.
     pub fn next_frame(&mut self) {
         while !self.is_frame_available {
-            self.handle_next_instruction();
+            self.step();
+
+            if self.cpu.state == CpuState::Stopped {
+                return;
+            }
         }
 
         self.is_frame_available = false;
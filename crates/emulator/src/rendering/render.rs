@@ -12,10 +12,6 @@ pub const TILE_MAP_TILES_COUNT: usize = TILE_MAP_WIDTH * TILE_MAP_HEIGHT;
 pub const TILE_MAP_SIZE: usize = TILE_MAP_TILES_COUNT * TILE_SIZE;
 
 impl Emulator {
-    pub fn render(&self) -> [u8; SCREEN_SIZE] {
-        todo!()
-    }
-
     /// Get index of the tile by it's position in the tile map.
     ///
     /// if window is true, then window tile map is used, otherwise background.
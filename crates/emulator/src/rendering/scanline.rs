@@ -0,0 +1,302 @@
+use crate::*;
+
+/// WX value which places the window at the left edge of the screen
+pub const WINDOW_X_OFFSET: usize = 7;
+
+impl Emulator {
+    /// Reset the pixel FIFO for the current scanline (LY). Called when the PPU
+    /// enters mode 3.
+    pub fn start_scanline(&mut self) {
+        if self.reg::<RegisterLY>().0 == self.reg::<RegisterWY>().0 {
+            self.window_y_triggered = true;
+        }
+
+        self.pixel_fifo = PixelFifo::new(self.reg::<RegisterSCX>().0);
+    }
+
+    /// Handle single dot of mode 3: advance the fetcher and send at most one
+    /// pixel to the LCD (`screen_buffer`).
+    ///
+    /// Registers are read when the tile is fetched (SCX, SCY, tile map and
+    /// tile data selection) or when the pixel is sent (palettes, BG and object
+    /// enable), so writes during the mode take effect mid-scanline.
+    ///
+    /// Returns `true` once the last pixel of the scanline was sent.
+    pub fn handle_pixel_fifo_dot(&mut self) -> bool {
+        if self.pixel_fifo.startup_dots > 0 {
+            self.pixel_fifo.startup_dots -= 1;
+            return false;
+        }
+
+        self.check_window_start();
+
+        // the LCD and the background fetcher are paused by the object fetch
+        if let Some((index, dots_left)) = self.pixel_fifo.object_fetch {
+            if dots_left > 1 {
+                self.pixel_fifo.object_fetch = Some((index, dots_left - 1));
+            } else {
+                self.pixel_fifo.object_fetch = None;
+                self.fetch_object(index);
+            }
+            return false;
+        }
+
+        self.tick_fetcher();
+
+        if let Some(index) = self.next_object_hit() {
+            // object is fetched once the background fetcher has its tile data
+            // ready, until then the LCD waits
+            if self.pixel_fifo.step == FetcherStep::Push {
+                self.pixel_fifo.object_fetch = Some((index, OBJECT_FETCH_DURATION - 1));
+            }
+            return false;
+        }
+
+        self.shift_pixel()
+    }
+
+    /// Restart the fetcher on the window once the LCD reaches WX
+    fn check_window_start(&mut self) {
+        let lcdc = *self.reg::<RegisterLCDC>();
+        let wx = self.reg::<RegisterWX>().0 as usize;
+        let lcd_x = self.pixel_fifo.lcd_x;
+
+        // in non CGB mode the window is hidden together with the background
+        let is_window_enabled = lcdc.get_window_enable()
+            && (self.cgb_mode || lcdc.get_bg_and_window_enable())
+            && self.window_y_triggered;
+        if self.pixel_fifo.is_window || !is_window_enabled || lcd_x + WINDOW_X_OFFSET < wx {
+            return;
+        }
+
+        // window placed left of the screen edge is shifted out before the
+        // first pixel
+        let discard = if lcd_x == 0 {
+            WINDOW_X_OFFSET.saturating_sub(wx) as u8
+        } else {
+            0
+        };
+        self.pixel_fifo.start_window(discard);
+    }
+
+    fn tick_fetcher(&mut self) {
+        if self.pixel_fifo.step == FetcherStep::Push {
+            self.pixel_fifo.try_push();
+            return;
+        }
+
+        let lcdc = *self.reg::<RegisterLCDC>();
+        match self.pixel_fifo.tick_step() {
+            Some(FetcherStep::Tile) => {
+                self.pixel_fifo.tile_index = self.fetch_tile_index(lcdc);
+            }
+            Some(FetcherStep::DataLow) => {
+                self.pixel_fifo.data_low = self.ppu_vram_byte(self.fetcher_row_address(lcdc));
+            }
+            Some(FetcherStep::DataHigh) => {
+                self.pixel_fifo.data_high = self.ppu_vram_byte(self.fetcher_row_address(lcdc) + 1);
+            }
+            Some(FetcherStep::Push) | None => {}
+        }
+    }
+
+    /// Line of the background or window (in pixels) fetched by the fetcher
+    fn fetcher_y(&self) -> usize {
+        if self.pixel_fifo.is_window {
+            self.window_line
+        } else {
+            let ly = self.reg::<RegisterLY>().0 as usize;
+            let scy = self.reg::<RegisterSCY>().0 as usize;
+            (ly + scy) % (TILE_MAP_HEIGHT * TILE_HEIGHT)
+        }
+    }
+
+    fn fetch_tile_index(&self, lcdc: RegisterLCDC) -> u8 {
+        let fifo = &self.pixel_fifo;
+
+        let (high_tile_map, tile_x) = if fifo.is_window {
+            (lcdc.get_win_tile_map(), fifo.fetcher_x as usize)
+        } else {
+            let scx = self.reg::<RegisterSCX>().0 as usize;
+            (
+                lcdc.get_bg_tile_map(),
+                scx / TILE_WIDTH + fifo.fetcher_x as usize,
+            )
+        };
+        let tile_map = if high_tile_map {
+            MEMORY_RANGE_TILE_INDICES_BANK1.start
+        } else {
+            MEMORY_RANGE_TILE_INDICES_BANK0.start
+        };
+
+        let position =
+            tile_map_cords_to_position(tile_x % TILE_MAP_WIDTH, self.fetcher_y() / TILE_HEIGHT);
+        self.ppu_vram_byte((tile_map + position) as u16)
+    }
+
+    /// Address of the fetched tile row
+    fn fetcher_row_address(&self, lcdc: RegisterLCDC) -> u16 {
+        let row = self.fetcher_y() % TILE_HEIGHT;
+        bg_tile_address(lcdc, self.pixel_fifo.tile_index) + (row * 2) as u16
+    }
+
+    /// Index in `line_objects` of the object starting at the next pixel, which
+    /// wasn't fetched yet.
+    ///
+    /// Objects left of the screen are reached together at the first pixel. In
+    /// non CGB mode the one with smaller X is fetched first, so it's drawn on
+    /// top, in CGB mode the one with smaller OAM index.
+    fn next_object_hit(&self) -> Option<usize> {
+        let fifo = &self.pixel_fifo;
+        if fifo.bg.is_empty() || fifo.discard > 0 || !self.reg::<RegisterLCDC>().get_object_enable()
+        {
+            return None;
+        }
+
+        let mut reached = self
+            .line_objects
+            .iter()
+            .enumerate()
+            .filter(|(index, object)| {
+                let is_fetched = fifo.fetched_objects & (1 << index) != 0;
+                !is_fetched && object.x as usize <= fifo.lcd_x + OBJECT_X_OFFSET
+            });
+
+        let object = if self.cgb_mode {
+            reached.next()
+        } else {
+            // first minimum keeps OAM order for the same X
+            reached.min_by_key(|(_, object)| object.x)
+        };
+        object.map(|(index, _)| index)
+    }
+
+    /// Read the current row of the object and mix it into the object FIFO
+    fn fetch_object(&mut self, index: usize) {
+        let object = self.line_objects[index];
+        self.pixel_fifo.fetched_objects |= 1 << index;
+
+        let ly = self.reg::<RegisterLY>().0 as usize;
+        let height = self.object_height();
+        let attributes = object.attributes;
+
+        // object size may change after the OAM scan, the row wraps then
+        let mut row = (ly + OBJECT_Y_OFFSET - object.y as usize) % height;
+        if attributes.get_y_flip() {
+            row = height - 1 - row;
+        }
+
+        // in 8x16 mode bit 0 of the tile index is ignored, the next tile
+        // is the bottom half
+        let tile_index = if height > TILE_HEIGHT {
+            object.tile_index & 0xFE
+        } else {
+            object.tile_index
+        };
+        let row_address = MEMORY_RANGE_OBJECT_TILES.start as u16
+            + (tile_index as u16 * TILE_SIZE as u16)
+            + (row * 2) as u16;
+
+        let bank = (self.cgb_mode && attributes.get_bank()) as usize;
+        let low = self.ppu_vram_byte_in_bank(bank, row_address);
+        let high = self.ppu_vram_byte_in_bank(bank, row_address + 1);
+
+        let pixels: [ObjectPixel; TILE_WIDTH] = std::array::from_fn(|x| {
+            let column = if attributes.get_x_flip() {
+                TILE_WIDTH - 1 - x
+            } else {
+                x
+            };
+            ObjectPixel {
+                index: tile_row_pixel(low, high, column),
+                attributes,
+                oam_index: object.oam_index,
+            }
+        });
+
+        // columns left of the current pixel are not drawn
+        let hidden = (self.pixel_fifo.lcd_x + OBJECT_X_OFFSET - object.x as usize).min(TILE_WIDTH);
+        self.pixel_fifo
+            .merge_object(&pixels[hidden..], self.cgb_mode);
+    }
+
+    /// Shift single pixel out of the FIFOs and draw it, returns `true` after
+    /// the last pixel of the scanline
+    fn shift_pixel(&mut self) -> bool {
+        let Some((bg_index, object)) = self.pixel_fifo.pop() else {
+            return false;
+        };
+
+        if self.pixel_fifo.discard > 0 {
+            self.pixel_fifo.discard -= 1;
+            return false;
+        }
+
+        let x = self.pixel_fifo.lcd_x;
+        let ly = self.reg::<RegisterLY>().0 as usize;
+        let shade = self.pixel_shade(bg_index, object);
+        self.screen_buffer
+            .set_pixel(x, ly, DMG_SHADES[shade as usize]);
+
+        self.pixel_fifo.lcd_x += 1;
+        if self.pixel_fifo.lcd_x < SCREEN_WIDTH {
+            return false;
+        }
+
+        // window line counter advances only on lines where the window is drawn
+        if self.pixel_fifo.is_window {
+            self.window_line += 1;
+        }
+        true
+    }
+
+    /// Shade of the pixel mixed from the background and object FIFOs
+    fn pixel_shade(&self, bg_index: PaletteIndex, object: Option<ObjectPixel>) -> u8 {
+        let lcdc = *self.reg::<RegisterLCDC>();
+        let bgp = *self.reg::<RegisterBGP>();
+
+        // blank background is white regardless of the palette and behaves as
+        // color index 0
+        let bg_index = (self.cgb_mode || lcdc.get_bg_and_window_enable()).then_some(bg_index);
+        let object = object.filter(|_| lcdc.get_object_enable());
+
+        match (object, bg_index) {
+            // BG colors 1-3 are drawn over the object with the priority flag,
+            // unless the BG priority is disabled in CGB mode
+            (Some(object), Some(bg_index))
+                if object.attributes.get_priority()
+                    && bg_index != PaletteIndex::I0
+                    && lcdc.get_bg_and_window_enable() =>
+            {
+                bgp.shade(bg_index)
+            }
+            (Some(object), _) => {
+                if object.attributes.get_dmg_palette() {
+                    self.reg::<RegisterOBP1>().shade(object.index)
+                } else {
+                    self.reg::<RegisterOBP0>().shade(object.index)
+                }
+            }
+            (None, Some(bg_index)) => bgp.shade(bg_index),
+            (None, None) => 0,
+        }
+    }
+
+    /// Hand off the rendered frame to `screen` and reset the window state.
+    /// Called at the start of VBlank.
+    pub fn finish_frame(&mut self) {
+        self.screen = self.screen_buffer;
+        self.window_line = 0;
+        self.window_y_triggered = false;
+    }
+
+    /// Read VRAM bank 0 as the PPU does, regardless of the CPU access
+    /// restrictions and the selected bank
+    fn ppu_vram_byte(&self, address: u16) -> u8 {
+        self.ppu_vram_byte_in_bank(0, address)
+    }
+
+    fn ppu_vram_byte_in_bank(&self, bank: usize, address: u16) -> u8 {
+        self.vram[bank][address as usize - MEMORY_RANGE_VRAM.start]
+    }
+}
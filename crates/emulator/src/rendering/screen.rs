@@ -31,6 +31,14 @@ impl Default for Screen {
     }
 }
 
+/// Shades of the non CGB palettes from white to black
+pub const DMG_SHADES: [ScreenPixel; 4] = [
+    ScreenPixel(0x7FFF),
+    ScreenPixel(0x56B5),
+    ScreenPixel(0x294A),
+    ScreenPixel(0x0000),
+];
+
 /// Represents a single pixel on the screen.
 ///
 /// 15 bit color depth.
@@ -60,14 +60,7 @@ impl Tile {
     /// There are 3 banks of tiles. Based on 4th bit of LCDC register, the
     /// tile data can be located at $8000-$87FF or $8800-$97FF.
     pub fn read_bg_tile(emulator: &Emulator, tile_index: u8) -> Self {
-        let tile_index = tile_index as u16;
-        let address = if emulator.reg::<RegisterLCDC>().get_bg_and_win_tile() {
-            MEMORY_RANGE_TILES_BLOCK0.start as u16 + (tile_index * TILE_SIZE as u16)
-        } else if tile_index < 128 {
-            MEMORY_RANGE_TILES_BLOCK2.start as u16 + (tile_index * TILE_SIZE as u16)
-        } else {
-            MEMORY_RANGE_TILES_BLOCK1.start as u16 + ((tile_index - 128) * TILE_SIZE as u16)
-        };
+        let address = bg_tile_address(*emulator.reg::<RegisterLCDC>(), tile_index);
 
         Self::read(emulator, address)
     }
@@ -76,17 +69,7 @@ impl Tile {
         assert!(x < TILE_WIDTH, "X coordinate out of bounds: {}", x);
         assert!(y < TILE_HEIGHT, "Y coordinate out of bounds: {}", y);
 
-        let shift = TILE_WIDTH - x - 1;
-
-        let bit0 = self.data[y * 2] >> shift & 0b1 == 1;
-        let bit1 = self.data[y * 2 + 1] >> shift & 0b1 == 1;
-
-        match (bit1, bit0) {
-            (false, false) => PaletteIndex::I0,
-            (false, true) => PaletteIndex::I1,
-            (true, false) => PaletteIndex::I2,
-            (true, true) => PaletteIndex::I3,
-        }
+        tile_row_pixel(self.data[y * 2], self.data[y * 2 + 1], x)
     }
 
     pub fn pixels(&self) -> [PaletteIndex; TILE_PIXEL_COUNT] {
@@ -103,6 +86,35 @@ impl Tile {
     }
 }
 
+/// Address of the background or window tile data, depends on the addressing
+/// mode selected by LCDC bit 4.
+pub fn bg_tile_address(lcdc: RegisterLCDC, tile_index: u8) -> u16 {
+    let tile_index = tile_index as u16;
+
+    if lcdc.get_bg_and_win_tile() {
+        MEMORY_RANGE_TILES_BLOCK0.start as u16 + (tile_index * TILE_SIZE as u16)
+    } else if tile_index < 128 {
+        MEMORY_RANGE_TILES_BLOCK2.start as u16 + (tile_index * TILE_SIZE as u16)
+    } else {
+        MEMORY_RANGE_TILES_BLOCK1.start as u16 + ((tile_index - 128) * TILE_SIZE as u16)
+    }
+}
+
+/// Color index of the pixel in a tile row, given by the two bytes of the row
+pub fn tile_row_pixel(low: u8, high: u8, x: usize) -> PaletteIndex {
+    let shift = TILE_WIDTH - x - 1;
+
+    let bit0 = low >> shift & 0b1 == 1;
+    let bit1 = high >> shift & 0b1 == 1;
+
+    match (bit1, bit0) {
+        (false, false) => PaletteIndex::I0,
+        (false, true) => PaletteIndex::I1,
+        (true, false) => PaletteIndex::I2,
+        (true, true) => PaletteIndex::I3,
+    }
+}
+
 /// Color index in the pallet.
 #[repr(u8)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Ord, PartialOrd, Hash)]
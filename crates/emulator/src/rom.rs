@@ -1,6 +1,10 @@
+use crate::*;
+
 pub const ROM_RANGE_ENTRYPOINT: std::ops::Range<usize> = 0x0100..0x0104;
 pub const ROM_RANGE_LOGO: std::ops::Range<usize> = 0x0104..0x0134;
-pub const ROM_RANGE_TITLE: std::ops::Range<usize> = 0x0134..0x13E;
+/// Title of the game, on newer cartridges the last bytes are used for the
+/// manufacturer code and the CGB flag
+pub const ROM_RANGE_TITLE: std::ops::Range<usize> = 0x0134..0x0144;
 pub const ROM_RANGE_MANUFACTURER_CODE: std::ops::Range<usize> = 0x13F..0x143;
 pub const ROM_ADDRESS_CGB_FLAG: usize = 0x143;
 pub const ROM_RANGE_NEW_LICENSEE_CODE: std::ops::Range<usize> = 0x144..0x146;
@@ -14,24 +18,170 @@ pub const ROM_ADDRESS_MASK_ROM_VERSION_NUMBER: usize = 0x14C;
 pub const ROM_ADDRESS_HEADER_CHECKSUM: usize = 0x14D;
 pub const ROM_RANGE_GLOBAL_CHECKSUM: std::ops::Range<usize> = 0x14E..0x150;
 
-// TODO add mappers
-#[derive(Default)]
+/// Game cartridge: ROM, external RAM and the memory bank controller.
+#[derive(Debug, Clone)]
 pub struct Rom {
     pub data: Vec<u8>,
+    /// External RAM of the cartridge, empty if there is none
+    pub ram: Vec<u8>,
+    pub mapper: CartridgeMapper,
+    /// RAM (and RTC) is battery-backed and should be persisted
+    pub has_battery: bool,
+    /// RAM was written since the last save
+    pub ram_modified: bool,
+}
+
+impl Default for Rom {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 impl Rom {
+    /// Blank 32 KiB ROM without mapper and RAM
     pub fn new() -> Self {
-        Self { data: Vec::new() }
+        Self {
+            data: vec![0; ROM_MIN_SIZE],
+            ram: Vec::new(),
+            mapper: CartridgeMapper::default(),
+            has_battery: false,
+            ram_modified: false,
+        }
     }
 
+    /// Create cartridge from the ROM data, the mapper and the RAM size are
+    /// taken from the header.
+    ///
+    /// If the header can't be decoded or the mapper is not supported, the
+    /// cartridge is treated as ROM only.
     pub fn from_bytes(data: Vec<u8>) -> Self {
-        Self { data }
+        let header = CartridgeHeader::decode(&data).ok();
+
+        let mapper = header
+            .as_ref()
+            .and_then(|header| CartridgeMapper::new(header.cartridge_type))
+            .unwrap_or_default();
+        let has_battery = header
+            .as_ref()
+            .is_some_and(|header| header.cartridge_type.has_battery());
+        let ram = match mapper {
+            // built-in RAM isn't declared in the header
+            CartridgeMapper::Mbc2(_) => vec![0xF0; MBC2_RAM_SIZE],
+            _ => vec![0; header.map_or(0, |header| header.ram_size)],
+        };
+
+        Self {
+            data,
+            ram,
+            mapper,
+            has_battery,
+            ram_modified: false,
+        }
     }
 
     pub fn read_range(&mut self, range: std::ops::Range<usize>) -> &[u8] {
         &self.data[range]
     }
+
+    /// Get byte of the ROM visible at `address` ($0000-$7FFF)
+    pub fn rom_byte(&self, address: u16) -> &u8 {
+        if self.data.is_empty() {
+            return &0xFF;
+        }
+
+        let offset = self.mapper.rom_offset(address) % self.data.len();
+        &self.data[offset]
+    }
+
+    /// Get mutable reference to byte of the ROM visible at `address`
+    /// ($0000-$7FFF), e.g. to patch the ROM.
+    pub fn rom_byte_mut(&mut self, address: u16) -> Option<&mut u8> {
+        if self.data.is_empty() {
+            return None;
+        }
+
+        let offset = self.mapper.rom_offset(address) % self.data.len();
+        Some(&mut self.data[offset])
+    }
+
+    /// Handle CPU write to the ROM area ($0000-$7FFF)
+    pub fn write_rom(&mut self, address: u16, value: u8) {
+        self.mapper.write_rom(address, value);
+    }
+
+    /// Get byte of the external RAM visible at `address` ($A000-$BFFF), $FF
+    /// if the RAM is disabled or not present.
+    pub fn ram_byte(&self, address: u16) -> &u8 {
+        self.mapper.read_ram(&self.ram, address).unwrap_or(&0xFF)
+    }
+
+    /// Write byte to the external RAM at `address` ($A000-$BFFF), ignored if
+    /// the RAM is disabled or not present.
+    pub fn write_ram(&mut self, address: u16, value: u8) {
+        if self.mapper.write_ram(&mut self.ram, address, value) {
+            self.ram_modified = true;
+        }
+    }
+
+    /// Content of the `.sav` file: external RAM followed by the RTC footer
+    /// (see [`RTC_SAVE_FOOTER_SIZE`]), `None` if the cartridge has no battery.
+    pub fn save_data(&self) -> Option<Vec<u8>> {
+        if !self.has_battery {
+            return None;
+        }
+
+        let mut data = self.ram.clone();
+        if let Some(rtc) = self.mapper.rtc() {
+            data.extend_from_slice(&rtc.save_footer());
+        }
+
+        Some(data)
+    }
+
+    /// Restore external RAM and the RTC from the `.sav` file content, the RTC
+    /// footer is optional.
+    pub fn load_save_data(&mut self, data: &[u8]) -> Result<(), SaveRamError> {
+        let size_mismatch = SaveRamError::SizeMismatch {
+            expected: self.ram.len(),
+            actual: data.len(),
+        };
+        if data.len() < self.ram.len() {
+            return Err(size_mismatch);
+        }
+
+        let (ram, footer) = data.split_at(self.ram.len());
+        if !footer.is_empty() {
+            let footer_loaded = self
+                .mapper
+                .rtc_mut()
+                .is_some_and(|rtc| rtc.load_footer(footer));
+            if !footer_loaded {
+                return Err(size_mismatch);
+            }
+        }
+
+        self.ram.copy_from_slice(ram);
+        if let CartridgeMapper::Mbc2(_) = self.mapper {
+            // only lower nibble is stored
+            self.ram.iter_mut().for_each(|value| *value |= 0xF0);
+        }
+        self.ram_modified = false;
+
+        Ok(())
+    }
+
+    /// Parse and verify the cartridge header, see [`CartridgeHeader::parse`]
+    pub fn header(&self) -> Result<CartridgeHeader, CartridgeHeaderError> {
+        CartridgeHeader::parse(&self.data)
+    }
+
+    /// Check if the cartridge supports CGB enhancements (based on the CGB flag
+    /// in the header).
+    pub fn supports_cgb(&self) -> bool {
+        self.data
+            .get(ROM_ADDRESS_CGB_FLAG)
+            .is_some_and(|flag| flag & 0x80 != 0)
+    }
 }
 
 impl<T> From<T> for Rom
@@ -0,0 +1,78 @@
+use std::path::PathBuf;
+
+use crate::*;
+
+#[derive(Debug, thiserror::Error)]
+pub enum SaveRamError {
+    #[error("Save file is not set")]
+    NoSaveFile,
+    #[error("Save data size mismatch: expected {expected} bytes, got {actual}")]
+    SizeMismatch { expected: usize, actual: usize },
+    #[error("Failed to access the save file: {0}")]
+    Io(#[from] std::io::Error),
+}
+
+impl Emulator {
+    /// Set path of the `.sav` file used by [`Emulator::save_ram`],
+    /// [`Emulator::load_save_ram`] and the autosave.
+    pub fn set_save_file(&mut self, path: impl Into<PathBuf>) {
+        self.save_file = Some(path.into());
+    }
+
+    pub fn with_save_file(mut self, path: impl Into<PathBuf>) -> Self {
+        self.set_save_file(path);
+        self
+    }
+
+    /// Write battery-backed RAM of the cartridge (and the RTC, if any) to the
+    /// save file.
+    ///
+    /// Does nothing if the cartridge has no battery.
+    pub fn save_ram(&mut self) -> Result<(), SaveRamError> {
+        let path = self.save_file.as_ref().ok_or(SaveRamError::NoSaveFile)?;
+
+        let Some(data) = self.rom.save_data() else {
+            return Ok(());
+        };
+        std::fs::write(path, data)?;
+        self.rom.ram_modified = false;
+
+        Ok(())
+    }
+
+    /// Load battery-backed RAM of the cartridge (and the RTC, if any) from the
+    /// save file.
+    ///
+    /// Does nothing if the cartridge has no battery or the save file doesn't
+    /// exist yet.
+    pub fn load_save_ram(&mut self) -> Result<(), SaveRamError> {
+        let path = self.save_file.as_ref().ok_or(SaveRamError::NoSaveFile)?;
+
+        if !self.rom.has_battery || !path.exists() {
+            return Ok(());
+        }
+        let data = std::fs::read(path)?;
+        self.rom.load_save_data(&data)
+    }
+
+    /// Save RAM every `autosave_interval` frames if it was modified. Errors
+    /// are stored in `autosave_error`.
+    ///
+    /// Called by [`Emulator::step`] at the frame boundary.
+    pub fn handle_autosave(&mut self) {
+        let Some(interval) = self.autosave_interval else {
+            return;
+        };
+
+        if self.save_file.is_none()
+            || !self.rom.ram_modified
+            || !self.frame_count.is_multiple_of(interval.max(1))
+        {
+            return;
+        }
+
+        if let Err(error) = self.save_ram() {
+            self.autosave_error = Some(error);
+        }
+    }
+}
@@ -1,23 +1,27 @@
 use crate::*;
 
-impl Emulator {
+impl Cpu {
     /// Push value to stack at current SP and decrement SP by size of value.
     /// (e.g. `2` for `u16`, `1` for `u8`)
-    pub fn push_to_stack<T: StackValue>(&mut self, value: T) {
+    ///
+    /// Takes single M-cycle per byte.
+    pub fn push_to_stack<B: Bus, T: StackValue>(&mut self, bus: &mut B, value: T) {
         for byte in value.to_bytes().into_iter().rev() {
             self.stack_pointer.decrement();
-            self.set(self.stack_pointer.into(), byte);
+            bus.cpu_write(self.stack_pointer.into(), byte);
         }
     }
 
     /// Pop value from stack at current SP and increment SP by size of value.
     /// (e.g. `2` for `u16`, `1` for `u8`)
-    pub fn pop_from_stack<T: StackValue>(&mut self) -> T {
+    ///
+    /// Takes single M-cycle per byte.
+    pub fn pop_from_stack<T: StackValue, B: Bus>(&mut self, bus: &mut B) -> T {
         let size = std::mem::size_of::<T>();
 
         let mut bytes = Vec::with_capacity(size);
         for _ in 0..size {
-            bytes.push(self.get(self.stack_pointer.into()));
+            bytes.push(bus.cpu_read(self.stack_pointer.into()));
             self.stack_pointer.increment();
         }
 
@@ -0,0 +1,103 @@
+use crate::*;
+
+/// Result of a single [`Emulator::step`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct StepResult {
+    /// Address of the executed instruction opcode
+    pub program_counter: u16,
+    /// Opcode of the executed instruction (IR register before the step)
+    pub opcode: u8,
+    /// Executed instruction. HALT, STOP or NOP if the CPU was suspended
+    pub instruction: Instruction,
+    /// Amount of M-cycles spent in the step, including the fetch of the next
+    /// opcode and the interrupt dispatch
+    pub cycles: usize,
+    /// Interrupt dispatched after the instruction, if any
+    pub interrupt: Option<Interrupt>,
+    /// Indicate that the PPU completed a frame during the step
+    pub frame_completed: bool,
+    /// Indicate that the APU filled an audio buffer during the step. Always
+    /// `false`, since the APU is not emulated yet
+    pub audio_buffer_completed: bool,
+}
+
+impl Emulator {
+    /// Execute single instruction and fetch the next opcode, same as
+    /// [`Emulator::handle_next_instruction`].
+    ///
+    /// If the CPU is suspended, handles single M-cycle of HALT, STOP or
+    /// locked CPU instead.
+    ///
+    /// Autosave (see [`Emulator::handle_autosave`]) is done after the step
+    /// which completed a frame.
+    pub fn step(&mut self) -> StepResult {
+        let program_counter = self.cpu.opcode_address;
+        let opcode = self.cpu.instruction_register;
+        let cycles = self.cycles;
+        let frame_count = self.frame_count;
+
+        // events of the previous step are not reported again if the CPU
+        // doesn't execute an instruction (e.g. speed switch or VRAM DMA)
+        self.cpu.dispatched_interrupt = None;
+
+        let instruction = self.handle_next_instruction();
+
+        // file I/O is kept out of the M-cycle, it's done between instructions
+        let frame_completed = self.frame_count != frame_count;
+        if frame_completed {
+            self.handle_autosave();
+        }
+
+        StepResult {
+            program_counter,
+            opcode,
+            instruction,
+            cycles: self.cycles - cycles,
+            interrupt: self.cpu.dispatched_interrupt,
+            frame_completed,
+            audio_buffer_completed: false,
+        }
+    }
+
+    /// Run emulator for at least given amount of M-cycles. Instructions are
+    /// never interrupted, so the last one may overshoot the target.
+    ///
+    /// Returns early if the CPU is in very low power mode (STOP), since time
+    /// doesn't pass in this mode.
+    ///
+    /// Returns the amount of M-cycles that have passed.
+    pub fn run_for_cycles(&mut self, cycles: usize) -> usize {
+        let start = self.cycles;
+
+        while self.cycles - start < cycles {
+            self.step();
+
+            if self.cpu.state == CpuState::Stopped {
+                break;
+            }
+        }
+
+        self.cycles - start
+    }
+
+    /// Run emulator step by step until `predicate` returns true. The
+    /// predicate is checked before every step.
+    ///
+    /// Returns early if the CPU is in very low power mode (STOP), since
+    /// nothing changes in this mode.
+    ///
+    /// Returns the amount of M-cycles that have passed.
+    pub fn run_until(&mut self, mut predicate: impl FnMut(&Emulator) -> bool) -> usize {
+        let start = self.cycles;
+
+        while !predicate(self) {
+            self.step();
+
+            if self.cpu.state == CpuState::Stopped {
+                break;
+            }
+        }
+
+        self.cycles - start
+    }
+}
@@ -0,0 +1,180 @@
+use crate::*;
+
+/// Number of bytes copied by a single block of the VRAM DMA, HBlank DMA copies
+/// one block per HBlank
+pub const VRAM_DMA_BLOCK_SIZE: u8 = 0x10;
+
+/// Number of bytes copied per M-cycle in normal speed. In double speed it's
+/// a single byte, so the transfer takes the same time.
+pub const VRAM_DMA_BYTES_PER_CYCLE: u8 = 2;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum VramDmaMode {
+    /// General-Purpose DMA, all blocks are copied at once
+    General,
+    /// HBlank DMA, single block is copied at the start of each HBlank
+    HBlank,
+}
+
+/// VRAM DMA controller (CGB only).
+///
+/// The CPU is halted while a block is being copied.
+///
+/// [more info](https://gbdev.io/pandocs/CGB_Registers.html#lcd-vram-dma-transfers)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct VramDma {
+    /// Address of the next source byte, set by HDMA1 and HDMA2
+    pub source: u16,
+    /// Address of the next destination byte in VRAM, set by HDMA3 and HDMA4
+    pub destination: u16,
+    /// Mode of the transfer in progress, `None` if it's finished or canceled
+    pub mode: Option<VramDmaMode>,
+    /// Amount of blocks left, including the one being copied
+    pub remaining_blocks: u8,
+    /// Amount of bytes left in the block being copied, `0` if no block is
+    /// being copied
+    pub block_bytes_left: u8,
+}
+
+impl Default for VramDma {
+    fn default() -> Self {
+        Self {
+            source: 0,
+            destination: MEMORY_RANGE_VRAM.start as u16,
+            mode: None,
+            remaining_blocks: 0,
+            block_bytes_left: 0,
+        }
+    }
+}
+
+impl VramDma {
+    /// Value read from HDMA5.
+    ///
+    /// Bits 0-6 are the remaining blocks minus 1, bit 7 is cleared while the
+    /// HBlank DMA is active. Finished transfer reads as $FF.
+    pub fn status(&self) -> u8 {
+        let length = self.remaining_blocks.wrapping_sub(1) & RegisterHDMA5::LENGTH;
+
+        if self.mode == Some(VramDmaMode::HBlank) {
+            length
+        } else {
+            length | RegisterHDMA5::HBLANK_MODE
+        }
+    }
+
+    /// Check if the CPU is halted by the block being copied
+    pub fn is_copying(&self) -> bool {
+        self.block_bytes_left > 0
+    }
+}
+
+impl Emulator {
+    /// Handle write to HDMA5
+    pub fn start_vram_dma(&mut self, value: u8) {
+        let is_hblank = value & RegisterHDMA5::HBLANK_MODE != 0;
+
+        if self.vram_dma.mode == Some(VramDmaMode::HBlank) && !is_hblank {
+            // block being copied is finished, the rest is canceled
+            self.vram_dma.mode = None;
+            return;
+        }
+
+        self.vram_dma.remaining_blocks = (value & RegisterHDMA5::LENGTH) + 1;
+
+        if is_hblank {
+            self.vram_dma.mode = Some(VramDmaMode::HBlank);
+
+            // first block is copied right away if started during HBlank or
+            // with LCD turned off
+            let is_lcd_enabled = self.reg::<RegisterLCDC>().get_lcd_and_ppu_enable();
+            if !is_lcd_enabled || self.reg::<RegisterSTAT>().get_ppu_mode() == PpuMode::Mode0 {
+                self.start_vram_dma_block();
+            }
+        } else {
+            self.vram_dma.mode = Some(VramDmaMode::General);
+            self.start_vram_dma_block();
+        }
+    }
+
+    /// Start copying the next block of the HBlank DMA. Called on HBlank
+    /// (mode 0) entry.
+    pub fn handle_hblank_dma(&mut self) {
+        if self.vram_dma.mode == Some(VramDmaMode::HBlank) && !self.vram_dma.is_copying() {
+            self.start_vram_dma_block();
+        }
+    }
+
+    fn start_vram_dma_block(&mut self) {
+        self.vram_dma.block_bytes_left = VRAM_DMA_BLOCK_SIZE;
+    }
+
+    /// Copy bytes of the block being copied. Called every M-cycle.
+    pub fn tick_vram_dma(&mut self) {
+        if !self.vram_dma.is_copying() {
+            return;
+        }
+
+        let bytes = if self.double_speed {
+            1
+        } else {
+            VRAM_DMA_BYTES_PER_CYCLE
+        };
+        for _ in 0..bytes {
+            self.copy_vram_dma_byte();
+        }
+
+        if self.vram_dma.is_copying() {
+            return;
+        }
+
+        self.vram_dma.remaining_blocks -= 1;
+        if self.vram_dma.remaining_blocks == 0 {
+            self.vram_dma.mode = None;
+        } else if self.vram_dma.mode == Some(VramDmaMode::General) {
+            self.start_vram_dma_block();
+        }
+    }
+
+    fn copy_vram_dma_byte(&mut self) {
+        let VramDma {
+            source,
+            destination,
+            ..
+        } = self.vram_dma;
+
+        // VRAM can't be read by the DMA
+        let value = if MEMORY_RANGE_VRAM.contains(&(source as usize)) {
+            0xFF
+        } else {
+            *self.get_force(source)
+        };
+        let bank = self.vram_bank();
+        self.vram[bank][destination as usize - MEMORY_RANGE_VRAM.start] = value;
+
+        self.vram_dma.source = source.wrapping_add(1);
+        self.vram_dma.destination = vram_dma_destination(destination.wrapping_add(1));
+        self.vram_dma.block_bytes_left -= 1;
+    }
+
+    /// Handle write to HDMA1-HDMA4
+    pub fn set_vram_dma_address(&mut self, address: u16, value: u8) {
+        let dma = &mut self.vram_dma;
+        let value = value as u16;
+
+        match address {
+            RegisterHDMA1::ADDRESS => dma.source = (dma.source & 0x00FF) | value << 8,
+            RegisterHDMA2::ADDRESS => dma.source = (dma.source & 0xFF00) | (value & 0xF0),
+            RegisterHDMA3::ADDRESS => {
+                dma.destination = vram_dma_destination((dma.destination & 0x00FF) | value << 8)
+            }
+            RegisterHDMA4::ADDRESS => dma.destination = (dma.destination & 0xFF00) | (value & 0xF0),
+            _ => unreachable!("not a VRAM DMA address register: {address:#06X}"),
+        }
+    }
+}
+
+/// Map `address` into VRAM, upper 3 bits are ignored
+fn vram_dma_destination(address: u16) -> u16 {
+    MEMORY_RANGE_VRAM.start as u16 | (address & 0x1FFF)
+}
@@ -0,0 +1,134 @@
+//! Helpers shared by the integration tests, not every test uses all of them
+#![allow(dead_code)]
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+
+use emulator::*;
+
+/// Opcodes which lock the CPU instead of executing an instruction
+pub const ILLEGAL_OPCODES: [u8; 11] = [
+    0xD3, 0xDB, 0xDD, 0xE3, 0xE4, 0xEB, 0xEC, 0xED, 0xF4, 0xFC, 0xFD,
+];
+
+/// Address where `emulator_with_program` places the program
+pub const PROGRAM_START: u16 = MEMORY_RANGE_WORK_RAM_0.start as u16;
+/// Stack pointer set by `emulator_with_program`
+pub const PROGRAM_STACK_POINTER: u16 = 0xDFFE;
+
+/// Emulator with `program` in the work RAM, ready to execute its first
+/// instruction. Interrupts are disabled and the stack is in the work RAM.
+pub fn emulator_with_program(program: &[u8]) -> Emulator {
+    let mut emulator = Emulator::default();
+
+    for (i, byte) in program.iter().enumerate() {
+        emulator.set(PROGRAM_START + i as u16, *byte);
+    }
+
+    emulator.cpu.program_counter = PROGRAM_START.into();
+    emulator.cpu.stack_pointer = PROGRAM_STACK_POINTER.into();
+    emulator.reg_mut::<RegisterIE>().0 = 0;
+    emulator.reg_mut::<RegisterIF>().0 = 0;
+
+    // Skip first NOP from initial state of the instruction register (IR)
+    emulator.handle_next_instruction();
+
+    emulator
+}
+
+pub const WHITE: ScreenPixel = DMG_SHADES[0];
+pub const LIGHT: ScreenPixel = DMG_SHADES[1];
+pub const DARK: ScreenPixel = DMG_SHADES[2];
+pub const BLACK: ScreenPixel = DMG_SHADES[3];
+
+/// Solid tile of color index 3
+pub const BLACK_TILE: u8 = 1;
+
+/// Place object `oam_index` at the screen position
+pub fn set_object(
+    emulator: &mut Emulator,
+    oam_index: usize,
+    x: usize,
+    y: usize,
+    tile_index: u8,
+    attributes: u8,
+) {
+    let entry = &mut emulator.oam[oam_index * OBJECT_ENTRY_SIZE..][..OBJECT_ENTRY_SIZE];
+    entry.copy_from_slice(&[
+        (y + OBJECT_Y_OFFSET) as u8,
+        (x + OBJECT_X_OFFSET) as u8,
+        tile_index,
+        attributes,
+    ]);
+}
+
+/// Run until the next frame is handed off to `screen`
+pub fn run_frame(emulator: &mut Emulator) {
+    let frame_count = emulator.frame_count;
+    emulator.run_until(|emulator| emulator.frame_count > frame_count);
+}
+
+pub fn run_until_line(emulator: &mut Emulator, ly: u8, mode: PpuMode) {
+    emulator.run_until(|emulator| {
+        emulator.reg::<RegisterLY>().0 == ly
+            && emulator.reg::<RegisterSTAT>().get_ppu_mode() == mode
+    });
+}
+
+/// Pixels of the columns `x` in the line `y`
+pub fn row(screen: &Screen, x: std::ops::Range<usize>, y: usize) -> Vec<ScreenPixel> {
+    x.map(|x| screen.get_pixel(x, y)).collect()
+}
+
+/// Emulator with a banked cartridge, first two bytes of every ROM bank are
+/// its number
+pub fn banked_cartridge(
+    cartridge_type: CartridgeType,
+    rom_size_code: u8,
+    ram_size_code: u8,
+) -> Emulator {
+    let rom_size = 0x8000 << rom_size_code;
+    let mut data = vec![0; rom_size];
+
+    for (bank, chunk) in data.chunks_mut(ROM_BANK_SIZE).enumerate() {
+        chunk[..2].copy_from_slice(&(bank as u16).to_le_bytes());
+    }
+    data[ROM_ADDRESS_CARTRIDGE_TYPE] = cartridge_type.code();
+    data[ROM_ADDRESS_ROM_SIZE] = rom_size_code;
+    data[ROM_ADDRESS_RAM_SIZE] = ram_size_code;
+
+    Emulator::from_rom(data)
+}
+
+/// Clock controlled by the test, clones share the time
+#[derive(Debug, Default, Clone)]
+pub struct FakeClock(Arc<AtomicU64>);
+
+impl FakeClock {
+    pub fn advance(&self, seconds: u64) {
+        self.0.fetch_add(seconds, Ordering::Relaxed);
+    }
+}
+
+impl RtcClock for FakeClock {
+    fn now(&self) -> u64 {
+        self.0.load(Ordering::Relaxed)
+    }
+}
+
+pub const HELLO_ROM: &[u8] = include_bytes!("../../../../test-roms/hello.gb");
+
+/// Copy of the test ROM with updated header bytes and fixed checksums
+pub fn rom_with_header(changes: &[(usize, u8)]) -> Vec<u8> {
+    let mut data = HELLO_ROM.to_vec();
+
+    for &(address, value) in changes {
+        data[address] = value;
+    }
+
+    data[ROM_ADDRESS_HEADER_CHECKSUM] = compute_header_checksum(&data);
+    let global_checksum = compute_global_checksum(&data).to_be_bytes();
+    data[ROM_RANGE_GLOBAL_CHECKSUM].copy_from_slice(&global_checksum);
+
+    data
+}
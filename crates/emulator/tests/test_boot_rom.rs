@@ -0,0 +1,139 @@
+use emulator::*;
+
+/// Cartridge with $AA at $0000 and NOPs at the entry point
+fn cartridge() -> Rom {
+    let mut rom = Rom::new();
+    rom.data[0x0000] = 0xAA;
+    rom.data[0x0150] = 0xBB;
+    rom
+}
+
+/// DMG boot ROM which sets SP and A, then unmaps itself at the end of the
+/// image like the real one does.
+fn dmg_boot_rom() -> BootRom {
+    let mut data = vec![0; BOOT_ROM_SIZE_DMG];
+    // LD SP, $FFFE; LD A, $42
+    data[..5].copy_from_slice(&[0x31, 0xFE, 0xFF, 0x3E, 0x42]);
+    // LD A, $01; LDH [$50], A
+    data[0xFC..].copy_from_slice(&[0x3E, 0x01, 0xE0, 0x50]);
+
+    BootRom::new(data).unwrap()
+}
+
+fn dmg_emulator() -> Emulator {
+    Emulator::from_rom_with_model(cartridge(), EmulatorModel::Dmg)
+        .with_boot_rom(dmg_boot_rom())
+        .unwrap()
+}
+
+#[test]
+fn test_boot_rom_hand_off() {
+    let mut emulator = dmg_emulator();
+
+    assert!(emulator.is_boot_rom_mapped());
+    assert_eq!(emulator.cpu.program_counter.0, 0x0000);
+    assert_eq!(emulator.get(0x0000), 0x31);
+    assert!(!emulator.reg::<RegisterLCDC>().get_lcd_and_ppu_enable());
+
+    emulator.run_until(|emulator| !emulator.is_boot_rom_mapped());
+
+    assert_eq!(emulator.cpu.stack_pointer.0, 0xFFFE);
+    assert_eq!(emulator.cpu.accumulator_and_flags.high(), 0x01);
+    // cartridge is visible after the hand-off
+    assert_eq!(emulator.get(0x0000), 0xAA);
+    assert_eq!(emulator.cpu.opcode_address, 0x0100);
+}
+
+#[test]
+fn test_boot_rom_unmap() {
+    let mut emulator = dmg_emulator();
+
+    // zero doesn't unmap the boot ROM
+    emulator.set(RegisterBANK::ADDRESS, 0x00);
+    assert!(emulator.is_boot_rom_mapped());
+
+    emulator.set(RegisterBANK::ADDRESS, 0x11);
+    assert!(!emulator.is_boot_rom_mapped());
+    assert_eq!(emulator.get(0x0000), 0xAA);
+    assert_eq!(emulator.get(RegisterBANK::ADDRESS), 0xFF);
+}
+
+#[test]
+fn test_boot_rom_cgb_mapping() {
+    let mut data = vec![0x11; BOOT_ROM_SIZE_CGB];
+    data[0x0150] = 0x22;
+    let boot_rom = BootRom::new(data).unwrap();
+    assert!(boot_rom.is_cgb());
+
+    let emulator = Emulator::from_rom(cartridge())
+        .with_boot_rom(boot_rom)
+        .unwrap();
+
+    assert_eq!(emulator.get(0x0000), 0x11);
+    // cartridge header is visible between the boot ROM parts
+    assert_eq!(emulator.get(0x0150), 0xBB);
+    assert_eq!(emulator.get(0x0200), 0x11);
+    assert_eq!(emulator.get(0x08FF), 0x11);
+    assert_eq!(emulator.get(0x0900), 0x00);
+}
+
+#[test]
+fn test_boot_rom_invalid_size() {
+    assert_eq!(
+        BootRom::new(vec![0; 0x200]),
+        Err(BootRomError::InvalidSize(0x200))
+    );
+    assert!(!dmg_boot_rom().is_cgb());
+}
+
+#[test]
+fn test_boot_rom_power_up_registers() {
+    let emulator = dmg_emulator();
+
+    // post-boot values are not left behind
+    assert_eq!(emulator.get(RegisterDIV::ADDRESS), 0x00);
+    assert_eq!(emulator.get(RegisterIF::ADDRESS), 0xE0);
+    assert_eq!(emulator.get(RegisterSTAT::ADDRESS), 0x80);
+    assert_eq!(emulator.get(RegisterBGP::ADDRESS), 0x00);
+    assert_eq!(emulator.get(RegisterTAC::ADDRESS), 0xF8);
+    // NR52
+    assert_eq!(emulator.get(0xFF26), 0x70);
+}
+
+#[test]
+fn test_boot_rom_model_mismatch() {
+    let result =
+        Emulator::from_rom_with_model(cartridge(), EmulatorModel::Cgb).set_boot_rom(dmg_boot_rom());
+    assert_eq!(result, Err(BootRomError::ModelMismatch(EmulatorModel::Cgb)));
+
+    let cgb_boot_rom = BootRom::new(vec![0; BOOT_ROM_SIZE_CGB]).unwrap();
+    let result =
+        Emulator::from_rom_with_model(cartridge(), EmulatorModel::Sgb).set_boot_rom(cgb_boot_rom);
+    assert_eq!(result, Err(BootRomError::ModelMismatch(EmulatorModel::Sgb)));
+}
+
+#[test]
+fn test_boot_rom_cgb_mode() {
+    let boot_rom = BootRom::new(vec![0; BOOT_ROM_SIZE_CGB]).unwrap();
+
+    // CGB boot ROM runs in CGB mode with a DMG cartridge too
+    let mut emulator = Emulator::from_rom_with_model(cartridge(), EmulatorModel::Cgb)
+        .with_boot_rom(boot_rom.clone())
+        .unwrap();
+    assert!(emulator.cgb_mode);
+    emulator.set(RegisterSVBK::ADDRESS, 0x02);
+    assert_eq!(emulator.reg::<RegisterSVBK>().bank(), 2);
+
+    // DMG compatibility mode after the hand-off
+    emulator.set(RegisterBANK::ADDRESS, 0x11);
+    assert!(!emulator.cgb_mode);
+    assert_eq!(emulator.get(RegisterSVBK::ADDRESS), 0xFF);
+
+    let mut rom = cartridge();
+    rom.data[ROM_ADDRESS_CGB_FLAG] = 0x80;
+    let mut emulator = Emulator::from_rom_with_model(rom, EmulatorModel::Cgb)
+        .with_boot_rom(boot_rom)
+        .unwrap();
+    emulator.set(RegisterBANK::ADDRESS, 0x11);
+    assert!(emulator.cgb_mode);
+}
@@ -0,0 +1,192 @@
+use emulator::*;
+
+mod common;
+use common::*;
+
+/// Emulator with `program` which records the bus activity
+fn emulator_with_logged_program(program: &[u8]) -> Emulator {
+    let mut emulator = emulator_with_program(program);
+    emulator.bus_log = Some(vec![]);
+
+    emulator
+}
+
+fn read(address: u16, value: u8) -> BusActivity {
+    BusActivity::Read { address, value }
+}
+
+fn write(address: u16, value: u8) -> BusActivity {
+    BusActivity::Write { address, value }
+}
+
+#[test]
+fn test_bus_activity_ld_at_hl_n8() {
+    // LD [HL], $42; NOP
+    let mut emulator = emulator_with_logged_program(&[0x36, 0x42, 0x00]);
+    emulator.cpu.register_hl.set(0xD000);
+
+    let cycles = emulator.cycles;
+    emulator.handle_next_instruction();
+
+    assert_eq!(emulator.cycles, cycles + 3);
+    assert_eq!(
+        emulator.bus_log.unwrap(),
+        [
+            read(PROGRAM_START + 1, 0x42),
+            write(0xD000, 0x42),
+            read(PROGRAM_START + 2, 0x00),
+        ]
+    );
+}
+
+#[test]
+fn test_bus_activity_push() {
+    // PUSH BC; NOP
+    let mut emulator = emulator_with_logged_program(&[0xC5, 0x00]);
+    emulator.cpu.register_bc.set(0x1234);
+
+    emulator.handle_next_instruction();
+
+    assert_eq!(
+        emulator.bus_log.unwrap(),
+        [
+            BusActivity::Idle,
+            write(0xDFFD, 0x12),
+            write(0xDFFC, 0x34),
+            read(PROGRAM_START + 1, 0x00),
+        ]
+    );
+}
+
+#[test]
+fn test_bus_activity_call_and_ret() {
+    // CALL $C004; NOP; RET
+    let mut emulator = emulator_with_logged_program(&[0xCD, 0x04, 0xC0, 0x00, 0xC9]);
+
+    emulator.handle_next_instruction();
+    emulator.handle_next_instruction();
+
+    assert_eq!(
+        emulator.bus_log.unwrap(),
+        [
+            // CALL
+            read(PROGRAM_START + 1, 0x04),
+            read(PROGRAM_START + 2, 0xC0),
+            BusActivity::Idle,
+            write(0xDFFD, 0xC0),
+            write(0xDFFC, 0x03),
+            read(PROGRAM_START + 4, 0xC9),
+            // RET
+            read(0xDFFC, 0x03),
+            read(0xDFFD, 0xC0),
+            BusActivity::Idle,
+            read(PROGRAM_START + 3, 0x00),
+        ]
+    );
+}
+
+#[test]
+fn test_bus_activity_ret_cc() {
+    // RET NZ; RET Z; NOP
+    let mut emulator = emulator_with_logged_program(&[0xC0, 0xC8, 0x00]);
+    emulator.cpu.accumulator_and_flags.set_low(0);
+    emulator.cpu.stack_pointer = 0xDFFC.into();
+    emulator.set(0xDFFC, 0x01);
+    emulator.set(0xDFFD, 0xC0);
+
+    // taken, returns to RET Z
+    emulator.handle_next_instruction();
+    // not taken
+    emulator.handle_next_instruction();
+
+    assert_eq!(
+        emulator.bus_log.unwrap(),
+        [
+            // RET NZ
+            BusActivity::Idle,
+            read(0xDFFC, 0x01),
+            read(0xDFFD, 0xC0),
+            BusActivity::Idle,
+            read(PROGRAM_START + 1, 0xC8),
+            // RET Z
+            BusActivity::Idle,
+            read(PROGRAM_START + 2, 0x00),
+        ]
+    );
+}
+
+#[test]
+fn test_bus_activity_inc_at_hl() {
+    // INC [HL]; NOP
+    let mut emulator = emulator_with_logged_program(&[0x34, 0x00]);
+    emulator.cpu.register_hl.set(0xD000);
+    emulator.set(0xD000, 0x41);
+
+    emulator.handle_next_instruction();
+
+    assert_eq!(
+        emulator.bus_log.unwrap(),
+        [
+            read(0xD000, 0x41),
+            write(0xD000, 0x42),
+            read(PROGRAM_START + 1, 0x00),
+        ]
+    );
+}
+
+#[test]
+fn test_bus_activity_interrupt_dispatch() {
+    // NOP; NOP
+    let mut emulator = emulator_with_logged_program(&[0x00, 0x00]);
+    emulator.cpu.ime_flag = true;
+    emulator.reg_mut::<RegisterIE>().set_timer(true);
+    emulator.request_interrupt(Interrupt::Timer);
+
+    emulator.handle_next_instruction();
+
+    let handler_opcode = emulator.get(TIMER_INTERRUPT);
+    assert_eq!(
+        emulator.bus_log.unwrap(),
+        [
+            // opcode at PC is read and discarded
+            read(PROGRAM_START + 1, 0x00),
+            BusActivity::Idle,
+            write(0xDFFD, 0xC0),
+            write(0xDFFC, 0x01),
+            read(TIMER_INTERRUPT, handler_opcode),
+        ]
+    );
+}
+
+/// Every instruction spends each of its M-cycles on the bus, otherwise the
+/// debug assertion in [`Cpu::handle_instruction`] fails.
+#[test]
+fn test_bus_activity_matches_instruction_duration() {
+    // HALT and STOP suspend the CPU
+    const SUSPENDING_OPCODES: [u8; 2] = [0x76, 0x10];
+
+    let programs = (u8::MIN..=u8::MAX)
+        .filter(|opcode| !ILLEGAL_OPCODES.contains(opcode))
+        .filter(|opcode| !SUSPENDING_OPCODES.contains(opcode))
+        .map(|opcode| vec![opcode, 0x00, 0x00, 0x00])
+        .chain((u8::MIN..=u8::MAX).map(|opcode| vec![0xCB, opcode, 0x00]));
+
+    for program in programs {
+        for flags in [0x00, 0xF0] {
+            let mut emulator = emulator_with_logged_program(&program);
+            emulator.cpu.accumulator_and_flags.set_low(flags);
+            emulator.cpu.register_bc.set(0xD000);
+            emulator.cpu.register_de.set(0xD100);
+            emulator.cpu.register_hl.set(0xD200);
+
+            let cycles = emulator.cycles;
+            let instruction = emulator.handle_next_instruction();
+
+            assert_eq!(
+                emulator.bus_log.unwrap().len(),
+                emulator.cycles - cycles,
+                "{instruction:?} with flags {flags:02X}",
+            );
+        }
+    }
+}
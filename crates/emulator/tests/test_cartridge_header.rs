@@ -0,0 +1,136 @@
+use emulator::*;
+
+mod common;
+use common::*;
+
+#[test]
+fn test_parse_header() {
+    let header = Rom::from(HELLO_ROM).header().unwrap();
+
+    assert_eq!(
+        header,
+        CartridgeHeader {
+            title: String::new(),
+            cgb_support: CgbSupport::None,
+            sgb_support: false,
+            cartridge_type: CartridgeType::RomOnly,
+            rom_size: 0x8000,
+            ram_size: 0,
+            destination: Destination::Japan,
+            licensee: Licensee::Old(0x00),
+            version: 1,
+            header_checksum: 0xE6,
+            global_checksum: 0x7BBE,
+        }
+    );
+    assert_eq!(header.rom_banks(), 2);
+    assert_eq!(header.ram_banks(), 0);
+}
+
+#[test]
+fn test_parse_header_fields() {
+    let mut changes = vec![
+        (ROM_ADDRESS_SGB_FLAG, 0x03),
+        (ROM_ADDRESS_CARTRIDGE_TYPE, 0x13),
+        (ROM_ADDRESS_RAM_SIZE, 0x03),
+        (ROM_ADDRESS_DESTINATION_CODE, 0x01),
+        (ROM_ADDRESS_OLD_LICENSEE_CODE, OLD_LICENSEE_CODE_USE_NEW),
+        (ROM_RANGE_NEW_LICENSEE_CODE.start, b'0'),
+        (ROM_RANGE_NEW_LICENSEE_CODE.start + 1, b'1'),
+        (ROM_ADDRESS_MASK_ROM_VERSION_NUMBER, 0x02),
+    ];
+    // title is cut at the manufacturer code in CGB cartridges
+    for (i, byte) in b"POKEMON GOLDAAUE".iter().enumerate() {
+        changes.push((ROM_RANGE_TITLE.start + i, *byte));
+    }
+    changes.push((ROM_ADDRESS_CGB_FLAG, 0x80));
+
+    let header = CartridgeHeader::parse(&rom_with_header(&changes)).unwrap();
+
+    assert_eq!(header.title, "POKEMON GOL");
+    assert_eq!(header.cgb_support, CgbSupport::Supported);
+    assert!(header.sgb_support);
+    assert_eq!(header.cartridge_type, CartridgeType::Mbc3RamBattery);
+    assert!(header.cartridge_type.has_ram());
+    assert!(header.cartridge_type.has_battery());
+    assert!(!header.cartridge_type.has_timer());
+    assert_eq!(header.ram_size, 0x8000);
+    assert_eq!(header.ram_banks(), 4);
+    assert_eq!(header.destination, Destination::Overseas);
+    assert_eq!(header.licensee, Licensee::New(*b"01"));
+    assert_eq!(
+        header.licensee.name(),
+        Some("Nintendo Research & Development 1")
+    );
+    assert_eq!(header.version, 2);
+}
+
+#[test]
+fn test_parse_header_title() {
+    let mut changes = vec![];
+    for (i, byte) in b"TETRIS".iter().enumerate() {
+        changes.push((ROM_RANGE_TITLE.start + i, *byte));
+    }
+
+    let header = CartridgeHeader::parse(&rom_with_header(&changes)).unwrap();
+    assert_eq!(header.title, "TETRIS");
+    assert_eq!(header.licensee.name(), Some("None"));
+}
+
+#[test]
+fn test_parse_header_errors() {
+    assert_eq!(
+        CartridgeHeader::parse(&HELLO_ROM[..0x4000]),
+        Err(CartridgeHeaderError::TooSmall(0x4000))
+    );
+
+    assert_eq!(
+        CartridgeHeader::parse(&rom_with_header(&[(ROM_ADDRESS_CARTRIDGE_TYPE, 0x04)])),
+        Err(CartridgeHeaderError::UnknownCartridgeType(0x04))
+    );
+
+    assert_eq!(
+        CartridgeHeader::parse(&rom_with_header(&[(ROM_ADDRESS_ROM_SIZE, 0x09)])),
+        Err(CartridgeHeaderError::UnknownRomSize(0x09))
+    );
+
+    assert_eq!(
+        CartridgeHeader::parse(&rom_with_header(&[(ROM_ADDRESS_RAM_SIZE, 0x06)])),
+        Err(CartridgeHeaderError::UnknownRamSize(0x06))
+    );
+
+    assert_eq!(
+        CartridgeHeader::parse(&rom_with_header(&[(ROM_ADDRESS_ROM_SIZE, 0x01)])),
+        Err(CartridgeHeaderError::RomSizeMismatch {
+            declared: 0x10000,
+            actual: 0x8000,
+        })
+    );
+
+    assert_eq!(
+        CartridgeHeader::parse(&rom_with_header(&[(ROM_RANGE_LOGO.start, 0x00)])),
+        Err(CartridgeHeaderError::InvalidLogo)
+    );
+
+    let mut data = HELLO_ROM.to_vec();
+    data[ROM_ADDRESS_MASK_ROM_VERSION_NUMBER] = 2;
+    assert_eq!(
+        CartridgeHeader::parse(&data),
+        Err(CartridgeHeaderError::HeaderChecksumMismatch {
+            stored: 0xE6,
+            computed: 0xE5,
+        })
+    );
+    // header is still readable
+    assert_eq!(CartridgeHeader::decode(&data).unwrap().version, 2);
+
+    let mut data = HELLO_ROM.to_vec();
+    data[0x200] = data[0x200].wrapping_add(1);
+    assert_eq!(
+        CartridgeHeader::parse(&data),
+        Err(CartridgeHeaderError::GlobalChecksumMismatch {
+            stored: 0x7BBE,
+            computed: 0x7BBF,
+        })
+    );
+}
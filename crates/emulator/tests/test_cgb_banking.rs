@@ -0,0 +1,99 @@
+use emulator::*;
+
+fn cgb_emulator() -> Emulator {
+    let mut rom = Rom::new();
+    rom.data[ROM_ADDRESS_CGB_FLAG] = 0x80;
+
+    let mut emulator = Emulator::from_rom(rom);
+    // LCD is turned off so VRAM is always accessible
+    emulator.reg_mut::<RegisterLCDC>().0 = 0;
+    emulator
+}
+
+#[test]
+fn test_vram_banking() {
+    let mut emulator = cgb_emulator();
+    assert!(emulator.cgb_mode);
+    assert_eq!(emulator.get(RegisterVBK::ADDRESS), 0xFE);
+
+    emulator.set(0x8000, 0x11);
+    emulator.set(RegisterVBK::ADDRESS, 0x01);
+    assert_eq!(emulator.get(RegisterVBK::ADDRESS), 0xFF);
+    assert_eq!(emulator.vram_bank(), 1);
+    assert_eq!(emulator.get(0x8000), 0x00);
+
+    emulator.set(0x8000, 0x22);
+    emulator.set(RegisterVBK::ADDRESS, 0xFE);
+    assert_eq!(emulator.get(0x8000), 0x11);
+    assert_eq!(emulator.vram[1][0], 0x22);
+}
+
+#[test]
+fn test_work_ram_banking() {
+    let mut emulator = cgb_emulator();
+    assert_eq!(emulator.get(RegisterSVBK::ADDRESS), 0xF8);
+    assert_eq!(emulator.work_ram_bank(), 1);
+
+    for bank in 1..WORK_RAM_BANKS as u8 {
+        emulator.set(RegisterSVBK::ADDRESS, bank);
+        emulator.set(0xD000, bank);
+    }
+
+    // bank 0 selects bank 1
+    emulator.set(RegisterSVBK::ADDRESS, 0x00);
+    assert_eq!(emulator.get(0xD000), 0x01);
+
+    emulator.set(RegisterSVBK::ADDRESS, 0x0D);
+    assert_eq!(emulator.get(RegisterSVBK::ADDRESS), 0xFD);
+    assert_eq!(emulator.get(0xD000), 0x05);
+    // echo RAM follows the selected bank
+    assert_eq!(emulator.get(0xF000), 0x05);
+    emulator.set(0xF001, 0x55);
+    assert_eq!(emulator.work_ram[5][1], 0x55);
+
+    // bank 0 is fixed
+    emulator.set(0xC000, 0x42);
+    emulator.set(RegisterSVBK::ADDRESS, 0x07);
+    assert_eq!(emulator.get(0xC000), 0x42);
+    assert_eq!(emulator.get(0xE000), 0x42);
+}
+
+#[test]
+fn test_banking_dmg_mode() {
+    let mut emulator = Emulator::from_rom(Rom::new());
+    emulator.reg_mut::<RegisterLCDC>().0 = 0;
+    assert!(!emulator.cgb_mode);
+
+    emulator.set(RegisterVBK::ADDRESS, 0x01);
+    emulator.set(RegisterSVBK::ADDRESS, 0x03);
+    assert_eq!(emulator.get(RegisterVBK::ADDRESS), 0xFF);
+    assert_eq!(emulator.get(RegisterSVBK::ADDRESS), 0xFF);
+    assert_eq!(emulator.vram_bank(), 0);
+    assert_eq!(emulator.work_ram_bank(), 1);
+
+    emulator.set(0x8000, 0x11);
+    emulator.set(0xD000, 0x22);
+    assert_eq!(emulator.vram[0][0], 0x11);
+    assert_eq!(emulator.work_ram[1][0], 0x22);
+}
+
+#[test]
+fn test_banked_debugger_access() {
+    let mut emulator = cgb_emulator();
+    emulator.set(RegisterSVBK::ADDRESS, 0x03);
+    emulator.set(0xD123, 0x33);
+
+    assert_eq!(emulator.get_banked(0xD123, 3), Some(&0x33));
+    assert_eq!(emulator.get_banked(0xF123, 3), Some(&0x33));
+    assert_eq!(emulator.get_banked(0xD123, 2), Some(&0x00));
+
+    *emulator.get_banked_mut(0x9FFF, 1).unwrap() = 0x44;
+    assert_eq!(emulator.get(0x9FFF), 0x00);
+    emulator.set(RegisterVBK::ADDRESS, 0x01);
+    assert_eq!(emulator.get(0x9FFF), 0x44);
+
+    // not banked or bank doesn't exist
+    assert_eq!(emulator.get_banked(0xC000, 0), None);
+    assert_eq!(emulator.get_banked(0x8000, VRAM_BANKS), None);
+    assert_eq!(emulator.get_banked(0xD000, WORK_RAM_BANKS), None);
+}
@@ -0,0 +1,109 @@
+use emulator::*;
+
+/// Create CPU with given program loaded at $0000 of the flat RAM
+fn cpu_with_program(program: &[u8]) -> (Cpu, FlatBus) {
+    let mut cpu = Cpu::new();
+    let mut bus = FlatBus::new();
+
+    bus.memory[..program.len()].copy_from_slice(program);
+    cpu.program_counter = 0x0000.into();
+    cpu.stack_pointer = 0xFFFE.into();
+
+    // Skip first NOP from initial state of the instruction register (IR)
+    cpu.handle_next_instruction(&mut bus);
+
+    (cpu, bus)
+}
+
+#[test]
+fn test_flat_bus_program() {
+    // LD A, $42; LD [$8000], A; INC A; LD [$0000], A; HALT
+    let (mut cpu, mut bus) =
+        cpu_with_program(&[0x3E, 0x42, 0xEA, 0x00, 0x80, 0x3C, 0xEA, 0x00, 0x00, 0x76]);
+
+    while !cpu.is_suspended() {
+        cpu.handle_next_instruction(&mut bus);
+    }
+
+    assert_eq!(cpu.state, CpuState::Halted);
+    assert_eq!(cpu.accumulator_and_flags.high(), 0x43);
+    assert_eq!(bus.memory[0x8000], 0x42);
+    // no ROM or PPU, every address is writable
+    assert_eq!(bus.memory[0x0000], 0x43);
+    // NOP + LD + LD + INC + LD, HALT doesn't fetch the next opcode
+    assert_eq!(bus.cycles, 1 + 2 + 4 + 1 + 4);
+}
+
+#[test]
+fn test_flat_bus_call_ret() {
+    // CALL $0010; HALT; ...; $0010: LD B, $07; RET
+    let mut program = [0; 0x13];
+    program[..4].copy_from_slice(&[0xCD, 0x10, 0x00, 0x76]);
+    program[0x10..].copy_from_slice(&[0x06, 0x07, 0xC9]);
+    let (mut cpu, mut bus) = cpu_with_program(&program);
+    bus.bus_log = Some(vec![]);
+
+    while !cpu.is_suspended() {
+        cpu.handle_next_instruction(&mut bus);
+    }
+
+    assert_eq!(cpu.register_bc.high(), 0x07);
+    assert_eq!(cpu.stack_pointer.0, 0xFFFE);
+    // return address is pushed to the stack
+    assert_eq!(bus.memory[0xFFFC..0xFFFE], [0x03, 0x00]);
+
+    let bus_log = bus.bus_log.unwrap();
+    assert_eq!(
+        &bus_log[..6],
+        [
+            BusActivity::Read {
+                address: 0x0001,
+                value: 0x10
+            },
+            BusActivity::Read {
+                address: 0x0002,
+                value: 0x00
+            },
+            BusActivity::Idle,
+            BusActivity::Write {
+                address: 0xFFFD,
+                value: 0x00
+            },
+            BusActivity::Write {
+                address: 0xFFFC,
+                value: 0x03
+            },
+            BusActivity::Read {
+                address: 0x0010,
+                value: 0x06
+            },
+        ]
+    );
+}
+
+#[test]
+fn test_flat_bus_interrupt() {
+    // EI; NOP; NOP
+    let (mut cpu, mut bus) = cpu_with_program(&[0xFB, 0x00, 0x00]);
+    bus.write(RegisterIE::ADDRESS, INTERRUPTS_MASK);
+    bus.request_interrupt(Interrupt::VBlank);
+
+    // IME is set after the instruction following EI
+    for _ in 0..3 {
+        cpu.handle_next_instruction(&mut bus);
+    }
+
+    assert_eq!(cpu.program_counter.0, V_BLANK_INTERRUPT + 1);
+    assert!(!cpu.ime_flag);
+    assert_eq!(bus.pending_interrupts(), 0);
+}
+
+#[test]
+fn test_flat_bus_stop() {
+    // STOP
+    let (mut cpu, mut bus) = cpu_with_program(&[0x10, 0x00]);
+
+    cpu.handle_next_instruction(&mut bus);
+
+    assert_eq!(cpu.state, CpuState::Stopped);
+}
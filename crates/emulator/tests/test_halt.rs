@@ -0,0 +1,87 @@
+use emulator::*;
+
+mod common;
+use common::*;
+
+fn request_timer_interrupt(emulator: &mut Emulator) {
+    emulator.reg_mut::<RegisterIE>().set_timer(true);
+    emulator.request_interrupt(Interrupt::Timer);
+}
+
+#[test]
+fn test_halt_ime_set() {
+    // HALT, NOP
+    let mut emulator = emulator_with_program(&[0x76, 0x00]);
+    emulator.cpu.ime_flag = true;
+
+    let instruction = emulator.handle_next_instruction();
+    assert_eq!(instruction, InstructionHALT.into());
+    assert_eq!(emulator.cpu.state, CpuState::Halted);
+    assert_eq!(emulator.cpu.program_counter.0, PROGRAM_START + 1);
+
+    // CPU stays halted while time goes on
+    let cycles = emulator.cycles;
+    for _ in 0..10 {
+        emulator.handle_next_instruction();
+    }
+    assert_eq!(emulator.cpu.state, CpuState::Halted);
+    assert_eq!(emulator.cycles, cycles + 10);
+    assert_eq!(emulator.cpu.program_counter.0, PROGRAM_START + 1);
+
+    request_timer_interrupt(&mut emulator);
+    emulator.handle_next_instruction();
+
+    // Interrupt handler is called and returns to the instruction after HALT
+    assert_eq!(emulator.cpu.state, CpuState::Running);
+    assert!(!emulator.cpu.ime_flag);
+    assert_eq!(emulator.cpu.program_counter.0, TIMER_INTERRUPT + 1);
+    assert_eq!(
+        emulator.get_u16(emulator.cpu.stack_pointer.0),
+        PROGRAM_START + 1
+    );
+}
+
+#[test]
+fn test_halt_ime_not_set_none_pending() {
+    // HALT, NOP
+    let mut emulator = emulator_with_program(&[0x76, 0x00]);
+
+    emulator.handle_next_instruction();
+    assert_eq!(emulator.cpu.state, CpuState::Halted);
+
+    emulator.handle_next_instruction();
+    assert_eq!(emulator.cpu.state, CpuState::Halted);
+
+    request_timer_interrupt(&mut emulator);
+    emulator.handle_next_instruction();
+
+    // Execution resumes after HALT without calling the handler
+    assert_eq!(emulator.cpu.state, CpuState::Running);
+    assert_eq!(emulator.cpu.instruction_register, 0x00);
+    assert_eq!(emulator.cpu.program_counter.0, PROGRAM_START + 2);
+    assert_eq!(emulator.cpu.stack_pointer.0, PROGRAM_STACK_POINTER);
+}
+
+#[test]
+fn test_halt_bug() {
+    // HALT, INC A, NOP
+    let mut emulator = emulator_with_program(&[0x76, 0x3C, 0x00]);
+    emulator.cpu.accumulator_and_flags.set_high(0);
+    request_timer_interrupt(&mut emulator);
+
+    emulator.handle_next_instruction();
+    assert_eq!(emulator.cpu.state, CpuState::Running);
+    // PC is not incremented after fetching the byte after HALT
+    assert_eq!(emulator.cpu.instruction_register, 0x3C);
+    assert_eq!(emulator.cpu.program_counter.0, PROGRAM_START + 1);
+
+    emulator.handle_next_instruction();
+    assert_eq!(emulator.cpu.instruction_register, 0x3C);
+    assert_eq!(emulator.cpu.program_counter.0, PROGRAM_START + 2);
+
+    emulator.handle_next_instruction();
+    assert_eq!(emulator.cpu.instruction_register, 0x00);
+
+    // INC A was executed twice
+    assert_eq!(emulator.cpu.accumulator_and_flags.high(), 2);
+}
@@ -0,0 +1,69 @@
+use std::cell::RefCell;
+use std::rc::Rc;
+
+use emulator::*;
+
+mod common;
+use common::*;
+
+/// Records opcodes that locked the CPU
+struct LockDebugger(Rc<RefCell<Vec<u8>>>);
+
+impl EmulatorDebugger for LockDebugger {
+    fn on_after_instruction(&mut self, _: &Emulator, _: u8, _: Instruction) {}
+
+    fn on_cpu_locked(&mut self, _: &Emulator, opcode: u8) {
+        self.0.borrow_mut().push(opcode);
+    }
+}
+
+#[test]
+fn test_illegal_opcode_locks_cpu() {
+    // INC A, illegal, INC A
+    let mut emulator = emulator_with_program(&[0x3C, 0xD3, 0x3C]);
+    emulator.cpu.accumulator_and_flags.set_high(0);
+
+    let locked_opcodes = Rc::new(RefCell::new(vec![]));
+    emulator.set_debugger(Box::new(LockDebugger(locked_opcodes.clone())));
+
+    emulator.handle_next_instruction();
+    assert_eq!(emulator.cpu.state, CpuState::Running);
+
+    emulator.handle_next_instruction();
+    assert_eq!(emulator.cpu.state, CpuState::Locked);
+    assert_eq!(*locked_opcodes.borrow(), [0xD3]);
+
+    // interrupts are ignored
+    emulator.cpu.ime_flag = true;
+    emulator.reg_mut::<RegisterIE>().set_timer(true);
+    emulator.request_interrupt(Interrupt::Timer);
+
+    // time goes on, but nothing is executed
+    let cycles = emulator.cycles;
+    let dots = emulator.scanline_progress;
+    for _ in 0..10 {
+        assert_eq!(emulator.handle_next_instruction(), InstructionNOP.into());
+    }
+    assert_eq!(emulator.cpu.state, CpuState::Locked);
+    assert_eq!(emulator.cycles, cycles + 10);
+    assert_ne!(emulator.scanline_progress, dots);
+    assert_eq!(emulator.cpu.program_counter.0, PROGRAM_START + 2);
+    assert_eq!(emulator.cpu.accumulator_and_flags.high(), 1);
+    assert_eq!(*locked_opcodes.borrow(), [0xD3]);
+}
+
+#[test]
+fn test_locked_cpu_does_not_access_bus() {
+    // illegal
+    let mut emulator = emulator_with_program(&[0xFD]);
+    emulator.bus_log = Some(vec![]);
+
+    emulator.handle_next_instruction();
+    emulator.handle_next_instruction();
+
+    assert_eq!(emulator.cpu.state, CpuState::Locked);
+    assert_eq!(
+        emulator.bus_log.unwrap(),
+        [BusActivity::Idle, BusActivity::Idle]
+    );
+}
@@ -0,0 +1,83 @@
+use emulator::*;
+
+mod common;
+use common::*;
+
+/// Decode instruction from the given bytes, as CPU would do it
+fn decode(bytes: &[u8]) -> Instruction {
+    let mut emulator = Emulator::default();
+    let address = MEMORY_RANGE_WORK_RAM_0.start as u16;
+
+    for (i, byte) in bytes.iter().enumerate() {
+        emulator.set(address + i as u16, *byte);
+    }
+
+    emulator.cpu.instruction_register = bytes[0];
+    emulator.cpu.program_counter = (address + 1).into();
+
+    emulator
+        .with_cpu(Instruction::read)
+        .expect("failed to decode instruction")
+}
+
+#[test]
+fn test_encode_round_trip() {
+    let programs = (u8::MIN..=u8::MAX)
+        .filter(|opcode| !ILLEGAL_OPCODES.contains(opcode))
+        .map(|opcode| match opcode {
+            // second byte of STOP is ignored, so it's always encoded as $00
+            0x10 => vec![0x10, 0x00],
+            _ => vec![opcode, 0xFE, 0xC0],
+        })
+        .chain((u8::MIN..=u8::MAX).map(|opcode| vec![0xCB, opcode]));
+
+    for program in programs {
+        let instruction = decode(&program);
+        let bytes = instruction.encode();
+
+        assert_eq!(
+            bytes,
+            program[..bytes.len()],
+            "{instruction} ({instruction:?})"
+        );
+        assert_eq!(decode(&bytes), instruction);
+    }
+}
+
+#[test]
+fn test_display() {
+    let cases: &[(&[u8], &str)] = &[
+        (&[0x00], "nop"),
+        (&[0x21, 0x00, 0xC0], "ld hl, $C000"),
+        (&[0x36, 0x0A], "ld [hl], $0A"),
+        (&[0x22], "ld [hl+], a"),
+        (&[0x3A], "ld a, [hl-]"),
+        (&[0x12], "ld [de], a"),
+        (&[0xFA, 0x34, 0x12], "ld a, [$1234]"),
+        (&[0x08, 0x00, 0xD0], "ld [$D000], sp"),
+        (&[0xF8, 0xFE], "ld hl, sp - 2"),
+        (&[0xF8, 0x05], "ld hl, sp + 5"),
+        (&[0xE0, 0x40], "ldh [$FF40], a"),
+        (&[0xF2], "ldh a, [c]"),
+        (&[0x80], "add a, b"),
+        (&[0xE8, 0xFF], "add sp, -1"),
+        (&[0x39], "add hl, sp"),
+        (&[0xFE, 0x90], "cp a, $90"),
+        (&[0x18, 0xFE], "jr @+0"),
+        (&[0x20, 0x05], "jr nz, @+7"),
+        (&[0xCA, 0x50, 0x01], "jp z, $0150"),
+        (&[0xE9], "jp hl"),
+        (&[0xDC, 0x00, 0x40], "call c, $4000"),
+        (&[0xD0], "ret nc"),
+        (&[0xFF], "rst $38"),
+        (&[0xF5], "push af"),
+        (&[0x10, 0x00], "stop"),
+        (&[0xCB, 0x7E], "bit 7, [hl]"),
+        (&[0xCB, 0x37], "swap a"),
+        (&[0xCB, 0x80], "res 0, b"),
+    ];
+
+    for (bytes, expected) in cases {
+        assert_eq!(decode(bytes).to_string(), *expected);
+    }
+}
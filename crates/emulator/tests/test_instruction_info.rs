@@ -0,0 +1,154 @@
+use emulator::*;
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::fs::read_to_string;
+
+mod common;
+use common::*;
+
+/// Opcodes table from https://gbdev.io/gb-opcodes/Opcodes.json
+const OPCODES_TABLE_PATH: &str = "../../gb-opcodes/Opcodes.json";
+
+/// Encoded forms of all the instructions
+fn all_programs() -> impl Iterator<Item = Vec<u8>> {
+    (u8::MIN..=u8::MAX)
+        .filter(|opcode| !ILLEGAL_OPCODES.contains(opcode) && *opcode != 0xCB)
+        .map(|opcode| vec![opcode, 0x00, 0xC0])
+        .chain((u8::MIN..=u8::MAX).map(|opcode| vec![0xCB, opcode]))
+}
+
+/// Emulator with `program`, 16-bit registers point to the work RAM
+fn emulator_with_instruction(program: &[u8]) -> Emulator {
+    let mut emulator = emulator_with_program(program);
+    emulator.cpu.register_bc.set(0xD000);
+    emulator.cpu.register_de.set(0xD100);
+    emulator.cpu.register_hl.set(0xD200);
+
+    emulator
+}
+
+#[test]
+fn test_instruction_info_length() {
+    for program in all_programs() {
+        let emulator = emulator_with_instruction(&program);
+        let (instruction, length) =
+            Instruction::decode_at(&emulator, MEMORY_RANGE_WORK_RAM_0.start as u16).unwrap();
+
+        assert_eq!(instruction.info().length as u16, length, "{instruction}");
+        assert_eq!(instruction.encode().len(), length as usize, "{instruction}");
+    }
+}
+
+#[test]
+fn test_instruction_info_cycles() {
+    for program in all_programs() {
+        let mut durations = vec![];
+
+        // all conditions are met with one of the flags values
+        for flags in [0x00, 0xF0] {
+            let mut emulator = emulator_with_instruction(&program);
+            emulator.cpu.accumulator_and_flags.set_low(flags);
+
+            let cycles = emulator.cycles;
+            let instruction = emulator.handle_next_instruction();
+            let info = instruction.info();
+
+            // HALT and STOP suspend the CPU right after the fetch
+            if matches!(instruction, Instruction::HALT(_) | Instruction::STOP(_)) {
+                continue;
+            }
+
+            durations.push(emulator.cycles - cycles);
+
+            if durations.len() == 2 {
+                let mut expected =
+                    vec![info.cycles, info.cycles_branch_taken.unwrap_or(info.cycles)];
+                expected.sort();
+                durations.sort();
+
+                assert_eq!(durations, expected, "{instruction}");
+            }
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct OpcodesTable {
+    unprefixed: HashMap<String, OpcodeData>,
+    cbprefixed: HashMap<String, OpcodeData>,
+}
+
+#[derive(Deserialize)]
+struct OpcodeData {
+    mnemonic: String,
+    bytes: u8,
+    /// T-cycles, for conditional instructions: taken and not taken
+    cycles: Vec<usize>,
+    flags: HashMap<String, String>,
+}
+
+impl OpcodeData {
+    fn flag(&self, name: &str) -> FlagEffect {
+        match self.flags[name].as_str() {
+            "-" => FlagEffect::Unaffected,
+            "0" => FlagEffect::Reset,
+            "1" => FlagEffect::Set,
+            _ => FlagEffect::Affected,
+        }
+    }
+}
+
+#[test]
+#[ignore = "integration test"]
+fn test_instruction_info_opcodes_table() {
+    let file = read_to_string(OPCODES_TABLE_PATH)
+        .unwrap_or_else(|err| panic!("failed to read {OPCODES_TABLE_PATH}: {err:?}"));
+    let table: OpcodesTable = serde_json::from_str(&file)
+        .unwrap_or_else(|err| panic!("failed to parse {OPCODES_TABLE_PATH}: {err:?}"));
+
+    let entries = table
+        .unprefixed
+        .iter()
+        .map(|(opcode, data)| (vec![], opcode, data))
+        .chain(
+            table
+                .cbprefixed
+                .iter()
+                .map(|(opcode, data)| (vec![0xCB], opcode, data)),
+        );
+
+    for (prefix, opcode, data) in entries {
+        if data.mnemonic.starts_with("ILLEGAL") || data.mnemonic == "PREFIX" {
+            continue;
+        }
+
+        let opcode = u8::from_str_radix(opcode.trim_start_matches("0x"), 16).unwrap();
+        let mut program = prefix;
+        program.extend([opcode, 0x00, 0xC0]);
+
+        let emulator = emulator_with_instruction(&program);
+        let (instruction, _) =
+            Instruction::decode_at(&emulator, MEMORY_RANGE_WORK_RAM_0.start as u16).unwrap();
+        let info = instruction.info();
+
+        assert_eq!(info.length, data.bytes, "{instruction} length");
+
+        // HALT and STOP durations depend on the wake-up conditions
+        if !matches!(instruction, Instruction::HALT(_) | Instruction::STOP(_)) {
+            let cycles: Vec<usize> = data.cycles.iter().map(|cycles| cycles / 4).collect();
+            let expected = match info.cycles_branch_taken {
+                Some(taken) => vec![taken, info.cycles],
+                None => vec![info.cycles],
+            };
+            assert_eq!(cycles, expected, "{instruction} cycles");
+        }
+
+        let flags = FlagsEffect {
+            zero: data.flag("Z"),
+            subtract: data.flag("N"),
+            half_carry: data.flag("H"),
+            carry: data.flag("C"),
+        };
+        assert_eq!(info.flags, flags, "{instruction} flags");
+    }
+}
@@ -0,0 +1,109 @@
+use emulator::*;
+
+fn emulator_for_dispatch() -> Emulator {
+    let mut emulator = Emulator::default();
+    emulator.reg_mut::<RegisterIE>().0 = 0;
+    emulator.reg_mut::<RegisterIF>().0 = 0;
+    emulator.cpu.ime_flag = true;
+    emulator.cpu.program_counter = 0x1234.into();
+    emulator.cpu.stack_pointer = 0xDFFE.into();
+
+    emulator
+}
+
+#[test]
+fn test_interrupt_registers_mapping() {
+    let mut emulator = Emulator::default();
+
+    emulator.set(0xFFFF, 0b0000_0101);
+    assert!(emulator.reg::<RegisterIE>().get_v_blank());
+    assert!(emulator.reg::<RegisterIE>().get_timer());
+    assert_eq!(emulator.interrupt_enable_register, 0b0000_0101);
+
+    emulator.set(0xFF0F, 0b0000_0100);
+    assert!(emulator.reg::<RegisterIF>().get_timer());
+    // upper bits of IF always read as 1
+    assert_eq!(emulator.get(0xFF0F), 0b1110_0100);
+
+    // LCDC is not affected
+    assert_eq!(emulator.reg::<RegisterLCDC>().0, RegisterLCDC::default().0);
+}
+
+#[test]
+fn test_request_interrupt() {
+    let mut emulator = emulator_for_dispatch();
+
+    emulator.request_interrupt(Interrupt::Serial);
+    assert!(emulator.reg::<RegisterIF>().get_serial());
+    assert_eq!(emulator.pending_interrupts(), 0);
+
+    emulator.reg_mut::<RegisterIE>().set_serial(true);
+    assert_eq!(emulator.pending_interrupts(), RegisterIF::SERIAL);
+}
+
+#[test]
+fn test_interrupt_dispatch() {
+    let mut emulator = emulator_for_dispatch();
+    emulator.reg_mut::<RegisterIE>().0 = INTERRUPTS_MASK;
+    emulator.request_interrupt(Interrupt::Joypad);
+    emulator.request_interrupt(Interrupt::Timer);
+
+    let cycles = emulator.cycles;
+    let interrupt = emulator.with_cpu(|cpu, bus| cpu.process_interrupt(bus));
+
+    // Timer has higher priority than joypad
+    assert_eq!(interrupt, Some(Interrupt::Timer));
+    assert_eq!(emulator.cpu.program_counter.0, TIMER_INTERRUPT);
+    // the last cycle is the fetch of the handler opcode
+    assert_eq!(emulator.cycles, cycles + INTERRUPT_DISPATCH_DURATION - 1);
+    assert!(!emulator.cpu.ime_flag);
+
+    // IF is acknowledged, IE is untouched
+    assert!(!emulator.reg::<RegisterIF>().get_timer());
+    assert!(emulator.reg::<RegisterIF>().get_joypad());
+    assert_eq!(emulator.reg::<RegisterIE>().0, INTERRUPTS_MASK);
+
+    assert_eq!(emulator.cpu.stack_pointer.0, 0xDFFC);
+    assert_eq!(emulator.get_u16(0xDFFC), 0x1234);
+
+    // IME is cleared, so next interrupt is not dispatched
+    assert_eq!(
+        emulator.with_cpu(|cpu, bus| cpu.process_interrupt(bus)),
+        None
+    );
+}
+
+#[test]
+fn test_interrupt_dispatch_canceled_by_ie_write() {
+    let mut emulator = emulator_for_dispatch();
+    // high byte of PC is pushed to $FFFF (IE)
+    emulator.cpu.stack_pointer = 0x0000.into();
+    emulator.reg_mut::<RegisterIE>().set_timer(true);
+    emulator.request_interrupt(Interrupt::Timer);
+
+    let interrupt = emulator.with_cpu(|cpu, bus| cpu.process_interrupt(bus));
+
+    assert_eq!(interrupt, None);
+    assert_eq!(emulator.cpu.program_counter.0, 0x0000);
+    assert_eq!(emulator.reg::<RegisterIE>().0, 0x12);
+    // interrupt is not acknowledged
+    assert!(emulator.reg::<RegisterIF>().get_timer());
+}
+
+#[test]
+fn test_interrupt_dispatch_redirected_by_ie_write() {
+    let mut emulator = emulator_for_dispatch();
+    emulator.cpu.program_counter = 0x1034.into();
+    // high byte of PC is pushed to $FFFF (IE)
+    emulator.cpu.stack_pointer = 0x0000.into();
+    emulator.reg_mut::<RegisterIE>().set_timer(true);
+    emulator.request_interrupt(Interrupt::Timer);
+    emulator.request_interrupt(Interrupt::Joypad);
+
+    let interrupt = emulator.with_cpu(|cpu, bus| cpu.process_interrupt(bus));
+
+    assert_eq!(interrupt, Some(Interrupt::Joypad));
+    assert_eq!(emulator.cpu.program_counter.0, JOYPAD_INTERRUPT);
+    assert!(emulator.reg::<RegisterIF>().get_timer());
+    assert!(!emulator.reg::<RegisterIF>().get_joypad());
+}
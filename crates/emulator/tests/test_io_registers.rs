@@ -0,0 +1,118 @@
+use emulator::*;
+
+/// Emulator with LCD turned off, so the PPU doesn't update its registers
+fn emulator() -> Emulator {
+    let mut emulator = Emulator::default();
+    emulator.reg_mut::<RegisterLCDC>().0 = 0;
+    emulator
+}
+
+#[test]
+fn test_io_unused_bits() {
+    let mut emulator = emulator();
+
+    let expected = [
+        (RegisterIF::ADDRESS, 0xE0),
+        (RegisterTAC::ADDRESS, 0xF8),
+        (0xFF02, 0x7E),
+        (0xFF1A, 0x7F),
+        (0xFF26, 0x70),
+    ];
+
+    for (address, value) in expected {
+        emulator.set(address, 0x00);
+        assert_eq!(emulator.get(address), value, "{address:#06X}");
+    }
+}
+
+#[test]
+fn test_io_serial_clock_speed() {
+    let mut emulator = emulator();
+
+    // clock speed bit of SC reads as 1 outside of CGB mode
+    emulator.set(0xFF02, 0x00);
+    assert_eq!(emulator.get(0xFF02), 0x7E);
+
+    emulator.cgb_mode = true;
+    assert_eq!(emulator.get(0xFF02), 0x7C);
+    emulator.set(0xFF02, 0x02);
+    assert_eq!(emulator.get(0xFF02), 0x7E);
+}
+
+#[test]
+fn test_io_unused_addresses() {
+    let mut emulator = emulator();
+
+    for address in [0xFF03, 0xFF08, 0xFF15, 0xFF27, 0xFF4C, 0xFF7F] {
+        emulator.set(address, 0x12);
+        assert_eq!(emulator.get(address), 0xFF, "{address:#06X}");
+        assert_eq!(*emulator.get_force(address), 0x00, "{address:#06X}");
+    }
+}
+
+#[test]
+fn test_io_read_only_bits() {
+    let mut emulator = emulator();
+
+    // PPU mode and LYC == LY are read-only
+    emulator.reg_mut::<RegisterSTAT>().0 = 0x85;
+    emulator.set(RegisterSTAT::ADDRESS, 0x7A);
+    assert_eq!(emulator.get(RegisterSTAT::ADDRESS), 0xFD);
+
+    // joypad lines are read-only
+    emulator.set(RegisterP1::ADDRESS, 0x00);
+    assert_eq!(emulator.get(RegisterP1::ADDRESS), 0xCF);
+    emulator.set(RegisterP1::ADDRESS, 0xFF);
+    assert_eq!(emulator.reg::<RegisterP1>().0, 0xFF);
+
+    // write-only registers
+    emulator.set(0xFF13, 0x12);
+    assert_eq!(emulator.get(0xFF13), 0xFF);
+    assert_eq!(*emulator.get_force(0xFF13), 0x12);
+}
+
+#[test]
+fn test_io_write_side_effects() {
+    let mut emulator = emulator();
+
+    // LY is read-only
+    emulator.reg_mut::<RegisterLY>().0 = 0x42;
+    emulator.set(RegisterLY::ADDRESS, 0x99);
+    assert_eq!(emulator.get(RegisterLY::ADDRESS), 0x42);
+
+    emulator.tick_cycles(0x300);
+    assert_ne!(emulator.get(RegisterDIV::ADDRESS), 0x00);
+    emulator.set(RegisterDIV::ADDRESS, 0x99);
+    assert_eq!(emulator.get(RegisterDIV::ADDRESS), 0x00);
+    assert_eq!(emulator.internal_timer.as_u16(), 0x0000);
+}
+
+#[test]
+fn test_io_timer_control() {
+    let mut emulator = emulator();
+    emulator.set(RegisterTMA::ADDRESS, 0x12);
+
+    // TAC and TMA are different registers
+    emulator.set(RegisterTAC::ADDRESS, 0x05);
+    assert_eq!(emulator.get(RegisterTMA::ADDRESS), 0x12);
+    assert!(emulator.reg::<RegisterTAC>().get_enable());
+    assert_eq!(
+        emulator.reg::<RegisterTAC>().get_clock_mode(),
+        ClockMode::Every4
+    );
+}
+
+#[test]
+fn test_io_cgb_only_registers() {
+    let mut emulator = emulator();
+    assert!(!emulator.cgb_mode);
+
+    for address in [RegisterKEY1::ADDRESS, 0xFF56, 0xFF68, 0xFF69, 0xFF74] {
+        emulator.set(address, 0x00);
+        assert_eq!(emulator.get(address), 0xFF, "{address:#06X}");
+    }
+
+    // available in all modes
+    emulator.set(0xFF72, 0x12);
+    assert_eq!(emulator.get(0xFF72), 0x12);
+}
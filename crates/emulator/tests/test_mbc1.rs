@@ -0,0 +1,112 @@
+use emulator::*;
+
+mod common;
+use common::*;
+
+#[test]
+fn test_mbc1_rom_banking() {
+    // 512 KiB, 32 banks
+    let mut emulator = banked_cartridge(CartridgeType::Mbc1RamBattery, 0x04, 0x00);
+    assert!(matches!(emulator.rom.mapper, CartridgeMapper::Mbc1(_)));
+
+    assert_eq!(emulator.get(0x0000), 0);
+    // bank 1 is mapped by default
+    assert_eq!(emulator.get(0x4000), 1);
+
+    emulator.set(0x2000, 0x05);
+    assert_eq!(emulator.get(0x4000), 5);
+    assert_eq!(emulator.get(0x0000), 0);
+
+    // bank 0 is treated as 1
+    emulator.set(0x3FFF, 0x00);
+    assert_eq!(emulator.get(0x4000), 1);
+
+    // only lower 5 bits are used
+    emulator.set(0x2000, 0xFF);
+    assert_eq!(emulator.get(0x4000), 31);
+
+    // ROM is not overwritten
+    assert_eq!(emulator.get(0x2000), 0x00);
+}
+
+#[test]
+fn test_mbc1_bank_number_wraps() {
+    // 128 KiB, 8 banks
+    let mut emulator = banked_cartridge(CartridgeType::Mbc1RamBattery, 0x02, 0x00);
+
+    emulator.set(0x2000, 0x09);
+    assert_eq!(emulator.get(0x4000), 1);
+
+    // bank 0x10 is masked to 0, but the zero check uses all 5 bits
+    emulator.set(0x2000, 0x10);
+    assert_eq!(emulator.get(0x4000), 0);
+}
+
+#[test]
+fn test_mbc1_large_rom() {
+    // 2 MiB, 128 banks
+    let mut emulator = banked_cartridge(CartridgeType::Mbc1RamBattery, 0x06, 0x00);
+
+    emulator.set(0x2000, 0x02);
+    emulator.set(0x4000, 0x03);
+    assert_eq!(emulator.get(0x4000), 0x62);
+    // simple banking mode, bank 0 is always mapped to $0000-$3FFF
+    assert_eq!(emulator.get(0x0000), 0x00);
+
+    // bank $20 can't be mapped to $4000-$7FFF
+    emulator.set(0x2000, 0x00);
+    emulator.set(0x4000, 0x01);
+    assert_eq!(emulator.get(0x4000), 0x21);
+
+    // advanced banking mode, BANK2 applies to $0000-$3FFF
+    emulator.set(0x6000, 0x01);
+    assert_eq!(emulator.get(0x0000), 0x20);
+    assert_eq!(emulator.get(0x4000), 0x21);
+}
+
+#[test]
+fn test_mbc1_ram_banking() {
+    // 32 KiB RAM, 4 banks
+    let mut emulator = banked_cartridge(CartridgeType::Mbc1RamBattery, 0x04, 0x03);
+
+    // RAM is disabled by default
+    emulator.set(0xA000, 0x42);
+    assert_eq!(emulator.get(0xA000), 0xFF);
+
+    emulator.set(0x0000, 0x0A);
+    emulator.set(0xA000, 0x42);
+    assert_eq!(emulator.get(0xA000), 0x42);
+
+    // simple banking mode, bank 0 is always mapped
+    emulator.set(0x4000, 0x02);
+    assert_eq!(emulator.get(0xA000), 0x42);
+
+    emulator.set(0x6000, 0x01);
+    assert_eq!(emulator.get(0xA000), 0x00);
+    emulator.set(0xBFFF, 0x24);
+    assert_eq!(emulator.rom.ram[2 * RAM_BANK_SIZE + 0x1FFF], 0x24);
+
+    emulator.set(0x4000, 0x00);
+    assert_eq!(emulator.get(0xA000), 0x42);
+
+    // any value with $A in the lower nibble enables RAM
+    emulator.set(0x0000, 0x00);
+    assert_eq!(emulator.get(0xA000), 0xFF);
+    emulator.set(0x1FFF, 0xFA);
+    assert_eq!(emulator.get(0xA000), 0x42);
+}
+
+#[test]
+fn test_rom_only() {
+    let rom = include_bytes!("../../../test-roms/hello.gb");
+    let mut emulator = Emulator::from_rom(rom);
+    assert!(matches!(emulator.rom.mapper, CartridgeMapper::NoMapper(_)));
+
+    let value = emulator.get(0x0150);
+    emulator.set(0x0150, value.wrapping_add(1));
+    assert_eq!(emulator.get(0x0150), value);
+
+    // no external RAM
+    emulator.set(0xA000, 0x42);
+    assert_eq!(emulator.get(0xA000), 0xFF);
+}
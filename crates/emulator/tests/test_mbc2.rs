@@ -0,0 +1,56 @@
+use emulator::*;
+
+mod common;
+use common::*;
+
+#[test]
+fn test_mbc2_rom_banking() {
+    // 256 KiB, 16 banks
+    let mut emulator = banked_cartridge(CartridgeType::Mbc2Battery, 0x03, 0x00);
+    assert!(matches!(emulator.rom.mapper, CartridgeMapper::Mbc2(_)));
+
+    assert_eq!(emulator.get(0x4000), 1);
+
+    // bit 8 of the address is set
+    emulator.set(0x2100, 0x0F);
+    assert_eq!(emulator.get(0x4000), 15);
+    emulator.set(0x0100, 0xF3);
+    assert_eq!(emulator.get(0x4000), 3);
+
+    // bank 0 is treated as 1
+    emulator.set(0x3FFF, 0x00);
+    assert_eq!(emulator.get(0x4000), 1);
+
+    // bit 8 is not set, the write enables RAM
+    emulator.set(0x2000, 0x0A);
+    assert_eq!(emulator.get(0x4000), 1);
+    assert_eq!(emulator.get(0xA000), 0xF0);
+
+    // writes to $4000-$7FFF are ignored
+    emulator.set(0x4100, 0x05);
+    assert_eq!(emulator.get(0x4000), 1);
+}
+
+#[test]
+fn test_mbc2_ram() {
+    let mut emulator = banked_cartridge(CartridgeType::Mbc2Battery, 0x03, 0x00);
+    assert_eq!(emulator.rom.ram.len(), MBC2_RAM_SIZE);
+
+    emulator.set(0xA000, 0x0C);
+    assert_eq!(emulator.get(0xA000), 0xFF);
+
+    emulator.set(0x0000, 0x0A);
+
+    // only lower nibble is stored, upper nibble reads as 1s
+    emulator.set(0xA000, 0x3C);
+    assert_eq!(emulator.get(0xA000), 0xFC);
+
+    // RAM is mirrored every 512 bytes
+    assert_eq!(emulator.get(0xA200), 0xFC);
+    assert_eq!(emulator.get(0xBE00), 0xFC);
+    emulator.set(0xBFFF, 0x05);
+    assert_eq!(emulator.get(0xA1FF), 0xF5);
+
+    emulator.set(0x00FF, 0x00);
+    assert_eq!(emulator.get(0xA000), 0xFF);
+}
@@ -0,0 +1,218 @@
+use std::sync::Arc;
+
+use emulator::*;
+
+mod common;
+use common::*;
+
+/// MBC3 cartridge with RAM and RTC enabled
+fn mbc3_emulator(cartridge_type: CartridgeType, rom_size_code: u8, ram_size_code: u8) -> Emulator {
+    let mut emulator = banked_cartridge(cartridge_type, rom_size_code, ram_size_code);
+    // enable RAM and RTC
+    emulator.set(0x0000, 0x0A);
+
+    emulator
+}
+
+/// MBC3 cartridge with RTC following the returned fake clock
+fn rtc_emulator() -> (Emulator, FakeClock) {
+    let mut emulator = mbc3_emulator(CartridgeType::Mbc3TimerRamBattery, 0x00, 0x03);
+
+    let clock = FakeClock::default();
+    let rtc = emulator.rom.mapper.rtc_mut().unwrap();
+    rtc.set_clock(Arc::new(clock.clone()));
+
+    (emulator, clock)
+}
+
+fn latch(emulator: &mut Emulator) {
+    emulator.set(0x6000, 0x00);
+    emulator.set(0x6000, 0x01);
+}
+
+fn read_rtc_register(emulator: &mut Emulator, register: u8) -> u8 {
+    emulator.set(0x4000, register);
+    emulator.get(0xA000)
+}
+
+fn write_rtc_register(emulator: &mut Emulator, register: u8, value: u8) {
+    emulator.set(0x4000, register);
+    emulator.set(0xA000, value);
+}
+
+#[test]
+fn test_mbc3_rom_banking() {
+    // 2 MiB, 128 banks
+    let mut emulator = mbc3_emulator(CartridgeType::Mbc3, 0x06, 0x00);
+    assert!(matches!(emulator.rom.mapper, CartridgeMapper::Mbc3(_)));
+    assert!(emulator.rom.mapper.rtc().is_none());
+
+    assert_eq!(emulator.get(0x4000), 1);
+
+    emulator.set(0x2000, 0x45);
+    assert_eq!(emulator.get(0x4000), 0x45);
+    assert_eq!(emulator.get(0x0000), 0x00);
+
+    // unlike MBC1, banks $20, $40 and $60 are accessible
+    emulator.set(0x2000, 0x20);
+    assert_eq!(emulator.get(0x4000), 0x20);
+
+    // bank 0 is treated as 1
+    emulator.set(0x2000, 0x00);
+    assert_eq!(emulator.get(0x4000), 1);
+
+    // only lower 7 bits are used
+    emulator.set(0x2000, 0xFF);
+    assert_eq!(emulator.get(0x4000), 0x7F);
+}
+
+#[test]
+fn test_mbc3_ram_banking() {
+    // 32 KiB RAM, 4 banks
+    let mut emulator = mbc3_emulator(CartridgeType::Mbc3RamBattery, 0x00, 0x03);
+
+    emulator.set(0xA000, 0x42);
+    emulator.set(0x4000, 0x03);
+    assert_eq!(emulator.get(0xA000), 0x00);
+    emulator.set(0xBFFF, 0x24);
+    assert_eq!(emulator.rom.ram[3 * RAM_BANK_SIZE + 0x1FFF], 0x24);
+
+    emulator.set(0x4000, 0x00);
+    assert_eq!(emulator.get(0xA000), 0x42);
+
+    // no RTC on this cartridge
+    emulator.set(0x4000, 0x08);
+    assert_eq!(emulator.get(0xA000), 0xFF);
+
+    emulator.set(0x0000, 0x00);
+    emulator.set(0x4000, 0x00);
+    assert_eq!(emulator.get(0xA000), 0xFF);
+}
+
+#[test]
+fn test_mbc3_rtc_latch() {
+    let (mut emulator, clock) = rtc_emulator();
+
+    clock.advance(2 * 86400 + 3 * 3600 + 4 * 60 + 5);
+
+    // registers are not updated until latched
+    assert_eq!(read_rtc_register(&mut emulator, 0x08), 0);
+
+    latch(&mut emulator);
+    assert_eq!(read_rtc_register(&mut emulator, 0x08), 5);
+    assert_eq!(read_rtc_register(&mut emulator, 0x09), 4);
+    assert_eq!(read_rtc_register(&mut emulator, 0x0A), 3);
+    assert_eq!(read_rtc_register(&mut emulator, 0x0B), 2);
+    assert_eq!(read_rtc_register(&mut emulator, 0x0C), 0);
+
+    // latch requires writing $00 first
+    clock.advance(10);
+    emulator.set(0x6000, 0x01);
+    assert_eq!(read_rtc_register(&mut emulator, 0x08), 5);
+
+    latch(&mut emulator);
+    assert_eq!(read_rtc_register(&mut emulator, 0x08), 15);
+
+    // RTC registers are not accessible while RAM is disabled
+    emulator.set(0x0000, 0x00);
+    assert_eq!(read_rtc_register(&mut emulator, 0x08), 0xFF);
+}
+
+#[test]
+fn test_mbc3_rtc_day_carry() {
+    let (mut emulator, clock) = rtc_emulator();
+
+    clock.advance(511 * 86400 + 86399);
+    latch(&mut emulator);
+    assert_eq!(read_rtc_register(&mut emulator, 0x0B), 0xFF);
+    assert_eq!(read_rtc_register(&mut emulator, 0x0C), RTC_DAY_HIGH_DAY);
+
+    // day counter overflows to 0 and sets the carry
+    clock.advance(1);
+    latch(&mut emulator);
+    assert_eq!(read_rtc_register(&mut emulator, 0x0A), 0);
+    assert_eq!(read_rtc_register(&mut emulator, 0x0B), 0);
+    assert_eq!(read_rtc_register(&mut emulator, 0x0C), RTC_DAY_HIGH_CARRY);
+
+    // carry stays set until cleared
+    clock.advance(86400);
+    latch(&mut emulator);
+    assert_eq!(read_rtc_register(&mut emulator, 0x0B), 1);
+    assert_eq!(read_rtc_register(&mut emulator, 0x0C), RTC_DAY_HIGH_CARRY);
+
+    write_rtc_register(&mut emulator, 0x0C, 0x00);
+    assert_eq!(read_rtc_register(&mut emulator, 0x0C), 0);
+}
+
+#[test]
+fn test_mbc3_rtc_halt() {
+    let (mut emulator, clock) = rtc_emulator();
+
+    write_rtc_register(&mut emulator, 0x0C, RTC_DAY_HIGH_HALT);
+    clock.advance(100);
+    latch(&mut emulator);
+    assert_eq!(read_rtc_register(&mut emulator, 0x08), 0);
+
+    // set the time while halted
+    write_rtc_register(&mut emulator, 0x08, 30);
+    write_rtc_register(&mut emulator, 0x09, 59);
+    write_rtc_register(&mut emulator, 0x0A, 23);
+    write_rtc_register(&mut emulator, 0x0C, 0x00);
+
+    clock.advance(30);
+    latch(&mut emulator);
+    assert_eq!(read_rtc_register(&mut emulator, 0x08), 0);
+    assert_eq!(read_rtc_register(&mut emulator, 0x09), 0);
+    assert_eq!(read_rtc_register(&mut emulator, 0x0A), 0);
+    assert_eq!(read_rtc_register(&mut emulator, 0x0B), 1);
+}
+
+#[test]
+fn test_mbc3_rtc_invalid_values() {
+    let (mut emulator, clock) = rtc_emulator();
+
+    // only writable bits are stored
+    write_rtc_register(&mut emulator, 0x08, 0xFF);
+    assert_eq!(read_rtc_register(&mut emulator, 0x08), 0x3F);
+
+    // out of range seconds overflow without incrementing minutes
+    clock.advance(1);
+    latch(&mut emulator);
+    assert_eq!(read_rtc_register(&mut emulator, 0x08), 0);
+    assert_eq!(read_rtc_register(&mut emulator, 0x09), 0);
+
+    // hours overflow without incrementing days
+    write_rtc_register(&mut emulator, 0x08, 59);
+    write_rtc_register(&mut emulator, 0x09, 59);
+    write_rtc_register(&mut emulator, 0x0A, 31);
+    clock.advance(1);
+    latch(&mut emulator);
+    assert_eq!(read_rtc_register(&mut emulator, 0x0A), 0);
+    assert_eq!(read_rtc_register(&mut emulator, 0x0B), 0);
+}
+
+#[test]
+fn test_mbc3_rtc_emulated_time() {
+    let mut emulator = mbc3_emulator(CartridgeType::Mbc3TimerBattery, 0x00, 0x00);
+
+    emulator.tick_cycles(1);
+    let rtc = emulator.rom.mapper.rtc().unwrap();
+    assert_eq!(rtc.sub_second_clocks, DOTS_PER_M_CYCLE);
+
+    // one second and a half
+    emulator
+        .rom
+        .mapper
+        .tick(RTC_CLOCKS_PER_SECOND * 3 / 2 - DOTS_PER_M_CYCLE);
+    latch(&mut emulator);
+    assert_eq!(read_rtc_register(&mut emulator, 0x08), 1);
+
+    // writing seconds resets the sub-second counter
+    write_rtc_register(&mut emulator, 0x08, 10);
+    emulator.rom.mapper.tick(RTC_CLOCKS_PER_SECOND - 1);
+    latch(&mut emulator);
+    assert_eq!(read_rtc_register(&mut emulator, 0x08), 10);
+    emulator.rom.mapper.tick(1);
+    latch(&mut emulator);
+    assert_eq!(read_rtc_register(&mut emulator, 0x08), 11);
+}
@@ -0,0 +1,77 @@
+use emulator::*;
+
+mod common;
+use common::*;
+
+fn mapped_rom_bank(emulator: &mut Emulator) -> u16 {
+    u16::from_le_bytes([emulator.get(0x4000), emulator.get(0x4001)])
+}
+
+#[test]
+fn test_mbc5_rom_banking() {
+    // 8 MiB, 512 banks
+    let mut emulator = banked_cartridge(CartridgeType::Mbc5, 0x08, 0x00);
+    assert!(matches!(emulator.rom.mapper, CartridgeMapper::Mbc5(_)));
+
+    assert_eq!(mapped_rom_bank(&mut emulator), 1);
+
+    emulator.set(0x2000, 0xAB);
+    assert_eq!(mapped_rom_bank(&mut emulator), 0xAB);
+
+    // 9th bit of the bank number
+    emulator.set(0x3000, 0x01);
+    assert_eq!(mapped_rom_bank(&mut emulator), 0x1AB);
+    assert_eq!(emulator.get(0x0000), 0x00);
+
+    // lower bits are kept
+    emulator.set(0x3FFF, 0xFE);
+    assert_eq!(mapped_rom_bank(&mut emulator), 0x0AB);
+
+    // bank 0 can be mapped to $4000-$7FFF
+    emulator.set(0x2FFF, 0x00);
+    assert_eq!(mapped_rom_bank(&mut emulator), 0);
+}
+
+#[test]
+fn test_mbc5_ram_banking() {
+    // 128 KiB RAM, 16 banks
+    let mut emulator = banked_cartridge(CartridgeType::Mbc5RamBattery, 0x01, 0x04);
+
+    emulator.set(0xA000, 0x42);
+    assert_eq!(emulator.get(0xA000), 0xFF);
+
+    emulator.set(0x0000, 0x0A);
+    emulator.set(0xA000, 0x42);
+    emulator.set(0x4000, 0x0F);
+    emulator.set(0xA000, 0x24);
+    assert_eq!(emulator.rom.ram[15 * RAM_BANK_SIZE], 0x24);
+
+    emulator.set(0x4000, 0x00);
+    assert_eq!(emulator.get(0xA000), 0x42);
+
+    // no rumble on this cartridge, bit 3 selects the RAM bank
+    emulator.set(0x4000, 0x08);
+    assert!(!emulator.is_rumble_active());
+    assert_eq!(emulator.get(0xA000), 0x00);
+
+    emulator.set(0x0000, 0x00);
+    assert_eq!(emulator.get(0xA000), 0xFF);
+}
+
+#[test]
+fn test_mbc5_rumble() {
+    let mut emulator = banked_cartridge(CartridgeType::Mbc5RumbleRamBattery, 0x01, 0x03);
+    emulator.set(0x0000, 0x0A);
+    emulator.set(0xA000, 0x42);
+
+    assert!(!emulator.is_rumble_active());
+
+    // motor is turned on and RAM bank 0 stays mapped
+    emulator.set(0x4000, MBC5_RUMBLE_BIT);
+    assert!(emulator.is_rumble_active());
+    assert_eq!(emulator.get(0xA000), 0x42);
+
+    emulator.set(0x4000, 0x01);
+    assert!(!emulator.is_rumble_active());
+    assert_eq!(emulator.get(0xA000), 0x00);
+}
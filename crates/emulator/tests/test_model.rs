@@ -0,0 +1,147 @@
+use emulator::*;
+
+mod common;
+use common::*;
+
+fn cpu_registers(emulator: &Emulator) -> [u16; 4] {
+    [
+        emulator.cpu.accumulator_and_flags.as_u16(),
+        emulator.cpu.register_bc.as_u16(),
+        emulator.cpu.register_de.as_u16(),
+        emulator.cpu.register_hl.as_u16(),
+    ]
+}
+
+#[test]
+fn test_model_dmg_registers() {
+    let emulator = Emulator::from_rom_with_model(HELLO_ROM, EmulatorModel::Dmg);
+
+    assert_eq!(emulator.model, EmulatorModel::Dmg);
+    assert_eq!(cpu_registers(&emulator), [0x01B0, 0x0013, 0x00D8, 0x014D]);
+    assert_eq!(emulator.cpu.program_counter.0, 0x0100);
+    assert_eq!(emulator.cpu.stack_pointer.0, 0xFFFE);
+    assert_eq!(emulator.get(RegisterDIV::ADDRESS), 0xAB);
+
+    // carry and half-carry are cleared if the header checksum is $00
+    let mut data = HELLO_ROM.to_vec();
+    data[ROM_ADDRESS_HEADER_CHECKSUM] = 0x00;
+    let emulator = Emulator::from_rom_with_model(data, EmulatorModel::Mgb);
+    assert_eq!(cpu_registers(&emulator), [0xFF80, 0x0013, 0x00D8, 0x014D]);
+}
+
+#[test]
+fn test_model_other_registers() {
+    let expected = [
+        (EmulatorModel::Dmg0, [0x0100, 0xFF13, 0x00C1, 0x8403], 0x18),
+        (EmulatorModel::Sgb, [0x0100, 0x0014, 0x0000, 0xC060], 0x00),
+        (EmulatorModel::Sgb2, [0xFF00, 0x0014, 0x0000, 0xC060], 0x00),
+    ];
+
+    for (model, registers, div) in expected {
+        let emulator = Emulator::from_rom_with_model(HELLO_ROM, model);
+        assert_eq!(cpu_registers(&emulator), registers, "{model:?}");
+        assert_eq!(emulator.get(RegisterDIV::ADDRESS), div, "{model:?}");
+    }
+}
+
+#[test]
+fn test_model_cgb_registers() {
+    let cgb_rom = rom_with_header(&[(ROM_ADDRESS_CGB_FLAG, 0x80)]);
+
+    let emulator = Emulator::from_rom_with_model(cgb_rom.clone(), EmulatorModel::Cgb);
+    assert!(emulator.cgb_mode);
+    assert_eq!(cpu_registers(&emulator), [0x1180, 0x0000, 0xFF56, 0x000D]);
+
+    let emulator = Emulator::from_rom_with_model(cgb_rom, EmulatorModel::Agb);
+    assert!(emulator.cgb_mode);
+    assert_eq!(cpu_registers(&emulator), [0x1100, 0x0100, 0xFF56, 0x000D]);
+}
+
+#[test]
+fn test_model_cgb_dmg_mode_registers() {
+    let mut changes = vec![(ROM_ADDRESS_OLD_LICENSEE_CODE, 0x01)];
+    for (i, byte) in b"TETRIS".iter().enumerate() {
+        changes.push((ROM_RANGE_TITLE.start + i, *byte));
+    }
+    let rom = rom_with_header(&changes);
+
+    // B is the sum of the title bytes for Nintendo games
+    let emulator = Emulator::from_rom_with_model(rom.clone(), EmulatorModel::Cgb);
+    assert!(!emulator.cgb_mode);
+    assert_eq!(cpu_registers(&emulator), [0x1180, 0xDB00, 0x0008, 0x007C]);
+
+    let emulator = Emulator::from_rom_with_model(rom, EmulatorModel::Agb);
+    assert_eq!(cpu_registers(&emulator), [0x1100, 0xDC00, 0x0008, 0x007C]);
+
+    // other licensees
+    let emulator = Emulator::from_rom_with_model(HELLO_ROM, EmulatorModel::Cgb);
+    assert_eq!(cpu_registers(&emulator), [0x1180, 0x0000, 0x0008, 0x007C]);
+}
+
+#[test]
+fn test_model_io_registers() {
+    // SC, NR52, STAT, LY, DMA
+    let expected = [
+        (EmulatorModel::Dmg0, [0x7E, 0xF1, 0x81, 0x91, 0xFF]),
+        (EmulatorModel::Dmg, [0x7E, 0xF1, 0x85, 0x00, 0xFF]),
+        (EmulatorModel::Mgb, [0x7E, 0xF1, 0x85, 0x00, 0xFF]),
+        (EmulatorModel::Sgb, [0x7E, 0xF0, 0x85, 0x00, 0xFF]),
+        (EmulatorModel::Sgb2, [0x7E, 0xF0, 0x85, 0x00, 0xFF]),
+        (EmulatorModel::Cgb, [0x7F, 0xF1, 0x85, 0x00, 0x00]),
+        (EmulatorModel::Agb, [0x7F, 0xF1, 0x85, 0x00, 0x00]),
+    ];
+
+    for (model, values) in expected {
+        let emulator = Emulator::from_rom_with_model(HELLO_ROM, model);
+        let addresses = [
+            0xFF02,
+            0xFF26,
+            RegisterSTAT::ADDRESS,
+            RegisterLY::ADDRESS,
+            RegisterDMA::ADDRESS,
+        ];
+        assert_eq!(
+            addresses.map(|address| emulator.get(address)),
+            values,
+            "{model:?}"
+        );
+
+        // same on every model
+        assert_eq!(emulator.get(RegisterP1::ADDRESS), 0xCF, "{model:?}");
+        assert_eq!(emulator.get(RegisterTAC::ADDRESS), 0xF8, "{model:?}");
+        assert_eq!(emulator.get(RegisterIF::ADDRESS), 0xE1, "{model:?}");
+        assert_eq!(emulator.get(RegisterLCDC::ADDRESS), 0x91, "{model:?}");
+        assert_eq!(emulator.get(RegisterBGP::ADDRESS), 0xFC, "{model:?}");
+        // NR10, NR50, NR51
+        assert_eq!(emulator.get(0xFF10), 0x80, "{model:?}");
+        assert_eq!(emulator.get(0xFF24), 0x77, "{model:?}");
+        assert_eq!(emulator.get(0xFF25), 0xF3, "{model:?}");
+    }
+}
+
+#[test]
+fn test_model_cgb_features() {
+    let cgb_rom = rom_with_header(&[(ROM_ADDRESS_CGB_FLAG, 0x80)]);
+
+    // CGB mode is available only on CGB models
+    for model in EmulatorModel::ALL {
+        let mut emulator = Emulator::from_rom_with_model(cgb_rom.clone(), model);
+        assert_eq!(emulator.cgb_mode, model.is_cgb(), "{model:?}");
+
+        emulator.set(RegisterKEY1::ADDRESS, 0x01);
+        let key1 = emulator.get(RegisterKEY1::ADDRESS);
+        if model.is_cgb() {
+            assert_eq!(key1, 0x7F, "{model:?}");
+        } else {
+            assert_eq!(key1, 0xFF, "{model:?}");
+        }
+    }
+}
+
+#[test]
+fn test_model_default() {
+    let emulator = Emulator::from_rom(HELLO_ROM);
+    assert_eq!(emulator.model, EmulatorModel::Cgb);
+    assert!(EmulatorModel::Sgb2.is_sgb());
+    assert!(!EmulatorModel::Mgb.is_sgb());
+}
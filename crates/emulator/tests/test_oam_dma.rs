@@ -0,0 +1,137 @@
+use emulator::*;
+
+const SOURCE: u16 = 0xC100;
+
+/// Emulator with distinct data in the source pages $C1 and $C2, LCD is turned
+/// off so OAM is always accessible.
+fn emulator_with_source() -> Emulator {
+    let mut emulator = Emulator::default();
+    emulator.reg_mut::<RegisterLCDC>().0 = 0;
+
+    for i in 0..OAM_DMA_LENGTH as u16 {
+        emulator.set(SOURCE + i, i as u8 + 1);
+        emulator.set(SOURCE + 0x100 + i, (i as u8).wrapping_add(0x80));
+    }
+
+    emulator
+}
+
+#[test]
+fn test_oam_dma_transfer() {
+    let mut emulator = emulator_with_source();
+
+    emulator.set(RegisterDMA::ADDRESS, 0xC1);
+    assert_eq!(emulator.get(RegisterDMA::ADDRESS), 0xC1);
+
+    // cycle of the write and the start delay, OAM is still accessible
+    emulator.tick_cycles(1);
+    assert!(!emulator.is_oam_dma_active());
+    emulator.tick_cycles(1);
+    assert!(emulator.is_oam_dma_active());
+    assert_eq!(emulator.oam[0], 0x00);
+
+    emulator.tick_cycles(1);
+    assert_eq!(emulator.oam[0], 0x01);
+    assert_eq!(emulator.oam[1], 0x00);
+
+    emulator.tick_cycles(OAM_DMA_LENGTH as usize - 2);
+    assert!(emulator.is_oam_dma_active());
+
+    emulator.tick_cycles(1);
+    assert!(!emulator.is_oam_dma_active());
+    assert_eq!(emulator.get(0xFE00), 0x01);
+    assert_eq!(emulator.get(0xFE9F), 0xA0);
+}
+
+#[test]
+fn test_oam_dma_bus_conflict() {
+    let mut emulator = emulator_with_source();
+    emulator.set(0x8000, 0x42);
+    emulator.set(0xFF80, 0x24);
+
+    emulator.set(RegisterDMA::ADDRESS, 0xC1);
+    emulator.tick_cycles(OAM_DMA_START_DELAY as usize + 4);
+
+    // OAM is not accessible
+    assert_eq!(emulator.get(0xFE00), 0xFF);
+    emulator.set(0xFE10, 0x99);
+    // HRAM and I/O registers are accessible
+    assert_eq!(emulator.get(0xFF80), 0x24);
+    assert_eq!(emulator.get(RegisterDMA::ADDRESS), 0xC1);
+    // different bus is accessible
+    assert_eq!(emulator.get(0x8000), 0x42);
+    // external bus returns the byte being copied
+    assert_eq!(emulator.get(0x0000), 0x05);
+    assert_eq!(emulator.get(0xC000), 0x05);
+    emulator.set(0xC000, 0x77);
+
+    emulator.tick_cycles(OAM_DMA_LENGTH as usize);
+    assert_eq!(emulator.oam[0x10], 0x11);
+    assert_eq!(emulator.get(0xC000), 0x00);
+}
+
+#[test]
+fn test_oam_dma_vram_source() {
+    let mut emulator = emulator_with_source();
+    emulator.set(0x8000, 0x42);
+    emulator.set(0xC000, 0x24);
+
+    emulator.set(RegisterDMA::ADDRESS, 0x80);
+    emulator.tick_cycles(OAM_DMA_START_DELAY as usize);
+
+    assert_eq!(emulator.get(0x8000), 0x42);
+    assert_eq!(emulator.get(0x8010), 0x42);
+    assert_eq!(emulator.get(0xC000), 0x24);
+}
+
+#[test]
+fn test_oam_dma_restart() {
+    let mut emulator = emulator_with_source();
+
+    emulator.set(RegisterDMA::ADDRESS, 0xC1);
+    emulator.tick_cycles(OAM_DMA_START_DELAY as usize + 10);
+
+    // old transfer continues during the start delay of the new one
+    emulator.set(RegisterDMA::ADDRESS, 0xC2);
+    emulator.tick_cycles(OAM_DMA_START_DELAY as usize);
+    assert!(emulator.is_oam_dma_active());
+    assert_eq!(emulator.oam[11], 0x0C);
+    assert_eq!(emulator.oam[12], 0x00);
+
+    emulator.tick_cycles(OAM_DMA_LENGTH as usize);
+    assert!(!emulator.is_oam_dma_active());
+    assert_eq!(emulator.oam[0], 0x80);
+    assert_eq!(emulator.oam[0x9F], 0x1F);
+}
+
+#[test]
+fn test_oam_dma_echo_source() {
+    let mut emulator = emulator_with_source();
+    emulator.set(0xDF00, 0x33);
+
+    // pages above $DF are read from the echo RAM
+    emulator.set(RegisterDMA::ADDRESS, 0xFF);
+    emulator.tick_cycles(OAM_DMA_START_DELAY as usize + OAM_DMA_LENGTH as usize);
+
+    assert_eq!(emulator.oam[0], 0x33);
+}
+
+#[test]
+fn test_oam_dma_routine_in_hram() {
+    let mut emulator = emulator_with_source();
+
+    // LDH [$46], A; LD A, 40; loop: DEC A; JR NZ, loop; NOP
+    let routine = [0xE0, 0x46, 0x3E, 0x28, 0x3D, 0x20, 0xFD, 0x00];
+    for (i, byte) in routine.iter().enumerate() {
+        emulator.set(0xFF80 + i as u16, *byte);
+    }
+    emulator.cpu.program_counter.0 = 0xFF80;
+    emulator.cpu.accumulator_and_flags.set_high(0xC1);
+    emulator.handle_next_instruction();
+
+    emulator.run_until(|emulator| emulator.cpu.opcode_address == 0xFF87);
+
+    assert!(!emulator.is_oam_dma_active());
+    assert_eq!(emulator.get(0xFE00), 0x01);
+    assert_eq!(emulator.get(0xFE9F), 0xA0);
+}
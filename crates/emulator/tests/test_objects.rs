@@ -0,0 +1,265 @@
+use emulator::*;
+
+mod common;
+use common::*;
+
+/// Left half color index 1, right half transparent
+const TILE_LEFT_HALF: u8 = 2;
+/// Only the top row has color index 2
+const TILE_TOP_ROW: u8 = 3;
+/// Solid color index 1, used as the top half of 8x16 objects
+const TILE_SOLID_1: u8 = 4;
+/// Solid color index 2, used as the bottom half of 8x16 objects
+const TILE_SOLID_2: u8 = 5;
+
+fn set_tile_rows(emulator: &mut Emulator, tile_index: u8, rows: [(u8, u8); TILE_HEIGHT]) {
+    let address = tile_index as usize * TILE_SIZE;
+    for (row, (low, high)) in rows.into_iter().enumerate() {
+        emulator.vram[0][address + row * 2] = low;
+        emulator.vram[0][address + row * 2 + 1] = high;
+    }
+}
+
+/// Emulator with test tiles, white background and OBP0 mapping color
+/// indices to the same shades, OBP1 reversed
+fn emulator() -> Emulator {
+    let mut emulator = Emulator::default();
+
+    set_tile_rows(&mut emulator, BLACK_TILE, [(0xFF, 0xFF); TILE_HEIGHT]);
+    set_tile_rows(&mut emulator, TILE_LEFT_HALF, [(0xF0, 0x00); TILE_HEIGHT]);
+    let mut top_row = [(0x00, 0x00); TILE_HEIGHT];
+    top_row[0] = (0x00, 0xFF);
+    set_tile_rows(&mut emulator, TILE_TOP_ROW, top_row);
+    set_tile_rows(&mut emulator, TILE_SOLID_1, [(0xFF, 0x00); TILE_HEIGHT]);
+    set_tile_rows(&mut emulator, TILE_SOLID_2, [(0x00, 0xFF); TILE_HEIGHT]);
+
+    emulator.reg_mut::<RegisterBGP>().0 = 0b11_10_01_00;
+    emulator.reg_mut::<RegisterOBP0>().0 = 0b11_10_01_00;
+    emulator.reg_mut::<RegisterOBP1>().0 = 0b00_01_10_11;
+    emulator.reg_mut::<RegisterLCDC>().set_object_enable(true);
+
+    emulator
+}
+
+#[test]
+fn test_objects_position_and_palettes() {
+    let mut emulator = emulator();
+    set_object(&mut emulator, 0, 20, 10, TILE_LEFT_HALF, 0);
+    set_object(
+        &mut emulator,
+        1,
+        40,
+        10,
+        TILE_LEFT_HALF,
+        ObjectAttributes::DMG_PALETTE,
+    );
+
+    run_frame(&mut emulator);
+
+    assert_eq!(
+        row(&emulator.screen, 19..25, 10),
+        [WHITE, LIGHT, LIGHT, LIGHT, LIGHT, WHITE]
+    );
+    assert_eq!(emulator.screen.get_pixel(20, 9), WHITE);
+    assert_eq!(emulator.screen.get_pixel(20, 17), LIGHT);
+    assert_eq!(emulator.screen.get_pixel(20, 18), WHITE);
+    // OBP1
+    assert_eq!(
+        row(&emulator.screen, 39..45, 10),
+        [WHITE, DARK, DARK, DARK, DARK, WHITE]
+    );
+
+    // objects are hidden if disabled
+    emulator.reg_mut::<RegisterLCDC>().set_object_enable(false);
+    run_frame(&mut emulator);
+    assert_eq!(emulator.screen.get_pixel(20, 10), WHITE);
+}
+
+#[test]
+fn test_objects_partially_off_screen() {
+    let mut emulator = emulator();
+    // X = 4 and Y = 12 in OAM
+    emulator.oam[..OBJECT_ENTRY_SIZE].copy_from_slice(&[12, 4, BLACK_TILE, 0]);
+
+    run_frame(&mut emulator);
+
+    assert_eq!(
+        row(&emulator.screen, 0..5, 0),
+        [BLACK, BLACK, BLACK, BLACK, WHITE]
+    );
+    assert_eq!(emulator.screen.get_pixel(0, 3), BLACK);
+    assert_eq!(emulator.screen.get_pixel(0, 4), WHITE);
+}
+
+#[test]
+fn test_objects_flip() {
+    let mut emulator = emulator();
+    set_object(
+        &mut emulator,
+        0,
+        20,
+        10,
+        TILE_LEFT_HALF,
+        ObjectAttributes::X_FLIP,
+    );
+    set_object(
+        &mut emulator,
+        1,
+        40,
+        10,
+        TILE_TOP_ROW,
+        ObjectAttributes::Y_FLIP,
+    );
+
+    run_frame(&mut emulator);
+
+    assert_eq!(
+        row(&emulator.screen, 20..28, 10),
+        [WHITE, WHITE, WHITE, WHITE, LIGHT, LIGHT, LIGHT, LIGHT]
+    );
+    assert_eq!(emulator.screen.get_pixel(40, 10), WHITE);
+    assert_eq!(emulator.screen.get_pixel(40, 17), DARK);
+}
+
+#[test]
+fn test_objects_8x16() {
+    let mut emulator = emulator();
+    emulator.reg_mut::<RegisterLCDC>().set_object_size(true);
+    // bit 0 of the tile index is ignored
+    set_object(&mut emulator, 0, 20, 10, TILE_SOLID_2, 0);
+    set_object(
+        &mut emulator,
+        1,
+        40,
+        10,
+        TILE_SOLID_1,
+        ObjectAttributes::Y_FLIP,
+    );
+
+    run_frame(&mut emulator);
+
+    assert_eq!(emulator.screen.get_pixel(20, 10), LIGHT);
+    assert_eq!(emulator.screen.get_pixel(20, 17), LIGHT);
+    assert_eq!(emulator.screen.get_pixel(20, 18), DARK);
+    assert_eq!(emulator.screen.get_pixel(20, 25), DARK);
+    assert_eq!(emulator.screen.get_pixel(20, 26), WHITE);
+
+    // Y flip swaps the tiles
+    assert_eq!(emulator.screen.get_pixel(40, 10), DARK);
+    assert_eq!(emulator.screen.get_pixel(40, 25), LIGHT);
+}
+
+#[test]
+fn test_objects_per_line_limit() {
+    let mut emulator = emulator();
+    // objects outside of the screen horizontally count to the limit too
+    set_object(&mut emulator, 0, 200, 10, BLACK_TILE, 0);
+    for oam_index in 1..=10 {
+        set_object(&mut emulator, oam_index, oam_index * 10, 10, BLACK_TILE, 0);
+    }
+    // other lines are not affected by the limit
+    set_object(&mut emulator, 11, 110, 20, BLACK_TILE, 0);
+
+    run_frame(&mut emulator);
+
+    for oam_index in 1..10 {
+        assert_eq!(
+            emulator.screen.get_pixel(oam_index * 10, 10),
+            BLACK,
+            "{oam_index}"
+        );
+    }
+    assert_eq!(emulator.screen.get_pixel(100, 10), WHITE);
+    assert_eq!(emulator.screen.get_pixel(110, 20), BLACK);
+}
+
+#[test]
+fn test_objects_priority_dmg() {
+    let mut emulator = emulator();
+    set_object(&mut emulator, 0, 22, 10, BLACK_TILE, 0);
+    set_object(&mut emulator, 1, 20, 10, TILE_SOLID_1, 0);
+    // same X, the smaller OAM index wins
+    set_object(&mut emulator, 2, 60, 10, TILE_SOLID_1, 0);
+    set_object(&mut emulator, 3, 60, 10, BLACK_TILE, 0);
+    // transparent pixels show the object behind
+    set_object(&mut emulator, 4, 80, 10, TILE_LEFT_HALF, 0);
+    set_object(&mut emulator, 5, 81, 10, BLACK_TILE, 0);
+    // both reached at the left edge, the smaller X wins over the OAM order
+    set_object(&mut emulator, 6, 0, 10, BLACK_TILE, 0);
+    set_object(&mut emulator, 7, 0, 10, TILE_SOLID_1, 0);
+    emulator.oam[7 * OBJECT_ENTRY_SIZE + 1] = 4;
+
+    run_frame(&mut emulator);
+
+    // smaller X wins
+    assert_eq!(
+        row(&emulator.screen, 19..31, 10),
+        [WHITE, LIGHT, LIGHT, LIGHT, LIGHT, LIGHT, LIGHT, LIGHT, LIGHT, BLACK, BLACK, WHITE]
+    );
+    assert_eq!(emulator.screen.get_pixel(60, 10), LIGHT);
+    assert_eq!(
+        row(&emulator.screen, 80..86, 10),
+        [LIGHT, LIGHT, LIGHT, LIGHT, BLACK, BLACK]
+    );
+    assert_eq!(
+        row(&emulator.screen, 0..9, 10),
+        [LIGHT, LIGHT, LIGHT, LIGHT, BLACK, BLACK, BLACK, BLACK, WHITE]
+    );
+}
+
+#[test]
+fn test_objects_priority_cgb() {
+    let mut emulator = emulator();
+    emulator.cgb_mode = true;
+    set_object(&mut emulator, 0, 22, 10, BLACK_TILE, 0);
+    set_object(&mut emulator, 1, 20, 10, TILE_SOLID_1, 0);
+
+    run_frame(&mut emulator);
+
+    // smaller OAM index wins
+    assert_eq!(row(&emulator.screen, 20..23, 10), [LIGHT, LIGHT, BLACK]);
+    assert_eq!(emulator.screen.get_pixel(29, 10), BLACK);
+}
+
+#[test]
+fn test_objects_bg_priority() {
+    let mut emulator = emulator();
+    // background tile of color index 1 at the left half of the object
+    let tile_map = MEMORY_RANGE_TILE_INDICES_BANK0.start - MEMORY_RANGE_VRAM.start;
+    emulator.vram[0][tile_map + tile_map_cords_to_position(2, 1)] = TILE_SOLID_1;
+    set_object(
+        &mut emulator,
+        0,
+        20,
+        8,
+        BLACK_TILE,
+        ObjectAttributes::PRIORITY,
+    );
+    set_object(&mut emulator, 1, 40, 8, BLACK_TILE, 0);
+    emulator.vram[0][tile_map + tile_map_cords_to_position(5, 1)] = TILE_SOLID_1;
+
+    run_frame(&mut emulator);
+
+    // BG color 1-3 is drawn over the object, BG color 0 is not
+    assert_eq!(emulator.screen.get_pixel(20, 8), LIGHT);
+    assert_eq!(row(&emulator.screen, 23..25, 8), [LIGHT, BLACK]);
+    // object without the flag is drawn over the BG
+    assert_eq!(emulator.screen.get_pixel(40, 8), BLACK);
+
+    // blank background in non CGB mode behaves as color 0
+    emulator
+        .reg_mut::<RegisterLCDC>()
+        .set_bg_and_window_enable(false);
+    run_frame(&mut emulator);
+    assert_eq!(emulator.screen.get_pixel(20, 8), BLACK);
+
+    // in CGB mode objects are drawn on top if BG priority is disabled
+    emulator.cgb_mode = true;
+    run_frame(&mut emulator);
+    assert_eq!(emulator.screen.get_pixel(20, 8), BLACK);
+    emulator
+        .reg_mut::<RegisterLCDC>()
+        .set_bg_and_window_enable(true);
+    run_frame(&mut emulator);
+    assert_eq!(emulator.screen.get_pixel(20, 8), LIGHT);
+}
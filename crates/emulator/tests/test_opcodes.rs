@@ -55,43 +55,62 @@ struct TestData {
 
 impl TestData {
     fn execute_test(self, index: usize, test_opcode: u8, test_path: &str) {
-        let mut emulator = Emulator::default();
-        emulator.instruction_register = test_opcode;
-        emulator.disable_registers_update = true;
+        // tests expect flat 64 KiB RAM without any memory mapped hardware
+        let mut cpu = Cpu::new();
+        let mut bus = FlatBus::new();
+        cpu.instruction_register = test_opcode;
+        bus.bus_log = Some(vec![]);
 
-        self.initial_state.set_to(&mut emulator);
+        self.initial_state.set_to(&mut cpu, &mut bus);
 
         let location = format!("{} [{}]", test_path, index);
 
         let mut last_cycle = 0;
         let m_cycles_count = self.cycles.len();
-        while emulator.cycles < m_cycles_count {
-            let opcode = emulator.instruction_register;
-            let instruction = emulator.handle_next_instruction();
+        while bus.cycles < m_cycles_count {
+            let opcode = cpu.instruction_register;
+            let instruction = cpu.handle_next_instruction(&mut bus);
 
-            let hl = emulator.register_hl.as_u16();
+            let hl = cpu.register_hl.as_u16();
             println!("{instruction:?} ({opcode:02X}) HL: {hl} ({hl:04X})");
 
-            if emulator.cycles > m_cycles_count {
+            if bus.cycles > m_cycles_count {
                 panic!(
                 "unexpected end of test at {}.cycles: M-Cycles expected {}, but CPU executed {}",
-                location, m_cycles_count, emulator.cycles
+                location, m_cycles_count, bus.cycles
             );
             }
-            for _cycle in &self.cycles[last_cycle..emulator.cycles] {
-                // TODO check bus activity
+            let bus_log = bus.bus_log.as_ref().unwrap();
+            for (i, cycle) in self.cycles[last_cycle..bus.cycles].iter().enumerate() {
+                let cycle_index = last_cycle + i;
+                let expected = match cycle {
+                    Some((address, value, Some(CycleAction::Read))) => BusActivity::Read {
+                        address: *address,
+                        value: *value,
+                    },
+                    Some((address, value, Some(CycleAction::Write))) => BusActivity::Write {
+                        address: *address,
+                        value: *value,
+                    },
+                    Some((_, _, None)) | None => BusActivity::Idle,
+                };
+
+                assert_eq!(
+                    bus_log[cycle_index], expected,
+                    "bus activity mismatch at {location}.cycles[{cycle_index}]",
+                );
             }
 
-            last_cycle = emulator.cycles;
+            last_cycle = bus.cycles;
         }
 
         assert_eq!(
-            emulator.cycles, m_cycles_count,
+            bus.cycles, m_cycles_count,
             "expected {} cycles, but CPU executed {}",
-            m_cycles_count, emulator.cycles
+            m_cycles_count, bus.cycles
         );
 
-        self.final_state.check(&emulator, &location);
+        self.final_state.check(&cpu, &bus, &location);
 
         println!("{} passed", location);
     }
@@ -121,76 +140,76 @@ struct EmulatorTestState {
 }
 
 impl EmulatorTestState {
-    fn set_to(self, emulator: &mut Emulator) {
-        emulator.accumulator_and_flags.set_high(self.a);
-        emulator.accumulator_and_flags.set_low(self.f);
-        emulator.register_bc.set_high(self.b);
-        emulator.register_bc.set_low(self.c);
-        emulator.register_de.set_high(self.d);
-        emulator.register_de.set_low(self.e);
-        emulator.register_hl.set_high(self.h);
-        emulator.register_hl.set_low(self.l);
-        emulator.program_counter = self.pc.into();
-        emulator.stack_pointer = self.sp.into();
+    fn set_to(self, cpu: &mut Cpu, bus: &mut FlatBus) {
+        cpu.accumulator_and_flags.set_high(self.a);
+        cpu.accumulator_and_flags.set_low(self.f);
+        cpu.register_bc.set_high(self.b);
+        cpu.register_bc.set_low(self.c);
+        cpu.register_de.set_high(self.d);
+        cpu.register_de.set_low(self.e);
+        cpu.register_hl.set_high(self.h);
+        cpu.register_hl.set_low(self.l);
+        cpu.program_counter = self.pc.into();
+        cpu.stack_pointer = self.sp.into();
 
         for (address, value) in self.ram {
-            emulator.set(address, value);
+            bus.write(address, value);
         }
     }
 
-    fn check(self, emulator: &Emulator, location: &str) {
+    fn check(self, cpu: &Cpu, bus: &FlatBus, location: &str) {
         assert_eq!(
-            emulator.accumulator_and_flags.high(),
+            cpu.accumulator_and_flags.high(),
             self.a,
             "mismatch value at {location}.final.a",
         );
         assert_eq!(
-            emulator.accumulator_and_flags.low(),
+            cpu.accumulator_and_flags.low(),
             self.f,
             "mismatch value at {location}.final.f",
         );
         assert_eq!(
-            emulator.register_bc.high(),
+            cpu.register_bc.high(),
             self.b,
             "mismatch value at {location}.final.b",
         );
         assert_eq!(
-            emulator.register_bc.low(),
+            cpu.register_bc.low(),
             self.c,
             "mismatch value at {location}.final.c",
         );
         assert_eq!(
-            emulator.register_de.high(),
+            cpu.register_de.high(),
             self.d,
             "mismatch value at {location}.final.d",
         );
         assert_eq!(
-            emulator.register_de.low(),
+            cpu.register_de.low(),
             self.e,
             "mismatch value at {location}.final.e",
         );
         assert_eq!(
-            emulator.register_hl.high(),
+            cpu.register_hl.high(),
             self.h,
             "mismatch value at {location}.final.h",
         );
         assert_eq!(
-            emulator.register_hl.low(),
+            cpu.register_hl.low(),
             self.l,
             "mismatch value at {location}.final.l",
         );
         assert_eq!(
-            emulator.program_counter.0, self.pc,
+            cpu.program_counter.0, self.pc,
             "mismatch value at {location}.final.pc",
         );
         assert_eq!(
-            emulator.stack_pointer.0, self.sp,
+            cpu.stack_pointer.0, self.sp,
             "mismatch value at {location}.final.sp",
         );
 
         for (i, (address, value)) in self.ram.into_iter().enumerate() {
             assert_eq!(
-                emulator.get(address),
+                bus.peek(address),
                 value,
                 "memory at address {address}({address:04X}) mismatch {location}.final.ram[{i}]"
             );
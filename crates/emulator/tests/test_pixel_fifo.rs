@@ -0,0 +1,173 @@
+use emulator::*;
+
+mod common;
+use common::*;
+
+/// Dot of mode 3 in which the first pixel is sent to the LCD, after the
+/// discarded startup fetch and the fetch of the first tile
+const FIRST_PIXEL_DOT: usize = 13;
+
+/// Emulator with the solid tile at index 1, used by the tile map columns for
+/// which `is_black` returns true
+fn emulator(is_black: impl Fn(usize) -> bool) -> Emulator {
+    let mut emulator = Emulator::default();
+    let tile_address = TILE_SIZE * BLACK_TILE as usize;
+    emulator.vram[0][tile_address..tile_address + TILE_SIZE].fill(0xFF);
+
+    let tile_map = MEMORY_RANGE_TILE_INDICES_BANK0.start;
+    for y in 0..TILE_MAP_HEIGHT {
+        for x in 0..TILE_MAP_WIDTH {
+            let address = tile_map + tile_map_cords_to_position(x, y);
+            emulator.vram[0][address - MEMORY_RANGE_VRAM.start] =
+                if is_black(x) { BLACK_TILE } else { 0 };
+        }
+    }
+
+    emulator
+}
+
+/// Duration of mode 3 of scanline 10 in the next frame
+fn line_10_mode_3_duration(emulator: &mut Emulator) -> usize {
+    run_frame(emulator);
+    run_until_line(emulator, 11, PpuMode::Mode2);
+
+    emulator.mode_3_duration
+}
+
+/// Step the PPU until it enters mode 3 of the scanline `ly`
+fn enter_mode_3(emulator: &mut Emulator, ly: u8) {
+    run_until_line(emulator, ly, PpuMode::Mode2);
+    while emulator.reg::<RegisterSTAT>().get_ppu_mode() != PpuMode::Mode3 {
+        emulator.handle_dot();
+    }
+}
+
+fn run_dots(emulator: &mut Emulator, dots: usize) {
+    for _ in 0..dots {
+        emulator.handle_dot();
+    }
+}
+
+fn finish_mode_3(emulator: &mut Emulator) {
+    while emulator.reg::<RegisterSTAT>().get_ppu_mode() == PpuMode::Mode3 {
+        emulator.handle_dot();
+    }
+}
+
+#[test]
+fn test_pixel_fifo_mode_3_duration() {
+    let mut emulator = emulator(|_| false);
+    assert_eq!(line_10_mode_3_duration(&mut emulator), MODE_3_BASE_DURATION);
+
+    // fine scroll discards pixels at the start of the scanline
+    emulator.reg_mut::<RegisterSCX>().0 = 3;
+    assert_eq!(
+        line_10_mode_3_duration(&mut emulator),
+        MODE_3_BASE_DURATION + 3
+    );
+    emulator.reg_mut::<RegisterSCX>().0 = 8;
+    assert_eq!(line_10_mode_3_duration(&mut emulator), MODE_3_BASE_DURATION);
+    emulator.reg_mut::<RegisterSCX>().0 = 0;
+
+    // fetcher restarts on the window
+    emulator.reg_mut::<RegisterWX>().0 = 80 + WINDOW_X_OFFSET as u8;
+    emulator.reg_mut::<RegisterLCDC>().set_window_enable(true);
+    assert_eq!(
+        line_10_mode_3_duration(&mut emulator),
+        MODE_3_BASE_DURATION + 6
+    );
+    emulator.reg_mut::<RegisterLCDC>().set_window_enable(false);
+}
+
+#[test]
+fn test_pixel_fifo_object_penalty() {
+    let mut emulator = emulator(|_| false);
+    emulator.reg_mut::<RegisterLCDC>().set_object_enable(true);
+
+    // object aligned with the background tile waits for the whole tile fetch
+    set_object(&mut emulator, 0, 40, 10, BLACK_TILE, 0);
+    assert_eq!(
+        line_10_mode_3_duration(&mut emulator),
+        MODE_3_BASE_DURATION + 11
+    );
+
+    // tile data is already fetched at the end of the background tile
+    set_object(&mut emulator, 0, 46, 10, BLACK_TILE, 0);
+    assert_eq!(
+        line_10_mode_3_duration(&mut emulator),
+        MODE_3_BASE_DURATION + 6
+    );
+
+    // object at the same position is fetched right after the first one
+    set_object(&mut emulator, 1, 46, 10, BLACK_TILE, 0);
+    assert_eq!(
+        line_10_mode_3_duration(&mut emulator),
+        MODE_3_BASE_DURATION + 12
+    );
+
+    // object left of the screen is fetched too
+    set_object(&mut emulator, 1, 0, 10, BLACK_TILE, 0);
+    emulator.oam[OBJECT_ENTRY_SIZE + 1] = 0;
+    assert_eq!(
+        line_10_mode_3_duration(&mut emulator),
+        MODE_3_BASE_DURATION + 17
+    );
+
+    // objects are not fetched while disabled
+    emulator.reg_mut::<RegisterLCDC>().set_object_enable(false);
+    assert_eq!(line_10_mode_3_duration(&mut emulator), MODE_3_BASE_DURATION);
+}
+
+#[test]
+fn test_pixel_fifo_mid_scanline_bgp() {
+    let mut emulator = emulator(|_| true);
+
+    enter_mode_3(&mut emulator, 10);
+    run_dots(&mut emulator, FIRST_PIXEL_DOT + 50);
+    emulator.reg_mut::<RegisterBGP>().0 = 0x00;
+    finish_mode_3(&mut emulator);
+
+    assert_eq!(
+        row(&emulator.screen_buffer, 49..53, 10),
+        [BLACK, BLACK, WHITE, WHITE]
+    );
+    assert_eq!(
+        emulator.screen_buffer.get_pixel(SCREEN_WIDTH - 1, 10),
+        WHITE
+    );
+}
+
+#[test]
+fn test_pixel_fifo_mid_scanline_lcdc() {
+    let mut emulator = emulator(|_| true);
+
+    enter_mode_3(&mut emulator, 10);
+    run_dots(&mut emulator, FIRST_PIXEL_DOT + 99);
+    emulator
+        .reg_mut::<RegisterLCDC>()
+        .set_bg_and_window_enable(false);
+    finish_mode_3(&mut emulator);
+
+    assert_eq!(
+        row(&emulator.screen_buffer, 98..102, 10),
+        [BLACK, BLACK, WHITE, WHITE]
+    );
+}
+
+#[test]
+fn test_pixel_fifo_mid_scanline_scx() {
+    // tile map columns alternate black and white
+    let mut emulator = emulator(|x| x % 2 == 0);
+
+    enter_mode_3(&mut emulator, 10);
+    run_dots(&mut emulator, FIRST_PIXEL_DOT + 20);
+    emulator.reg_mut::<RegisterSCX>().0 = 8;
+    finish_mode_3(&mut emulator);
+
+    // tiles fetched before the write keep the old scroll, the next tile is
+    // read one column further
+    assert_eq!(row(&emulator.screen_buffer, 16..17, 10), [BLACK]);
+    assert_eq!(row(&emulator.screen_buffer, 23..25, 10), [BLACK, WHITE]);
+    assert_eq!(row(&emulator.screen_buffer, 31..33, 10), [WHITE, WHITE]);
+    assert_eq!(row(&emulator.screen_buffer, 39..41, 10), [WHITE, BLACK]);
+}
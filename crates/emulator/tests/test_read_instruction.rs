@@ -1,24 +1,28 @@
-use emulator::{ArgumentN16, ArgumentR16, Emulator, Instruction, InstructionLD, InstructionNOP};
+use emulator::{
+    ArgumentN16, ArgumentR16, ArgumentR8, Emulator, Instruction, InstructionBIT, InstructionLD,
+    InstructionNOP,
+};
 
 #[test]
 fn test_read_instruction() {
     let mut emulator = Emulator::default();
 
-    emulator.set(0x0150, 0x31); // LD sp, n16
+    // ROM is read-only for the CPU, so patch it directly
+    emulator.set_force(0x0150, 0x31); // LD sp, n16
 
     // n16 = 0x1234, stored in little-endian format
-    emulator.set(0x0151, 0x34);
-    emulator.set(0x0152, 0x12);
-    emulator.set(0x0153, 0x10);
+    emulator.set_force(0x0151, 0x34);
+    emulator.set_force(0x0152, 0x12);
+    emulator.set_force(0x0153, 0x10);
 
-    emulator.program_counter = 0x0150.into();
+    emulator.cpu.program_counter = 0x0150.into();
 
     // first instruction is always NOP because during it CPU reads the opcode
     // of the actual first instruction
     let instruction = emulator.handle_next_instruction();
     assert_eq!(instruction, InstructionNOP.into());
 
-    assert_eq!(emulator.program_counter.0, 0x0151);
+    assert_eq!(emulator.cpu.program_counter.0, 0x0151);
 
     let instruction = emulator.handle_next_instruction();
     let expected_instruction =
@@ -27,6 +31,45 @@ fn test_read_instruction() {
     assert_eq!(instruction, expected_instruction);
 
     // Next instruction already loaded to IR register and PC incremented
-    assert_eq!(emulator.instruction_register, 0x10);
-    assert_eq!(emulator.program_counter.0, 0x0154);
+    assert_eq!(emulator.cpu.instruction_register, 0x10);
+    assert_eq!(emulator.cpu.program_counter.0, 0x0154);
+}
+
+#[test]
+fn test_decode_at() {
+    let mut emulator = Emulator::default();
+
+    let program = [
+        0x31, 0x34, 0x12, // LD sp, $1234
+        0xCB, 0x7C, // BIT 7, h
+        0x00, // NOP
+        0xD3, // illegal
+    ];
+    for (i, byte) in program.iter().enumerate() {
+        emulator.set(0xC000 + i as u16, *byte);
+    }
+
+    let program_counter = emulator.cpu.program_counter.0;
+    let cycles = emulator.cycles;
+
+    assert_eq!(
+        Instruction::decode_at(&emulator, 0xC000),
+        Some((
+            Instruction::LD(InstructionLD::R16_N16(ArgumentR16::SP, ArgumentN16(0x1234))),
+            3
+        ))
+    );
+    assert_eq!(
+        Instruction::decode_at(&emulator, 0xC003),
+        Some((Instruction::BIT(InstructionBIT(7.into(), ArgumentR8::H)), 2))
+    );
+    assert_eq!(
+        Instruction::decode_at(&emulator, 0xC005),
+        Some((InstructionNOP.into(), 1))
+    );
+    assert_eq!(Instruction::decode_at(&emulator, 0xC006), None);
+
+    // emulator state is not affected
+    assert_eq!(emulator.cpu.program_counter.0, program_counter);
+    assert_eq!(emulator.cycles, cycles);
 }
@@ -0,0 +1,162 @@
+use std::path::PathBuf;
+use std::sync::Arc;
+
+use emulator::*;
+
+mod common;
+use common::*;
+
+fn cartridge(cartridge_type: CartridgeType, ram_size_code: u8) -> Rom {
+    let mut data = vec![0; ROM_MIN_SIZE];
+    data[ROM_ADDRESS_CARTRIDGE_TYPE] = cartridge_type.code();
+    data[ROM_ADDRESS_RAM_SIZE] = ram_size_code;
+
+    Rom::from(data)
+}
+
+/// Unique path of the save file in the temporary directory
+fn save_file(name: &str) -> PathBuf {
+    let path = std::env::temp_dir().join(format!("gameboy-rs-{}-{name}.sav", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    path
+}
+
+#[test]
+fn test_save_ram_file() {
+    let path = save_file("file");
+
+    let mut emulator =
+        Emulator::from_rom(cartridge(CartridgeType::Mbc1RamBattery, 0x02)).with_save_file(&path);
+    assert!(emulator.rom.has_battery);
+
+    // no save yet
+    emulator.load_save_ram().unwrap();
+
+    // ignored while the RAM is disabled
+    emulator.set(0xA000, 0x42);
+    assert!(!emulator.rom.ram_modified);
+
+    emulator.set(0x0000, 0x0A);
+    emulator.set(0xA000, 0x42);
+    emulator.set(0xBFFF, 0x24);
+    assert!(emulator.rom.ram_modified);
+
+    emulator.save_ram().unwrap();
+    assert!(!emulator.rom.ram_modified);
+    assert_eq!(std::fs::read(&path).unwrap().len(), 0x2000);
+
+    let mut emulator =
+        Emulator::from_rom(cartridge(CartridgeType::Mbc1RamBattery, 0x02)).with_save_file(&path);
+    emulator.load_save_ram().unwrap();
+    emulator.set(0x0000, 0x0A);
+    assert_eq!(emulator.get(0xA000), 0x42);
+    assert_eq!(emulator.get(0xBFFF), 0x24);
+
+    std::fs::remove_file(&path).unwrap();
+}
+
+#[test]
+fn test_save_ram_without_battery() {
+    let path = save_file("without-battery");
+
+    let mut emulator = Emulator::from_rom(cartridge(CartridgeType::Mbc1Ram, 0x02));
+    assert!(matches!(emulator.save_ram(), Err(SaveRamError::NoSaveFile)));
+
+    emulator.set_save_file(&path);
+    emulator.save_ram().unwrap();
+    assert!(!path.exists());
+    assert_eq!(emulator.rom.save_data(), None);
+}
+
+#[test]
+fn test_save_ram_size_mismatch() {
+    let mut rom = cartridge(CartridgeType::Mbc1RamBattery, 0x02);
+
+    assert!(matches!(
+        rom.load_save_data(&[0; 0x1000]),
+        Err(SaveRamError::SizeMismatch {
+            expected: 0x2000,
+            actual: 0x1000
+        })
+    ));
+
+    // no RTC, extra data is not allowed
+    assert!(matches!(
+        rom.load_save_data(&[0; 0x2000 + RTC_SAVE_FOOTER_SIZE]),
+        Err(SaveRamError::SizeMismatch { .. })
+    ));
+}
+
+#[test]
+fn test_save_ram_rtc_footer() {
+    let clock = FakeClock::default();
+    clock.advance(1_000_000);
+
+    let mut rom = cartridge(CartridgeType::Mbc3TimerRamBattery, 0x02);
+    let rtc = rom.mapper.rtc_mut().unwrap();
+    rtc.set_clock(Arc::new(clock.clone()));
+    rtc.registers = [10, 20, 3, 0xFF, RTC_DAY_HIGH_DAY];
+    rtc.latched = [1, 2, 3, 4, 0];
+    rom.ram[0] = 0x42;
+
+    let data = rom.save_data().unwrap();
+    assert_eq!(data.len(), 0x2000 + RTC_SAVE_FOOTER_SIZE);
+    assert_eq!(data[0], 0x42);
+    let footer = &data[0x2000..];
+    assert_eq!(footer[..8], [10, 0, 0, 0, 20, 0, 0, 0]);
+    assert_eq!(footer[16..20], [0x01, 0, 0, 0]);
+    assert_eq!(footer[20..24], [1, 0, 0, 0]);
+    assert_eq!(footer[40..], 1_000_000u64.to_le_bytes());
+
+    // time passed since the save is added on load
+    clock.advance(86400 + 5);
+    let mut rom = cartridge(CartridgeType::Mbc3TimerRamBattery, 0x02);
+    rom.mapper
+        .rtc_mut()
+        .unwrap()
+        .set_clock(Arc::new(clock.clone()));
+    rom.load_save_data(&data).unwrap();
+
+    let rtc = rom.mapper.rtc().unwrap();
+    assert_eq!(rom.ram[0], 0x42);
+    assert_eq!(rtc.registers, [15, 20, 3, 0x00, RTC_DAY_HIGH_CARRY]);
+    assert_eq!(rtc.latched, [1, 2, 3, 4, 0]);
+
+    // footer with 32-bit timestamp
+    let mut short_data = data[..0x2000 + RTC_SAVE_FOOTER_SIZE_SHORT].to_vec();
+    short_data[0x2000 + 40..].copy_from_slice(&1_000_000u32.to_le_bytes());
+    let mut rom = cartridge(CartridgeType::Mbc3TimerRamBattery, 0x02);
+    rom.mapper
+        .rtc_mut()
+        .unwrap()
+        .set_clock(Arc::new(clock.clone()));
+    rom.load_save_data(&short_data).unwrap();
+    assert_eq!(rom.mapper.rtc().unwrap().registers[RTC_SECONDS], 15);
+
+    // footer is optional
+    let mut rom = cartridge(CartridgeType::Mbc3TimerRamBattery, 0x02);
+    rom.load_save_data(&data[..0x2000]).unwrap();
+    assert_eq!(rom.ram[0], 0x42);
+}
+
+#[test]
+fn test_autosave() {
+    let path = save_file("autosave");
+
+    let mut emulator =
+        Emulator::from_rom(cartridge(CartridgeType::Mbc1RamBattery, 0x02)).with_save_file(&path);
+    emulator.autosave_interval = Some(1);
+
+    // RAM is not modified, nothing to save
+    emulator.run_until(|emulator| emulator.frame_count == 1);
+    assert!(!path.exists());
+
+    emulator.set(0x0000, 0x0A);
+    emulator.set(0xA000, 0x42);
+    emulator.run_until(|emulator| emulator.frame_count == 2);
+
+    assert!(emulator.autosave_error.is_none());
+    assert_eq!(std::fs::read(&path).unwrap()[0], 0x42);
+
+    std::fs::remove_file(&path).unwrap();
+}
@@ -0,0 +1,152 @@
+use emulator::*;
+
+mod common;
+use common::*;
+
+/// Emulator with the solid tile at index 1 and both tile maps filled with
+/// tile 0 (color index 0)
+fn emulator() -> Emulator {
+    let mut emulator = Emulator::default();
+    let tile_address = TILE_SIZE * BLACK_TILE as usize;
+    emulator.vram[0][tile_address..tile_address + TILE_SIZE].fill(0xFF);
+
+    emulator
+}
+
+/// Set tile index in the tile map starting at `tile_map`
+fn set_tile(emulator: &mut Emulator, tile_map: usize, x: usize, y: usize, tile_index: u8) {
+    let address = tile_map + tile_map_cords_to_position(x, y);
+    emulator.vram[0][address - MEMORY_RANGE_VRAM.start] = tile_index;
+}
+
+#[test]
+fn test_scanline_background_scroll() {
+    let mut emulator = emulator();
+    let tile_map = MEMORY_RANGE_TILE_INDICES_BANK0.start;
+    set_tile(&mut emulator, tile_map, 1, 0, BLACK_TILE);
+    set_tile(&mut emulator, tile_map, 31, 31, BLACK_TILE);
+
+    run_frame(&mut emulator);
+    assert_eq!(emulator.screen.get_pixel(7, 0), WHITE);
+    assert_eq!(emulator.screen.get_pixel(8, 0), BLACK);
+    assert_eq!(emulator.screen.get_pixel(15, 7), BLACK);
+    assert_eq!(emulator.screen.get_pixel(16, 7), WHITE);
+    assert_eq!(emulator.screen.get_pixel(8, 8), WHITE);
+
+    emulator.reg_mut::<RegisterSCX>().0 = 4;
+    emulator.reg_mut::<RegisterSCY>().0 = 2;
+    run_frame(&mut emulator);
+    assert_eq!(emulator.screen.get_pixel(3, 0), WHITE);
+    assert_eq!(emulator.screen.get_pixel(4, 0), BLACK);
+    assert_eq!(emulator.screen.get_pixel(11, 5), BLACK);
+    assert_eq!(emulator.screen.get_pixel(11, 6), WHITE);
+
+    // background wraps around the tile map
+    emulator.reg_mut::<RegisterSCX>().0 = 252;
+    emulator.reg_mut::<RegisterSCY>().0 = 252;
+    run_frame(&mut emulator);
+    assert_eq!(emulator.screen.get_pixel(3, 3), BLACK);
+    assert_eq!(emulator.screen.get_pixel(4, 3), WHITE);
+    assert_eq!(emulator.screen.get_pixel(3, 4), WHITE);
+    assert_eq!(emulator.screen.get_pixel(12, 4), BLACK);
+}
+
+#[test]
+fn test_scanline_window() {
+    let mut emulator = emulator();
+    for x in 0..TILE_MAP_WIDTH {
+        for y in 0..TILE_MAP_HEIGHT {
+            set_tile(
+                &mut emulator,
+                MEMORY_RANGE_TILE_INDICES_BANK1.start,
+                x,
+                y,
+                BLACK_TILE,
+            );
+        }
+    }
+
+    let lcdc = emulator.reg_mut::<RegisterLCDC>();
+    lcdc.set_win_tile_map(true);
+    lcdc.set_window_enable(true);
+    emulator.reg_mut::<RegisterWX>().0 = 80 + WINDOW_X_OFFSET as u8;
+    emulator.reg_mut::<RegisterWY>().0 = 72;
+
+    run_frame(&mut emulator);
+    assert_eq!(emulator.screen.get_pixel(79, 72), WHITE);
+    assert_eq!(emulator.screen.get_pixel(80, 72), BLACK);
+    assert_eq!(emulator.screen.get_pixel(80, 71), WHITE);
+    assert_eq!(emulator.screen.get_pixel(159, 143), BLACK);
+
+    // WX above 166 hides the window
+    emulator.reg_mut::<RegisterWX>().0 = 167;
+    run_frame(&mut emulator);
+    assert_eq!(emulator.screen.get_pixel(159, 143), WHITE);
+
+    // window is hidden together with the background
+    emulator.reg_mut::<RegisterWX>().0 = 0;
+    emulator
+        .reg_mut::<RegisterLCDC>()
+        .set_bg_and_window_enable(false);
+    run_frame(&mut emulator);
+    assert_eq!(emulator.screen.get_pixel(100, 100), WHITE);
+}
+
+#[test]
+fn test_scanline_window_line_counter() {
+    let mut emulator = emulator();
+    // only the first tile row of the window is black
+    for x in 0..TILE_MAP_WIDTH {
+        set_tile(
+            &mut emulator,
+            MEMORY_RANGE_TILE_INDICES_BANK1.start,
+            x,
+            0,
+            BLACK_TILE,
+        );
+    }
+
+    let lcdc = emulator.reg_mut::<RegisterLCDC>();
+    lcdc.set_win_tile_map(true);
+    lcdc.set_window_enable(true);
+    emulator.reg_mut::<RegisterWX>().0 = WINDOW_X_OFFSET as u8;
+    run_frame(&mut emulator);
+
+    // window is disabled for lines 5-19, it continues from its line 5
+    run_until_line(&mut emulator, 4, PpuMode::Mode0);
+    emulator.reg_mut::<RegisterLCDC>().set_window_enable(false);
+    run_until_line(&mut emulator, 20, PpuMode::Mode2);
+    emulator.reg_mut::<RegisterLCDC>().set_window_enable(true);
+    run_frame(&mut emulator);
+
+    assert_eq!(emulator.screen.get_pixel(0, 4), BLACK);
+    assert_eq!(emulator.screen.get_pixel(0, 5), WHITE);
+    assert_eq!(emulator.screen.get_pixel(0, 22), BLACK);
+    assert_eq!(emulator.screen.get_pixel(0, 23), WHITE);
+}
+
+#[test]
+fn test_scanline_palette_and_frame_hand_off() {
+    let mut emulator = emulator();
+
+    emulator.reg_mut::<RegisterBGP>().0 = 0b00_01_10_11;
+    run_frame(&mut emulator);
+    assert_eq!(emulator.screen.get_pixel(0, 0), BLACK);
+
+    // finished frame is kept until the next VBlank
+    emulator.reg_mut::<RegisterBGP>().0 = 0b11_10_01_00;
+    run_until_line(&mut emulator, 100, PpuMode::Mode0);
+    assert_eq!(emulator.screen.get_pixel(0, 0), BLACK);
+    assert_eq!(emulator.screen_buffer.get_pixel(0, 0), WHITE);
+
+    run_frame(&mut emulator);
+    assert_eq!(emulator.screen.get_pixel(0, 143), WHITE);
+
+    // background is blank if disabled in non CGB mode
+    emulator.reg_mut::<RegisterBGP>().0 = 0b00_01_10_11;
+    emulator
+        .reg_mut::<RegisterLCDC>()
+        .set_bg_and_window_enable(false);
+    run_frame(&mut emulator);
+    assert_eq!(emulator.screen.get_pixel(0, 0), WHITE);
+}
@@ -0,0 +1,152 @@
+use emulator::*;
+
+mod common;
+use common::*;
+
+#[test]
+fn test_step_result() {
+    // LD BC, $1234; INC A
+    let mut emulator = emulator_with_program(&[0x01, 0x34, 0x12, 0x3C]);
+
+    let result = emulator.step();
+    assert_eq!(
+        result,
+        StepResult {
+            program_counter: PROGRAM_START,
+            opcode: 0x01,
+            instruction: InstructionLD::R16_N16(ArgumentR16::BC, ArgumentN16(0x1234)).into(),
+            cycles: 3,
+            interrupt: None,
+            frame_completed: false,
+            audio_buffer_completed: false,
+        }
+    );
+
+    let result = emulator.step();
+    assert_eq!(result.program_counter, PROGRAM_START + 3);
+    assert_eq!(result.opcode, 0x3C);
+    assert_eq!(result.cycles, 1);
+}
+
+#[test]
+fn test_step_interrupt() {
+    // NOP
+    let mut emulator = emulator_with_program(&[0x00]);
+    emulator.cpu.ime_flag = true;
+    emulator.reg_mut::<RegisterIE>().set_timer(true);
+    emulator.request_interrupt(Interrupt::Timer);
+
+    let result = emulator.step();
+    assert_eq!(result.instruction, InstructionNOP.into());
+    assert_eq!(result.interrupt, Some(Interrupt::Timer));
+    assert_eq!(result.cycles, 1 + INTERRUPT_DISPATCH_DURATION - 1);
+
+    // next step executes the handler
+    let result = emulator.step();
+    assert_eq!(result.program_counter, TIMER_INTERRUPT);
+    assert_eq!(result.interrupt, None);
+}
+
+#[test]
+fn test_run_for_cycles() {
+    // loop: INC A; JR loop
+    let mut emulator = emulator_with_program(&[0x3C, 0x18, 0xFD]);
+
+    // INC A + JR taken
+    assert_eq!(emulator.run_for_cycles(4 * 10), 4 * 10);
+    assert_eq!(emulator.cpu.accumulator_and_flags.high(), 0x11 + 10);
+
+    // JR is not interrupted in the middle
+    assert_eq!(emulator.run_for_cycles(2), 4);
+}
+
+#[test]
+fn test_run_until() {
+    // loop: INC B; JR loop
+    let mut emulator = emulator_with_program(&[0x04, 0x18, 0xFD]);
+    emulator.cpu.register_bc.set_high(0);
+
+    emulator.run_until(|emulator| emulator.cpu.register_bc.high() == 100);
+    assert_eq!(emulator.cpu.register_bc.high(), 100);
+
+    // predicate is checked before the first step
+    assert_eq!(emulator.run_until(|_| true), 0);
+
+    let frame_count = emulator.frame_count;
+    emulator.run_until(|emulator| emulator.frame_count > frame_count);
+    assert_eq!(emulator.frame_count, frame_count + 1);
+}
+
+#[test]
+fn test_step_frame_completed() {
+    // loop: JR loop
+    let mut emulator = emulator_with_program(&[0x18, 0xFE]);
+
+    let mut steps = 0;
+    while !emulator.step().frame_completed {
+        steps += 1;
+        assert!(steps < 100_000, "frame is not completed");
+    }
+
+    assert!(emulator.is_frame_available);
+    assert_eq!(emulator.frame_count, 1);
+}
+
+#[test]
+fn test_run_stopped() {
+    // STOP
+    let mut emulator = emulator_with_program(&[0x10, 0x00]);
+
+    emulator.run_for_cycles(100);
+    assert_eq!(emulator.cpu.state, CpuState::Stopped);
+
+    // time doesn't pass in STOP mode
+    assert_eq!(emulator.run_for_cycles(100), 0);
+    assert_eq!(emulator.run_until(|_| false), 0);
+}
+
+#[test]
+fn test_step_interrupt_before_vram_dma() {
+    // NOP
+    let mut emulator = emulator_with_program(&[0x00]);
+    emulator.cgb_mode = true;
+    emulator.reg_mut::<RegisterLCDC>().0 = 0;
+    emulator.cpu.ime_flag = true;
+    emulator.reg_mut::<RegisterIE>().set_timer(true);
+    emulator.request_interrupt(Interrupt::Timer);
+
+    assert_eq!(emulator.step().interrupt, Some(Interrupt::Timer));
+
+    // general purpose DMA of a single block
+    emulator.set(RegisterHDMA1::ADDRESS, 0xC0);
+    emulator.set(RegisterHDMA3::ADDRESS, 0x00);
+    emulator.set(RegisterHDMA5::ADDRESS, 0x00);
+    assert!(emulator.is_cpu_suspended());
+
+    // the interrupt is reported only once
+    while emulator.is_cpu_suspended() {
+        assert_eq!(emulator.step().interrupt, None);
+    }
+}
+
+#[test]
+fn test_step_interrupt_before_speed_switch() {
+    // STOP, NOP
+    let mut emulator = emulator_with_program(&[0x10, 0x00]);
+    emulator.cgb_mode = true;
+    emulator.set(RegisterKEY1::ADDRESS, 0x01);
+    emulator.cpu.ime_flag = true;
+    emulator.reg_mut::<RegisterIE>().set_timer(true);
+    emulator.request_interrupt(Interrupt::Timer);
+
+    // interrupt is dispatched right after the STOP which switches the speed
+    let result = emulator.step();
+    assert_eq!(result.instruction, InstructionSTOP.into());
+    assert_eq!(result.interrupt, Some(Interrupt::Timer));
+    assert!(emulator.speed_switch_delay > 0);
+
+    // the interrupt is reported only once
+    while emulator.speed_switch_delay > 0 {
+        assert_eq!(emulator.step().interrupt, None);
+    }
+}
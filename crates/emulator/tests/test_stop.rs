@@ -0,0 +1,78 @@
+use emulator::*;
+
+mod common;
+use common::*;
+
+#[test]
+fn test_stop_until_joypad() {
+    // STOP, NOP, INC A
+    let mut emulator = emulator_with_program(&[0x10, 0x00, 0x3C]);
+    *emulator.internal_timer.as_u16_mut() = 0x1234;
+
+    let instruction = emulator.handle_next_instruction();
+    assert_eq!(instruction, InstructionSTOP.into());
+    assert_eq!(emulator.cpu.state, CpuState::Stopped);
+    assert_eq!(emulator.reg::<RegisterDIV>().0, 0);
+
+    // Nothing happens until a button is pressed
+    let cycles = emulator.cycles;
+    for _ in 0..10 {
+        emulator.handle_next_instruction();
+    }
+    assert_eq!(emulator.cpu.state, CpuState::Stopped);
+    assert_eq!(emulator.cycles, cycles);
+    // byte after STOP is consumed
+    assert_eq!(emulator.cpu.program_counter.0, PROGRAM_START + 2);
+
+    emulator.reg_mut::<RegisterP1>().set_a_right(false);
+    emulator.handle_next_instruction();
+
+    assert_eq!(emulator.cpu.state, CpuState::Running);
+    assert_eq!(emulator.cpu.instruction_register, 0x3C);
+    assert_eq!(emulator.cpu.program_counter.0, PROGRAM_START + 3);
+}
+
+#[test]
+fn test_stop_speed_switch() {
+    // STOP, NOP, INC A
+    let mut emulator = emulator_with_program(&[0x10, 0x00, 0x3C]);
+    emulator.cgb_mode = true;
+    emulator.set(RegisterKEY1::ADDRESS, 0xFF);
+
+    assert!(emulator.reg::<RegisterKEY1>().get_switch_armed());
+    assert!(!emulator.reg::<RegisterKEY1>().get_current_speed());
+    assert_eq!(emulator.dots_per_cycle(), DOTS_PER_M_CYCLE);
+
+    emulator.handle_next_instruction();
+
+    assert_eq!(emulator.cpu.state, CpuState::Running);
+    assert!(emulator.double_speed);
+    assert!(!emulator.reg::<RegisterKEY1>().get_switch_armed());
+    assert!(emulator.reg::<RegisterKEY1>().get_current_speed());
+    assert_eq!(emulator.dots_per_cycle(), DOTS_PER_M_CYCLE_DOUBLE_SPEED);
+
+    // CPU is paused during the speed switch
+    for _ in 0..SPEED_SWITCH_DURATION - 1 {
+        emulator.handle_next_instruction();
+        assert_eq!(emulator.cpu.program_counter.0, PROGRAM_START + 2);
+    }
+    assert_eq!(emulator.reg::<RegisterDIV>().0, 0);
+
+    emulator.handle_next_instruction();
+    assert_eq!(emulator.speed_switch_delay, 0);
+    assert_eq!(emulator.cpu.instruction_register, 0x3C);
+    assert_eq!(emulator.cpu.program_counter.0, PROGRAM_START + 3);
+}
+
+#[test]
+fn test_stop_speed_switch_dmg() {
+    // STOP, NOP, INC A
+    let mut emulator = emulator_with_program(&[0x10, 0x00, 0x3C]);
+    emulator.set(RegisterKEY1::ADDRESS, 0xFF);
+
+    emulator.handle_next_instruction();
+
+    // Speed switch is not available in DMG mode
+    assert_eq!(emulator.cpu.state, CpuState::Stopped);
+    assert!(!emulator.double_speed);
+}
@@ -0,0 +1,139 @@
+use emulator::*;
+
+const SOURCE: u16 = 0xC000;
+const DESTINATION: u16 = 0x8100;
+
+/// CGB emulator with distinct data at the source and the transfer addresses
+/// set up
+fn cgb_emulator() -> Emulator {
+    let mut rom = Rom::new();
+    rom.data[ROM_ADDRESS_CGB_FLAG] = 0x80;
+    let mut emulator = Emulator::from_rom(rom);
+
+    for i in 0..0x100 {
+        emulator.set(SOURCE + i, (i as u8).wrapping_add(1));
+    }
+
+    emulator.set(RegisterHDMA1::ADDRESS, (SOURCE >> 8) as u8);
+    emulator.set(RegisterHDMA2::ADDRESS, SOURCE as u8);
+    // upper bits of the destination are ignored
+    emulator.set(RegisterHDMA3::ADDRESS, 0xE1);
+    emulator.set(RegisterHDMA4::ADDRESS, 0x0F);
+
+    emulator
+}
+
+/// Run until the CPU resumes, returns elapsed M-cycles
+fn run_transfer(emulator: &mut Emulator) -> usize {
+    emulator.run_until(|emulator| !emulator.is_cpu_suspended())
+}
+
+#[test]
+fn test_general_dma() {
+    let mut emulator = cgb_emulator();
+    emulator.reg_mut::<RegisterLCDC>().0 = 0;
+    assert_eq!(emulator.get(RegisterHDMA1::ADDRESS), 0xFF);
+
+    emulator.set(RegisterHDMA5::ADDRESS, 0x03);
+    assert!(emulator.is_cpu_suspended());
+    let program_counter = emulator.cpu.program_counter.0;
+
+    // 4 blocks, 8 M-cycles each
+    assert_eq!(run_transfer(&mut emulator), 32);
+    assert_eq!(emulator.cpu.program_counter.0, program_counter);
+
+    assert_eq!(emulator.get(DESTINATION), 0x01);
+    assert_eq!(emulator.get(DESTINATION + 0x3F), 0x40);
+    assert_eq!(emulator.get(DESTINATION + 0x40), 0x00);
+    assert_eq!(emulator.get(RegisterHDMA5::ADDRESS), 0xFF);
+
+    // next transfer continues from the last address
+    emulator.set(RegisterHDMA5::ADDRESS, 0x00);
+    run_transfer(&mut emulator);
+    assert_eq!(emulator.get(DESTINATION + 0x40), 0x41);
+}
+
+#[test]
+fn test_general_dma_double_speed() {
+    let mut emulator = cgb_emulator();
+    emulator.reg_mut::<RegisterLCDC>().0 = 0;
+    emulator.double_speed = true;
+
+    emulator.set(RegisterHDMA5::ADDRESS, 0x03);
+    assert_eq!(run_transfer(&mut emulator), 64);
+    assert_eq!(emulator.get(DESTINATION + 0x3F), 0x40);
+}
+
+#[test]
+fn test_general_dma_vram_bank() {
+    let mut emulator = cgb_emulator();
+    emulator.reg_mut::<RegisterLCDC>().0 = 0;
+    emulator.set(RegisterVBK::ADDRESS, 0x01);
+
+    emulator.set(RegisterHDMA5::ADDRESS, 0x00);
+    run_transfer(&mut emulator);
+
+    assert_eq!(emulator.get_banked(DESTINATION, 1), Some(&0x01));
+    assert_eq!(emulator.get_banked(DESTINATION, 0), Some(&0x00));
+}
+
+#[test]
+fn test_hblank_dma() {
+    let mut emulator = cgb_emulator();
+    emulator.run_until(|emulator| emulator.reg::<RegisterSTAT>().get_ppu_mode() == PpuMode::Mode2);
+
+    emulator.set(RegisterHDMA5::ADDRESS, 0x82);
+    assert_eq!(emulator.get(RegisterHDMA5::ADDRESS), 0x02);
+    assert!(!emulator.is_cpu_suspended());
+
+    // single block per HBlank
+    emulator.run_until(|emulator| emulator.is_cpu_suspended());
+    assert_eq!(
+        emulator.reg::<RegisterSTAT>().get_ppu_mode(),
+        PpuMode::Mode0
+    );
+    assert_eq!(run_transfer(&mut emulator), 8);
+    assert_eq!(emulator.get_banked(DESTINATION + 0x0F, 0), Some(&0x10));
+    assert_eq!(emulator.get_banked(DESTINATION + 0x10, 0), Some(&0x00));
+    assert_eq!(emulator.get(RegisterHDMA5::ADDRESS), 0x01);
+
+    emulator.run_until(|emulator| emulator.reg::<RegisterSTAT>().get_ppu_mode() == PpuMode::Mode2);
+    emulator.run_until(|emulator| emulator.reg::<RegisterSTAT>().get_ppu_mode() == PpuMode::Mode0);
+    run_transfer(&mut emulator);
+    assert_eq!(emulator.get_banked(DESTINATION + 0x1F, 0), Some(&0x20));
+    assert_eq!(emulator.get(RegisterHDMA5::ADDRESS), 0x00);
+
+    // cancel, bit 7 is set and the remaining length is kept
+    emulator.set(RegisterHDMA5::ADDRESS, 0x00);
+    assert_eq!(emulator.get(RegisterHDMA5::ADDRESS), 0x80);
+
+    emulator.run_until(|emulator| emulator.reg::<RegisterSTAT>().get_ppu_mode() == PpuMode::Mode2);
+    emulator.run_until(|emulator| emulator.reg::<RegisterSTAT>().get_ppu_mode() == PpuMode::Mode0);
+    assert!(!emulator.is_cpu_suspended());
+    assert_eq!(emulator.get_banked(DESTINATION + 0x20, 0), Some(&0x00));
+}
+
+#[test]
+fn test_hblank_dma_finish() {
+    let mut emulator = cgb_emulator();
+    emulator.reg_mut::<RegisterLCDC>().0 = 0;
+
+    // with LCD turned off the first block is copied right away
+    emulator.set(RegisterHDMA5::ADDRESS, 0x80);
+    assert!(emulator.is_cpu_suspended());
+    run_transfer(&mut emulator);
+
+    assert_eq!(emulator.get(DESTINATION + 0x0F), 0x10);
+    assert_eq!(emulator.get(RegisterHDMA5::ADDRESS), 0xFF);
+    assert_eq!(emulator.vram_dma.mode, None);
+}
+
+#[test]
+fn test_vram_dma_dmg_mode() {
+    let mut emulator = Emulator::from_rom(Rom::new());
+    emulator.reg_mut::<RegisterLCDC>().0 = 0;
+
+    emulator.set(RegisterHDMA5::ADDRESS, 0x00);
+    assert!(!emulator.is_cpu_suspended());
+    assert_eq!(emulator.get(RegisterHDMA5::ADDRESS), 0xFF);
+}
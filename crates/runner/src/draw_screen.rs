@@ -19,6 +19,28 @@ pub fn draw_tilemap_to_image(emulator: &Emulator, image: &mut Image, window: boo
     }
 }
 
+/// Draw the last frame rendered by the emulator
+pub fn draw_screen_to_image(emulator: &Emulator, image: &mut Image) {
+    for y in 0..SCREEN_HEIGHT {
+        for x in 0..SCREEN_WIDTH {
+            let pixel = emulator.screen.get_pixel(x, y);
+            let color = Color::from_rgba(
+                color_channel(pixel.r()),
+                color_channel(pixel.g()),
+                color_channel(pixel.b()),
+                255,
+            );
+
+            image.set_pixel(x as u32, y as u32, color);
+        }
+    }
+}
+
+/// Scale 5-bit color channel to 8 bits
+fn color_channel(value: u8) -> u8 {
+    (value << 3) | (value >> 2)
+}
+
 fn draw_tile_to_image(image: &mut Image, tile: &Tile, x: u32, y: u32) {
     for tile_y in 0..TILE_HEIGHT {
         for tile_x in 0..TILE_WIDTH {
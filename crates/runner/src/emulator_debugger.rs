@@ -59,7 +59,7 @@ impl EmulatorDebugger for SqliteDebugger {
         instruction: emulator::Instruction,
     ) {
         let machine_cycle = emulator.cycles;
-        let ime_flag = emulator.ime_flag as i32;
+        let ime_flag = emulator.cpu.ime_flag as i32;
 
         let reg_ie = emulator.reg::<RegisterIE>();
         let ie_v_blank = reg_ie.get_v_blank() as i32;
@@ -73,11 +73,11 @@ impl EmulatorDebugger for SqliteDebugger {
         let if_timer = reg_if.get_timer() as i32;
         let if_serial = reg_if.get_serial() as i32;
 
-        let a = emulator.accumulator_and_flags.high();
-        let f = emulator.accumulator_and_flags.low();
-        let bc = format!("{:04X}", emulator.register_bc.as_u16());
-        let de = format!("{:04X}", emulator.register_de.as_u16());
-        let hl = format!("{:04X}", emulator.register_hl.as_u16());
+        let a = emulator.cpu.accumulator_and_flags.high();
+        let f = emulator.cpu.accumulator_and_flags.low();
+        let bc = format!("{:04X}", emulator.cpu.register_bc.as_u16());
+        let de = format!("{:04X}", emulator.cpu.register_de.as_u16());
+        let hl = format!("{:04X}", emulator.cpu.register_hl.as_u16());
 
         let flag_zero = f & FLAG_ZERO;
         let flag_subtract = f & FLAG_SUBTRACT;
@@ -111,7 +111,7 @@ impl EmulatorDebugger for SqliteDebugger {
                     ) VALUES (
                         {machine_cycle},
                         {opcode},
-                        '{instruction:?}',
+                        '{instruction}',
                         {ime_flag},
                         {ie_v_blank},
                         {ie_lcd},
@@ -37,6 +37,10 @@ async fn main() {
     );
     let window_texture = Texture2D::from_image(&window_image);
 
+    let mut screen_image =
+        Image::gen_image_color(SCREEN_WIDTH as u16, SCREEN_HEIGHT as u16, MAGENTA);
+    let screen_texture = Texture2D::from_image(&screen_image);
+
     loop {
         if is_key_pressed(KeyCode::R) {
             state.emulator = Emulator::from_rom(rom.to_vec());
@@ -47,6 +51,7 @@ async fn main() {
 
         draw_tilemap_to_image(&state.emulator, &mut background_image, false);
         draw_tilemap_to_image(&state.emulator, &mut window_image, true);
+        draw_screen_to_image(&state.emulator, &mut screen_image);
 
         clear_background(WHITE);
 
@@ -73,6 +78,9 @@ async fn main() {
                 ..Default::default()
             },
         );
+
+        screen_texture.update(&screen_image);
+        draw_texture(&screen_texture, 0., 256., WHITE);
         next_frame().await
     }
 }